@@ -0,0 +1,94 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Api, CanonicalAddr, HumanAddr, StdResult};
+
+use secret_toolkit::utils::HandleCallback;
+
+use crate::contract::BLOCK_SIZE;
+
+/// a token held by a parent token
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ChildToken {
+    /// address of the child's SNIP-721 contract, or None if it is a token of this contract
+    pub contract: Option<HumanAddr>,
+    /// id of the child token
+    pub token_id: String,
+}
+
+impl ChildToken {
+    /// Returns StdResult<StoredChild> from creating a StoredChild from a ChildToken
+    ///
+    /// # Arguments
+    ///
+    /// * `api` - a reference to the Api used to convert human and canonical addresses
+    pub fn to_stored<A: Api>(&self, api: &A) -> StdResult<StoredChild> {
+        Ok(StoredChild {
+            contract: self
+                .contract
+                .as_ref()
+                .map(|c| api.canonical_address(c))
+                .transpose()?,
+            token_id: self.token_id.clone(),
+        })
+    }
+}
+
+/// stored child token
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct StoredChild {
+    /// address of the child's SNIP-721 contract, or None if it is a token of this contract
+    pub contract: Option<CanonicalAddr>,
+    /// id of the child token
+    pub token_id: String,
+}
+
+impl StoredChild {
+    /// Returns StdResult<ChildToken> from creating a ChildToken from a StoredChild
+    ///
+    /// # Arguments
+    ///
+    /// * `api` - a reference to the Api used to convert human and canonical addresses
+    pub fn to_display<A: Api>(&self, api: &A) -> StdResult<ChildToken> {
+        Ok(ChildToken {
+            contract: self
+                .contract
+                .as_ref()
+                .map(|c| api.human_address(c))
+                .transpose()?,
+            token_id: self.token_id.clone(),
+        })
+    }
+}
+
+/// the msg to include when sending tokens of a registered child contract to this contract with
+/// SendNft or BatchSendNft, naming the parent token they should be attached to
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct AttachChildMsg {
+    /// id of the parent token
+    pub parent_token_id: String,
+}
+
+/// the SNIP-721 handle messages this contract sends to child contracts
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Snip721HandleMsg {
+    /// register this contract's BatchReceiveNft so the child contract calls it when tokens
+    /// are sent to this contract
+    RegisterReceiveNft {
+        code_hash: String,
+        also_implements_batch_receive_nft: Option<bool>,
+        padding: Option<String>,
+    },
+    /// return a detached child to the parent's owner
+    TransferNft {
+        recipient: HumanAddr,
+        token_id: String,
+        memo: Option<String>,
+        padding: Option<String>,
+    },
+}
+
+impl HandleCallback for Snip721HandleMsg {
+    const BLOCK_SIZE: usize = BLOCK_SIZE;
+}
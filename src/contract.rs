@@ -2,43 +2,68 @@
 #![allow(warnings, unused)]
 
 use cosmwasm_std::{
-    from_slice, log, to_binary, Api, BankMsg, Binary, BlockInfo, CanonicalAddr, Coin, CosmosMsg,
-    Decimal, Env, Extern, HandleResponse, HandleResult, HumanAddr, InitResponse, InitResult,
-    Querier, QueryResult, ReadonlyStorage, StdError, StdResult, Storage, Uint128, WasmMsg,
+    from_binary, from_slice, log, to_binary, to_vec, Api, BankMsg, Binary, BlockInfo,
+    CanonicalAddr, Coin, CosmosMsg, Decimal, Env, Extern, HandleResponse, HandleResult, HumanAddr,
+    InitResponse, InitResult, Querier, QueryResult, ReadonlyStorage, StdError, StdResult, Storage,
+    Uint128, WasmMsg,
 };
 use cosmwasm_storage::{PrefixedStorage, ReadonlyPrefixedStorage};
 use num_traits::pow;
 use primitive_types::U256;
 use secret_toolkit::{
     permit::{validate, Permit, RevokedPermits},
-    utils::{pad_handle_result, pad_query_result},
+    utils::{pad_handle_result, pad_query_result, HandleCallback},
 };
 /// This contract implements SNIP-721 standard:
 /// https://github.com/SecretFoundation/SNIPs/blob/master/SNIP-721.md
 use std::{collections::HashSet, ops::Deref};
 
+use crate::composable::{AttachChildMsg, ChildToken, Snip721HandleMsg, StoredChild};
 use crate::expiration::Expiration;
 use crate::inventory::{Inventory, InventoryIter};
-use crate::mint_run::{SerialNumber, StoredMintRunInfo};
+use crate::launchpad::{
+    verify_merkle_proof, LazyListing, MintPhase, MintTemplate, MintVoucher, StoredLazyListing,
+    StoredPublicMint, StoredRevealCommitment,
+};
+use crate::mint_run::{
+    EditionOverride, MintRun, MintRunRef, SerialNumber, StoredEdition, StoredMintRun,
+    StoredMintRunInfo,
+};
+use crate::minters::{MinterInfo, StoredMinterRole};
 use crate::msg::{
-    AccessLevel, BatchNftDossierElement, Burn, ContractStatus, Cw721Approval, Cw721OwnerOfResponse,
-    HandleAnswer, HandleMsg, InitMsg, Mint, QueryAnswer, QueryMsg, QueryWithPermit, ReceiverInfo,
-    ResponseStatus::Success, SaleNum, SaleStatus, Send, Snip721Approval, TokenSaleInfo, Transfer,
-    ViewerInfo,
+    AccessLevel, BatchNftDossierElement, Bundle, Burn, ContractStatus, Cw721Approval,
+    Cw721OwnerOfResponse, DenomAccounting, HandleAnswer, HandleMsg, InitMsg, Mint, MintListing,
+    QueryAnswer, QueryMsg, QueryWithPermit, ReceiverInfo, ResponseStatus::Success, SaleNum,
+    SaleStatus, Send, Snip721Approval, TokenSaleInfo, TraitCount, Transfer, ViewerInfo,
 };
-use crate::rand::sha_256;
+use crate::rand::{sha_256, Prng};
 use crate::receiver::{batch_receive_nft_msg, receive_nft_msg};
-use crate::royalties::{Royalty, RoyaltyInfo, StoredRoyalty, StoredRoyaltyInfo};
+use crate::royalties::{RecipientRoyalty, Royalty, RoyaltyInfo, StoredRoyalty, StoredRoyaltyInfo};
 use crate::state::{
-    get_txs, json_load, json_may_load, json_save, load, may_load, remove, save, store_burn,
-    store_mint, store_transfer, AuthList, Config, Permission, PermissionType, ReceiveRegistration,
-    BLOCK_KEY, CONFIG_KEY, CREATOR_KEY, DEFAULT_ROYALTY_KEY, FOR_SALE_KEY, MINTERS_KEY,
-    MY_ADDRESS_KEY, PREFIX_ALL_PERMISSIONS, PREFIX_AUTHLIST, PREFIX_INFOS, PREFIX_MAP_TO_ID,
-    PREFIX_MAP_TO_INDEX, PREFIX_MINT_RUN, PREFIX_MINT_RUN_NUM, PREFIX_OWNER_PRIV, PREFIX_PRIV_META,
-    PREFIX_PUB_META, PREFIX_RECEIVERS, PREFIX_REVOKED_PERMITS, PREFIX_ROYALTY_INFO,
-    PREFIX_SALE_NUM, PREFIX_TOKEN_SALE_INFO, PREFIX_VIEW_KEY, PRNG_SEED_KEY, RECEIVED_NFT_KEY,
+    credit_fees, credit_proceeds, get_floor_price, get_metadata_history, get_proceeds,
+    get_royalty_tokens, get_token_mint_run, get_trait_count, get_trait_counts, get_trait_tokens,
+    get_txs, json_load, json_may_load, json_save, load, may_load, rarity_traits,
+    record_royalty_earnings, remove, save, set_token_mint_run, store_burn, store_metadata_revision,
+    store_mint, store_reissue, store_revoke, store_royalty_change, store_transfer, take_fees,
+    take_proceeds, update_floor_index, update_royalty_index, update_trait_index, AuthList, Config,
+    Permission, PermissionType, ReceiveRegistration, StoredAttributeUpdater, StoredBalance,
+    StoredBundle, StoredMetadataRevision, StoredSaleStats, ATTRIBUTE_UPDATERS_KEY, BLOCK_KEY,
+    BUNDLES_FOR_SALE_KEY, BUNDLE_COUNT_KEY, COLLECTION_STATS_KEY, CONFIG_KEY, CREATOR_KEY,
+    DEFAULT_ROYALTY_KEY, EARNED_FEES_KEY, ESCROW_KEY, FOR_SALE_KEY, LAZY_LISTINGS_KEY,
+    METADATA_FROZEN_KEY, MINTERS_KEY, MINT_RUN_COUNT_KEY, MY_ADDRESS_KEY, PREFIX_ALL_PERMISSIONS,
+    PREFIX_AUTHLIST, PREFIX_BUNDLED_TOKENS, PREFIX_BUNDLES, PREFIX_CHILDREN,
+    PREFIX_CHILD_CONTRACTS, PREFIX_CREATOR_RUNS, PREFIX_EDITIONS, PREFIX_EDITION_META,
+    PREFIX_FROZEN_METADATA, PREFIX_INFOS, PREFIX_LAZY_LISTINGS, PREFIX_MAP_TO_ID,
+    PREFIX_MAP_TO_INDEX, PREFIX_MINTER_ROLES, PREFIX_MINT_RUN, PREFIX_MINT_RUN_LOOKUP,
+    PREFIX_MINT_RUN_NUM, PREFIX_MINT_RUN_REGISTRY, PREFIX_MINT_RUN_STATS, PREFIX_MINT_RUN_TOKENS,
+    PREFIX_MINT_TEMPLATES, PREFIX_OWNER_PRIV, PREFIX_PHASE_MINTED, PREFIX_PRIV_META,
+    PREFIX_PUBLIC_MINTED, PREFIX_PUB_META, PREFIX_RECEIVERS, PREFIX_REVOKED_PERMITS,
+    PREFIX_ROYALTY_EARNINGS, PREFIX_ROYALTY_INFO, PREFIX_ROYALTY_TOTALS, PREFIX_SALE_NUM,
+    PREFIX_TOKEN_MINT_RUN, PREFIX_TOKEN_SALE_INFO, PREFIX_UNLOCKABLE, PREFIX_VIEW_KEY,
+    PREFIX_VOUCHER_NONCES, PREFIX_VOUCHER_SIGNERS, PRNG_SEED_KEY, PUBLIC_MINT_KEY,
+    RECEIVED_NFT_KEY, REVEAL_COMMITMENT_KEY,
 };
-use crate::token::{Metadata, Token};
+use crate::token::{Extension, Metadata, Token, Trait};
 use crate::viewing_key::{ViewingKey, VIEWING_KEY_SIZE};
 
 /// pad handle responses and log attributes to blocks of 256 bytes to prevent leaking info based on
@@ -46,6 +71,20 @@ use crate::viewing_key::{ViewingKey, VIEWING_KEY_SIZE};
 pub const BLOCK_SIZE: usize = 256;
 /// max number of token ids to keep in id list block
 pub const ID_BLOCK_SIZE: u32 = 64;
+/// denomination that tokens are sold in
+pub const SALE_DENOM: &str = "uscrt";
+/// percent of a sale (after royalties) kept by the marketplace
+pub const MARKETPLACE_FEE_PERCENT: u128 = 2;
+/// number of basis points in 100%
+pub const MAX_BPS: u16 = 10000;
+/// schemes that metadata urls must be prefixed with
+pub const URI_SCHEMES: [&str; 4] = ["http://", "https://", "ipfs://", "ar://"];
+/// file types a metadata media file may have
+pub const MEDIA_FILE_TYPES: [&str; 6] = ["image", "video", "audio", "text", "font", "application"];
+/// max number of attributes in a token's metadata
+pub const MAX_ATTRIBUTES: usize = 64;
+/// max number of characters in each field of a metadata attribute
+pub const MAX_TRAIT_LENGTH: usize = 256;
 
 ////////////////////////////////////// Init ///////////////////////////////////////
 /// Returns InitResult
@@ -76,6 +115,12 @@ pub fn init<S: Storage, A: Api, Q: Querier>(
         .unwrap_or(creator_raw);
     let prng_seed: Vec<u8> = sha_256(base64::encode(msg.entropy).as_bytes()).to_vec();
     let init_config = msg.config.unwrap_or_default();
+    let max_royalty_bps = init_config.max_royalty_bps.unwrap_or(MAX_BPS);
+    if max_royalty_bps > MAX_BPS {
+        return Err(StdError::generic_err(
+            "The maximum royalty can not exceed 10000 basis points",
+        ));
+    }
 
     let config = Config {
         name: msg.name,
@@ -92,6 +137,8 @@ pub fn init<S: Storage, A: Api, Q: Querier>(
         minter_may_update_metadata: init_config.minter_may_update_metadata.unwrap_or(true),
         owner_may_update_metadata: init_config.owner_may_update_metadata.unwrap_or(false),
         burn_is_enabled: init_config.enable_burn.unwrap_or(false),
+        max_royalty_bps,
+        sale_stats_are_public: init_config.public_sale_stats.unwrap_or(true),
     };
 
     let minters = vec![admin_raw];
@@ -108,6 +155,7 @@ pub fn init<S: Storage, A: Api, Q: Querier>(
             msg.royalty_info.as_ref(),
             None,
             DEFAULT_ROYALTY_KEY,
+            config.max_royalty_bps,
         )?;
     }
 
@@ -186,6 +234,8 @@ pub fn handle<S: Storage, A: Api, Q: Querier>(
             private_metadata,
             royalty_info,
             memo,
+            listing,
+            editions,
             ..
         } => mint_clones(
             deps,
@@ -199,6 +249,8 @@ pub fn handle<S: Storage, A: Api, Q: Querier>(
             private_metadata,
             royalty_info,
             memo,
+            listing,
+            editions,
         ),
         HandleMsg::SetMetadata {
             token_id,
@@ -214,6 +266,54 @@ pub fn handle<S: Storage, A: Api, Q: Querier>(
             public_metadata,
             private_metadata,
         ),
+        HandleMsg::FreezeMetadata { token_id, .. } => freeze_metadata(
+            deps,
+            env,
+            &config,
+            ContractStatus::StopTransactions.to_u8(),
+            &token_id,
+        ),
+        HandleMsg::SetUnlockable {
+            token_id,
+            unlockable,
+            ..
+        } => set_unlockable(
+            deps,
+            env,
+            &config,
+            ContractStatus::StopTransactions.to_u8(),
+            &token_id,
+            unlockable,
+        ),
+        HandleMsg::SetAttributeUpdater {
+            updater,
+            trait_types,
+            ..
+        } => set_attribute_updater(
+            deps,
+            env,
+            &config,
+            ContractStatus::StopTransactions.to_u8(),
+            &updater,
+            trait_types,
+        ),
+        HandleMsg::UpdateAttributes {
+            token_id,
+            public_attributes,
+            private_attributes,
+            ..
+        } => update_attributes(
+            deps,
+            env,
+            &config,
+            ContractStatus::StopTransactions.to_u8(),
+            &token_id,
+            public_attributes,
+            private_attributes,
+        ),
+        HandleMsg::FreezeAllMetadata { .. } => {
+            freeze_all_metadata(deps, env, &config, ContractStatus::StopTransactions.to_u8())
+        }
         HandleMsg::SetRoyaltyInfo {
             token_id,
             royalty_info,
@@ -221,7 +321,7 @@ pub fn handle<S: Storage, A: Api, Q: Querier>(
         } => set_royalty_info(
             deps,
             env,
-            &config,
+            &mut config,
             ContractStatus::StopTransactions.to_u8(),
             token_id.as_deref(),
             royalty_info.as_ref(),
@@ -401,6 +501,93 @@ pub fn handle<S: Storage, A: Api, Q: Querier>(
             ContractStatus::Normal.to_u8(),
             burns,
         ),
+        HandleMsg::RevokeNonTransferable { token_id, memo, .. } => revoke_non_transferable(
+            deps,
+            env,
+            &mut config,
+            ContractStatus::Normal.to_u8(),
+            token_id,
+            memo,
+        ),
+        HandleMsg::ReissueNonTransferable {
+            token_id,
+            new_owner,
+            memo,
+            ..
+        } => reissue_non_transferable(
+            deps,
+            env,
+            &mut config,
+            ContractStatus::Normal.to_u8(),
+            token_id,
+            &new_owner,
+            memo,
+        ),
+        HandleMsg::RegisterChildContract {
+            contract,
+            code_hash,
+            ..
+        } => register_child_contract(
+            deps,
+            env,
+            &config,
+            ContractStatus::StopTransactions.to_u8(),
+            &contract,
+            code_hash,
+        ),
+        HandleMsg::AttachChild {
+            parent_token_id,
+            child_token_id,
+            ..
+        } => attach_child(
+            deps,
+            env,
+            &mut config,
+            ContractStatus::Normal.to_u8(),
+            &parent_token_id,
+            child_token_id,
+        ),
+        HandleMsg::DetachChild {
+            parent_token_id,
+            child,
+            ..
+        } => detach_child(
+            deps,
+            env,
+            &mut config,
+            ContractStatus::Normal.to_u8(),
+            &parent_token_id,
+            child,
+        ),
+        HandleMsg::BatchReceiveNft {
+            from,
+            token_ids,
+            msg,
+            ..
+        } => receive_children(
+            deps,
+            env,
+            &config,
+            ContractStatus::Normal.to_u8(),
+            &from,
+            token_ids,
+            msg,
+            HandleAnswer::BatchReceiveNft { status: Success },
+        ),
+        HandleMsg::ReceiveNft {
+            sender,
+            token_id,
+            msg,
+        } => receive_children(
+            deps,
+            env,
+            &config,
+            ContractStatus::Normal.to_u8(),
+            &sender,
+            vec![token_id],
+            msg,
+            HandleAnswer::ReceiveNft { status: Success },
+        ),
         HandleMsg::CreateViewingKey { entropy, .. } => create_key(
             deps,
             env,
@@ -436,6 +623,24 @@ pub fn handle<S: Storage, A: Api, Q: Querier>(
             ContractStatus::StopTransactions.to_u8(),
             &minters,
         ),
+        HandleMsg::SetMinterRole {
+            minter,
+            may_update_metadata,
+            max_tokens,
+            allowed_mint_run_ids,
+            expiration,
+            ..
+        } => set_minter_role(
+            deps,
+            env,
+            &config,
+            ContractStatus::StopTransactions.to_u8(),
+            &minter,
+            may_update_metadata,
+            max_tokens,
+            allowed_mint_run_ids,
+            expiration,
+        ),
         HandleMsg::ChangeAdmin { address, .. } => change_admin(
             deps,
             env,
@@ -446,6 +651,9 @@ pub fn handle<S: Storage, A: Api, Q: Querier>(
         HandleMsg::SetContractStatus { level, .. } => {
             set_contract_status(deps, env, &mut config, level)
         }
+        HandleMsg::SetMaxRoyalty {
+            max_royalty_bps, ..
+        } => set_max_royalty(deps, env, &mut config, max_royalty_bps),
         HandleMsg::RevokePermit { permit_name, .. } => {
             revoke_permit(&mut deps.storage, &env.message.sender, &permit_name)
         }
@@ -462,6 +670,33 @@ pub fn handle<S: Storage, A: Api, Q: Querier>(
             sale_status,
             price,
         ),
+        HandleMsg::ListBundle {
+            token_ids,
+            price,
+            token_prices,
+        } => list_bundle(
+            deps,
+            env,
+            &config,
+            ContractStatus::StopTransactions.to_u8(),
+            token_ids,
+            price,
+            token_prices,
+        ),
+        HandleMsg::BuyBundle { bundle_id } => buy_bundle(
+            deps,
+            env,
+            &mut config,
+            ContractStatus::StopTransactions.to_u8(),
+            bundle_id,
+        ),
+        HandleMsg::CancelBundle { bundle_id } => cancel_bundle(
+            deps,
+            env,
+            &config,
+            ContractStatus::StopTransactions.to_u8(),
+            bundle_id,
+        ),
         HandleMsg::SetPrice { token_id, price } => set_price(
             deps,
             env,
@@ -470,6 +705,52 @@ pub fn handle<S: Storage, A: Api, Q: Querier>(
             &token_id,
             price,
         ),
+        HandleMsg::CreateLazyListing {
+            listing_id,
+            mint_run_id,
+            price,
+            editions,
+            public_metadata,
+            private_metadata,
+            royalty_info,
+            ..
+        } => create_lazy_listing(
+            deps,
+            env,
+            &config,
+            ContractStatus::Normal.to_u8(),
+            listing_id,
+            mint_run_id.as_ref(),
+            price,
+            editions,
+            public_metadata,
+            private_metadata,
+            royalty_info,
+        ),
+        HandleMsg::CancelLazyListing { listing_id, .. } => cancel_lazy_listing(
+            deps,
+            env,
+            &config,
+            ContractStatus::StopTransactions.to_u8(),
+            &listing_id,
+        ),
+        HandleMsg::SetVoucherSigner { pubkey, .. } => set_voucher_signer(
+            deps,
+            env,
+            &config,
+            ContractStatus::StopTransactions.to_u8(),
+            pubkey,
+        ),
+        HandleMsg::RedeemMintVoucher {
+            voucher, signature, ..
+        } => redeem_mint_voucher(
+            deps,
+            env,
+            &mut config,
+            ContractStatus::Normal.to_u8(),
+            voucher,
+            signature,
+        ),
         HandleMsg::BuyToken { token_id } => buy_token(
             deps,
             env,
@@ -484,12 +765,80 @@ pub fn handle<S: Storage, A: Api, Q: Querier>(
             ContractStatus::WithdrawFunds.to_u8(),
             receiver_addr,
         ),
+        HandleMsg::SetPublicMint {
+            enabled,
+            price,
+            max_supply,
+            per_wallet_limit,
+            random_assignment,
+            ..
+        } => set_public_mint(
+            deps,
+            env,
+            &config,
+            enabled,
+            price,
+            max_supply,
+            per_wallet_limit,
+            random_assignment,
+        ),
+        HandleMsg::AddMintTemplates { templates, .. } => {
+            add_mint_templates(deps, env, &config, templates)
+        }
+        HandleMsg::SetMintPhases { phases, .. } => set_mint_phases(deps, env, &config, phases),
+        HandleMsg::CommitReveal {
+            metadata_hash,
+            seed_hash,
+            supply,
+            ..
+        } => commit_reveal(deps, env, &config, metadata_hash, seed_hash, supply),
+        HandleMsg::RevealCollection { seed, limit, .. } => reveal_collection(
+            deps,
+            env,
+            &config,
+            ContractStatus::StopTransactions.to_u8(),
+            seed,
+            limit,
+        ),
+        HandleMsg::PublicMint {
+            quantity,
+            merkle_proof,
+            entropy,
+            ..
+        } => public_mint(
+            deps,
+            env,
+            &mut config,
+            ContractStatus::Normal.to_u8(),
+            quantity,
+            merkle_proof,
+            entropy,
+        ),
+        HandleMsg::ClaimProceeds { denom, .. } => claim_proceeds(
+            deps,
+            env,
+            &config,
+            ContractStatus::WithdrawFunds.to_u8(),
+            denom,
+        ),
     };
     pad_handle_result(response, BLOCK_SIZE)
 }
 
+/// Returns HandleResult
+///
+/// sends the marketplace fees earned from sales to the specified address.  Funds held on
+/// behalf of others, such as unclaimed proceeds, are never withdrawn
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a reference to the Config
+/// * `priority` - u8 representation of highest status level this action is permitted at
+/// * `receiver_addr` - address the fees should be sent to
 pub fn withdraw_funds<S: Storage, A: Api, Q: Querier>(
-    deps: &Extern<S, A, Q>,
+    deps: &mut Extern<S, A, Q>,
     env: Env,
     config: &Config,
     priority: u8,
@@ -497,21 +846,34 @@ pub fn withdraw_funds<S: Storage, A: Api, Q: Querier>(
 ) -> HandleResult {
     check_status(config.status, priority)?;
     let sender_raw = deps.api.canonical_address(&env.message.sender)?;
-    let this_contract = (env.contract.address).clone();
     if config.admin != sender_raw {
         return Err(StdError::generic_err(
             "This is an admin command and can only be run from the admin address",
         ));
     }
-    let contract_bal = deps.querier.query_balance(this_contract.clone(), "uscrt")?;
-    let _amount = contract_bal.amount;
+    let fees: Vec<Coin> = take_fees(&mut deps.storage)?
+        .into_iter()
+        .map(|b| b.into_coin())
+        .collect();
+    if fees.is_empty() {
+        return Err(StdError::generic_err(
+            "There are no earned fees to withdraw",
+        ));
+    }
+    let mut logs = Vec::new();
+    for fee in fees.iter() {
+        logs.push(log(
+            "Funds withdrawn",
+            format!("{}{}", fee.amount, fee.denom),
+        ));
+    }
     Ok(HandleResponse {
         messages: vec![CosmosMsg::Bank(BankMsg::Send {
-            from_address: this_contract,
+            from_address: env.contract.address,
             to_address: receiver_addr,
-            amount: vec![contract_bal],
+            amount: fees,
         })],
-        log: vec![log("Funds withdrawn", _amount)],
+        log: logs,
         data: Some(to_binary(&HandleAnswer::WithdrawFunds { status: Success })?),
     })
 }
@@ -538,16 +900,21 @@ pub fn buy_token<S: Storage, A: Api, Q: Querier>(
     let buyer = (&env.message.sender).clone();
     let buyer_raw = deps.api.canonical_address(&env.message.sender)?;
     let mut price_info: Vec<Coin> = Vec::new();
-    let this_contract = (env.contract.address).clone();
     let mut token_value: Vec<Coin> = Vec::new();
-    let mut decimal_places: u8;
-    let mut rate: u16;
-    let mut royalties: Vec<StoredRoyalty> = Vec::new();
 
     // check if token_id exists
     let map2idx = PrefixedStorage::new(PREFIX_MAP_TO_INDEX, &mut deps.storage);
     let may_exist: Option<u32> = may_load(&map2idx, token_id.as_bytes())?;
 
+    // the editions of a lazy listing are minted when they are bought
+    if may_exist.is_none() {
+        let lazy_store = ReadonlyPrefixedStorage::new(PREFIX_LAZY_LISTINGS, &deps.storage);
+        let may_lazy: Option<StoredLazyListing> = may_load(&lazy_store, token_id.as_bytes())?;
+        if let Some(listing) = may_lazy {
+            return buy_lazy_listing(deps, env, config, token_id, listing);
+        }
+    }
+
     let err_msg = format!(
         "You are not authorized to perform this action on token {}",
         token_id
@@ -565,6 +932,10 @@ pub fn buy_token<S: Storage, A: Api, Q: Querier>(
     let seller = deps.api.human_address(&seller_raw)?;
     let (ptoken, pidx) = get_sale_info(&mut deps.storage, token_id, opt_err)?;
     let token_key = idx.to_le_bytes();
+    let denom = ptoken
+        .denom
+        .clone()
+        .unwrap_or_else(|| SALE_DENOM.to_string());
 
     // if token exists make your checks
     if may_exist.is_some() {
@@ -589,26 +960,30 @@ pub fn buy_token<S: Storage, A: Api, Q: Querier>(
             ));
         }
 
+        // check if the listing has expired
+        if ptoken.expiration.is_some_and(|e| e.is_expired(&env.block)) {
+            return Err(StdError::generic_err("This listing has expired"));
+        }
+
         // check if token has appropriate price set
         let tprice: Option<u32> = Some((ptoken.token_price).unwrap());
         if tprice == None || tprice == Some(0) {
             return Err(StdError::generic_err("Invalid price. Set price of token"));
         }
 
-        let tprice_int = tprice.unwrap();
         token_value.push(Coin {
-            denom: "uscrt".to_string(),
+            denom: denom.clone(),
             amount: Uint128(u128::from(tprice.unwrap())),
         });
 
         price_info.push(Coin {
-            denom: "uscrt".to_string(),
+            denom: denom.clone(),
             amount: Uint128(u128::from(tprice.unwrap())),
         });
 
         if env.message.sent_funds.len() != 1
             || env.message.sent_funds[0].amount < token_value[0].amount
-            || env.message.sent_funds[0].denom != String::from("uscrt")
+            || env.message.sent_funds[0].denom != denom
         {
             return Err(StdError::generic_err("Insufficient funds provided"));
         }
@@ -625,120 +1000,35 @@ pub fn buy_token<S: Storage, A: Api, Q: Querier>(
         let _m = send_list(deps, &env, config, &seller_raw, transfers, None)?;
     }
 
-    let x = &this_contract;
-    /* all deductions to be made from this original value */
-    let original = token_value[0].amount.clone();
-    /* stores final amount sent to seller */
-    let mut coins = token_value[0].amount.clone();
-    let roy_store = ReadonlyPrefixedStorage::new(PREFIX_ROYALTY_INFO, &deps.storage);
-    let default_roy: Option<StoredRoyaltyInfo> =
-        may_load(&deps.storage, DEFAULT_ROYALTY_KEY).unwrap();
-    let may_roy_inf: Option<StoredRoyaltyInfo> = may_load(&roy_store, &token_key).unwrap();
-    let mut all_transfers: Vec<CosmosMsg> = Vec::new();
-
     let num: i32 = get_sale_num_type(&deps.storage, &token_key);
-
-    // if this is secondary sale then deduct royalty amount if configured
-    if num == 1 {
-        // get the royalty information if present
-        if may_roy_inf.is_none() && default_roy.is_some() {
-            decimal_places = default_roy.as_ref().unwrap().decimal_places_in_rates;
-            royalties = default_roy.unwrap().royalties.clone();
-            for royalty in royalties.iter() {
-                let seller_ = deps.api.human_address(&royalty.recipient).unwrap();
-                rate = royalty.rate;
-                let after_royalty_ded = original
-                    * Decimal::from_ratio(
-                        rate as u128,
-                        pow(10 as u128, usize::from(decimal_places)),
-                    );
-                let royalty_amount = (original - after_royalty_ded).unwrap();
-
-                all_transfers.push(CosmosMsg::Bank(BankMsg::Send {
-                    from_address: x.clone(),
-                    to_address: seller_,
-                    amount: vec![Coin {
-                        denom: "uscrt".to_string(),
-                        amount: after_royalty_ded,
-                    }],
-                }));
-
-                all_transfers.push(CosmosMsg::Bank(BankMsg::Send {
-                    from_address: x.clone(),
-                    to_address: seller.clone(),
-                    amount: vec![Coin {
-                        denom: "uscrt".to_string(),
-                        amount: royalty_amount * Decimal::percent(98),
-                    }],
-                }));
-            }
-        } else if may_roy_inf.is_some() {
-            decimal_places = may_roy_inf.as_ref().unwrap().decimal_places_in_rates;
-            royalties = may_roy_inf.unwrap().royalties.clone();
-            for royalty in royalties.iter() {
-                let seller_ = deps.api.human_address(&royalty.recipient).unwrap();
-                rate = royalty.rate;
-                let after_royalty_ded = original
-                    * Decimal::from_ratio(
-                        rate as u128,
-                        pow(10 as u128, usize::from(decimal_places)),
-                    );
-                let royalty_amount = (original - after_royalty_ded).unwrap();
-
-                all_transfers.push(CosmosMsg::Bank(BankMsg::Send {
-                    from_address: x.clone(),
-                    to_address: seller_,
-                    amount: vec![Coin {
-                        denom: "uscrt".to_string(),
-                        amount: after_royalty_ded,
-                    }],
-                }));
-
-                all_transfers.push(CosmosMsg::Bank(BankMsg::Send {
-                    from_address: x.clone(),
-                    to_address: seller.clone(),
-                    amount: vec![Coin {
-                        denom: "uscrt".to_string(),
-                        amount: royalty_amount * Decimal::percent(98),
-                    }],
-                }));
-            }
-        } else if may_roy_inf.is_none() && default_roy.is_none() {
-            // deduct marketplace fee from total amount
-            coins = original * Decimal::percent(98);
-            token_value[0].amount = coins;
-
-            all_transfers.push(CosmosMsg::Bank(BankMsg::Send {
-                from_address: x.clone(),
-                to_address: seller,
-                amount: vec![Coin {
-                    denom: "uscrt".to_string(),
-                    amount: token_value[0].amount,
-                }],
-            }));
-        }
-    }
-    /* when its the primary sale of token*/
-    else if num == 0 {
-        // deduct marketplace fee from total amount
-        coins = original * Decimal::percent(98);
-        token_value[0].amount = coins;
-
-        all_transfers.push(CosmosMsg::Bank(BankMsg::Send {
-            from_address: this_contract,
-            to_address: seller.clone(),
-            amount: vec![Coin {
-                denom: "uscrt".to_string(),
-                amount: token_value[0].amount,
-            }],
-        }));
+    // royalties are only paid on secondary sales
+    let royalties = if num == 1 {
+        effective_royalties(&deps.storage, &token_key)?
+    } else {
         set_sale_num(deps, &token_key, 1);
+        None
+    };
+    let price = token_value[0].amount.u128();
+    let royalties_paid = royalties
+        .map(|r| credit_royalties(&mut deps.storage, &token_key, &r, &denom, price))
+        .transpose()?
+        .unwrap_or(0);
+    // the marketplace fee is deducted from what is left after royalties
+    credit_sale_proceeds(
+        &mut deps.storage,
+        &seller_raw,
+        &denom,
+        price.saturating_sub(royalties_paid),
+    )?;
+    // sale stats are kept in uscrt
+    if denom == SALE_DENOM {
+        record_sale_stats(&mut deps.storage, idx, price)?;
     }
 
-    revert_sale_status(deps, env, config, token_id);
+    revert_sale_status(deps, env, config, token_id)?;
 
     Ok(HandleResponse {
-        messages: all_transfers,
+        messages: vec![],
         log: vec![log("Token sold", &token_id)],
         data: Some(to_binary(&HandleAnswer::BuyToken {
             token_id: token_id.to_string(),
@@ -747,54 +1037,469 @@ pub fn buy_token<S: Storage, A: Api, Q: Querier>(
     })
 }
 
-fn get_sale_num_type<S: ReadonlyStorage>(storage: &S, token_key: &[u8]) -> (i32) {
-    let sale_num = ReadonlyPrefixedStorage::new(PREFIX_SALE_NUM, storage);
-    let num = load(&sale_num, token_key).unwrap();
-    return num;
-}
-
-fn set_sale_num<S: Storage, A: Api, Q: Querier>(
-    deps: &mut Extern<S, A, Q>,
+/// Returns StdResult<u128> of the total royalties paid out of a sale after crediting each
+/// royalty recipient's share to the proceeds ledger
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `token_key` - the storage key of the token that was sold
+/// * `royalties` - a reference to the royalties to pay out of this sale
+/// * `denom` - denomination the sale was paid in
+/// * `price` - the sale price
+fn credit_royalties<S: Storage>(
+    storage: &mut S,
     token_key: &[u8],
-    value: i32,
-) -> () {
-    let mut sale_num = PrefixedStorage::new(PREFIX_SALE_NUM, &mut deps.storage);
-    save(&mut sale_num, token_key, &value);
+    royalties: &StoredRoyaltyInfo,
+    denom: &str,
+    price: u128,
+) -> StdResult<u128> {
+    let mut paid = 0u128;
+    // store_royalties guarantees the denominator fits and the rates do not exceed 100%
+    let royalty_den = U256::from(10).pow(U256::from(royalties.decimal_places_in_rates));
+    for royalty in royalties.royalties.iter() {
+        let amount = (U256::from(price) * U256::from(royalty.rate) / royalty_den).as_u128();
+        credit_proceeds(storage, &royalty.recipient, denom, amount)?;
+        record_royalty_earnings(storage, &royalty.recipient, token_key, denom, amount)?;
+        paid += amount;
+    }
+    Ok(paid)
 }
 
-/// Reverts a token's sale status back to not for sale and price to 0
+/// Returns StdResult<()>
+///
+/// splits what is left of a sale after royalties between the marketplace fee and the seller,
+/// and credits both shares to be claimed later
 ///
 /// # Arguments
 ///
-/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
-/// * `env` - Env of contract's environment
-/// * `config` - a mutable reference to the Config
-/// * `token_id` - optional token id, if not specified, use token index
-fn revert_sale_status<S: Storage, A: Api, Q: Querier>(
-    deps: &mut Extern<S, A, Q>,
-    env: Env,
-    config: &mut Config,
-    token_id: &str,
-) -> () {
-    let (ptoken, pidx) = get_sale_info(&deps.storage, token_id, None).unwrap();
-    let token_key = pidx.to_le_bytes();
-    let mut sale_store = PrefixedStorage::new(PREFIX_TOKEN_SALE_INFO, &mut deps.storage);
-    let mut token_store: TokenSaleInfo = json_load(&sale_store, &token_key).unwrap();
-    token_store.sale_status = SaleStatus::NotForSale;
-    token_store.token_price = Some(0);
-    json_save(&mut sale_store, &token_key, &token_store);
-
-    let mut for_sale: Vec<String> = load(&deps.storage, FOR_SALE_KEY).unwrap_or_default();
-    let find_v = token_id.to_string();
-    if for_sale.iter().find(|&s| *s == find_v).is_some() {
-        for_sale.retain(|id| *id != find_v);
-        save(&mut deps.storage, FOR_SALE_KEY, &for_sale);
-    }
+/// * `storage` - a mutable reference to the contract's storage
+/// * `seller` - a reference to the seller's address
+/// * `denom` - denomination the sale was paid in
+/// * `amount` - amount of the sale left after royalties
+fn credit_sale_proceeds<S: Storage>(
+    storage: &mut S,
+    seller: &CanonicalAddr,
+    denom: &str,
+    amount: u128,
+) -> StdResult<()> {
+    let seller_share = amount * (100 - MARKETPLACE_FEE_PERCENT) / 100;
+    credit_fees(storage, denom, amount - seller_share)?;
+    credit_proceeds(storage, seller, denom, seller_share)
 }
 
-/// Returns HandleResult
+/// Returns StdResult<()>
 ///
-/// Sets or updates token price
+/// adds a sale to the stats of the collection and of the mint run the token belongs to
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `token_idx` - the index of the token that was sold
+/// * `price` - the sale price
+fn record_sale_stats<S: Storage>(storage: &mut S, token_idx: u32, price: u128) -> StdResult<()> {
+    let mut stats: StoredSaleStats = may_load(storage, COLLECTION_STATS_KEY)?.unwrap_or_default();
+    stats.record_sale(price)?;
+    save(storage, COLLECTION_STATS_KEY, &stats)?;
+    // run numbers restart for every mint run ID, so stats are kept by registry index
+    if let Some(reg_idx) = get_token_mint_run(storage, token_idx)? {
+        let mut stats_store = PrefixedStorage::new(PREFIX_MINT_RUN_STATS, storage);
+        let run_key = reg_idx.to_le_bytes();
+        let mut stats: StoredSaleStats = may_load(&stats_store, &run_key)?.unwrap_or_default();
+        stats.record_sale(price)?;
+        save(&mut stats_store, &run_key, &stats)?;
+    }
+    Ok(())
+}
+
+/// Returns HandleResult
+///
+/// lists several tokens to be sold together for a single price, locking them until the
+/// bundle is bought or cancelled
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a reference to the Config
+/// * `priority` - u8 representation of highest status level this action is permitted at
+/// * `token_ids` - ids of the tokens in the bundle
+/// * `price` - price of the whole bundle in uscrt
+/// * `token_prices` - optional price of each token in uscrt
+pub fn list_bundle<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &Config,
+    priority: u8,
+    token_ids: Vec<String>,
+    price: u32,
+    token_prices: Option<Vec<u32>>,
+) -> HandleResult {
+    check_status(config.status, priority)?;
+    if price == 0 {
+        return Err(StdError::generic_err(
+            "Invalid input! Price cannot be set to 0.",
+        ));
+    }
+    if token_ids.len() < 2 {
+        return Err(StdError::generic_err(
+            "A bundle must include at least two tokens",
+        ));
+    }
+    if let Some(prices) = token_prices.as_ref() {
+        if prices.len() != token_ids.len() {
+            return Err(StdError::generic_err(
+                "A price must be given for each token in the bundle",
+            ));
+        }
+        if prices.iter().map(|p| u64::from(*p)).sum::<u64>() != u64::from(price) {
+            return Err(StdError::generic_err(
+                "The token prices must add up to the price of the bundle",
+            ));
+        }
+    }
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    let mut indices: Vec<u32> = Vec::new();
+    for token_id in token_ids.iter() {
+        let err_msg = format!(
+            "You are not authorized to perform this action on token {}",
+            token_id
+        );
+        // if token supply is private, don't leak that the token id does not exist
+        // instead just say they are not authorized for that token
+        let opt_err = if config.token_supply_is_public {
+            None
+        } else {
+            Some(&*err_msg)
+        };
+        let (token, idx) = get_token(&deps.storage, token_id, opt_err)?;
+        if token.owner != sender_raw {
+            return Err(StdError::generic_err(err_msg));
+        }
+        if indices.contains(&idx) {
+            return Err(StdError::generic_err(format!(
+                "Token {} is included in the bundle more than once",
+                token_id
+            )));
+        }
+        if !token.transferable {
+            return Err(StdError::generic_err(format!(
+                "Non-transferable token {} can not be sold",
+                token_id
+            )));
+        }
+        let sale_store = ReadonlyPrefixedStorage::new(PREFIX_TOKEN_SALE_INFO, &deps.storage);
+        let sale_info: Option<TokenSaleInfo> = json_may_load(&sale_store, &idx.to_le_bytes())?;
+        if sale_info.is_some_and(|s| s.sale_status == SaleStatus::ForSale) {
+            return Err(StdError::generic_err(format!(
+                "Token {} is already listed for sale on its own",
+                token_id
+            )));
+        }
+        check_not_bundled(&deps.storage, idx, token_id)?;
+        indices.push(idx);
+    }
+    let bundle_id: u32 = may_load(&deps.storage, BUNDLE_COUNT_KEY)?.unwrap_or(0);
+    save(&mut deps.storage, BUNDLE_COUNT_KEY, &(bundle_id + 1))?;
+    let mut bundled_store = PrefixedStorage::new(PREFIX_BUNDLED_TOKENS, &mut deps.storage);
+    for idx in indices.iter() {
+        save(&mut bundled_store, &idx.to_le_bytes(), &bundle_id)?;
+    }
+    let bundle = StoredBundle {
+        seller: sender_raw,
+        token_ids,
+        price,
+        token_prices,
+    };
+    let mut bundle_store = PrefixedStorage::new(PREFIX_BUNDLES, &mut deps.storage);
+    save(&mut bundle_store, &bundle_id.to_le_bytes(), &bundle)?;
+    let mut for_sale: Vec<u32> = may_load(&deps.storage, BUNDLES_FOR_SALE_KEY)?.unwrap_or_default();
+    for_sale.push(bundle_id);
+    save(&mut deps.storage, BUNDLES_FOR_SALE_KEY, &for_sale)?;
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![log("Bundle listed", bundle_id)],
+        data: Some(to_binary(&HandleAnswer::ListBundle { bundle_id })?),
+    })
+}
+
+/// Returns HandleResult
+///
+/// buys all the tokens of a bundle, paying each token's royalties on its listed token price,
+/// or on an even split of the bundle price if no token prices were listed
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a mutable reference to the Config
+/// * `priority` - u8 representation of highest status level this action is permitted at
+/// * `bundle_id` - id of the bundle
+pub fn buy_bundle<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &mut Config,
+    priority: u8,
+    bundle_id: u32,
+) -> HandleResult {
+    check_status(config.status, priority)?;
+    let buyer_raw = deps.api.canonical_address(&env.message.sender)?;
+    let bundle = load_bundle(&deps.storage, bundle_id)?;
+    if bundle.seller == buyer_raw {
+        return Err(StdError::generic_err(
+            "Bundle seller cannot be the buyer of the bundle",
+        ));
+    }
+    let price = u128::from(bundle.price);
+    if env.message.sent_funds.len() != 1
+        || env.message.sent_funds[0].amount.u128() < price
+        || env.message.sent_funds[0].denom != SALE_DENOM
+    {
+        return Err(StdError::generic_err("Insufficient funds provided"));
+    }
+    if env.message.sent_funds[0].amount.u128() > price {
+        return Err(StdError::generic_err("Funds sent exceeds funds needed"));
+    }
+    // unlock the tokens so they can be transferred to the buyer
+    unlock_bundle(&mut deps.storage, bundle_id, &bundle)?;
+    let transfers = Some(vec![Transfer {
+        recipient: env.message.sender.clone(),
+        token_ids: bundle.token_ids.clone(),
+        memo: None,
+    }]);
+    let _m = send_list(deps, &env, config, &bundle.seller, transfers, None)?;
+
+    // each token is treated as selling for its listed price, or an even share of the bundle
+    let num_tokens = bundle.token_ids.len() as u128;
+    let share = price / num_tokens;
+    let mut royalties_paid = 0u128;
+    for (i, token_id) in bundle.token_ids.iter().enumerate() {
+        let (_token, idx) = get_token(&deps.storage, token_id, None)?;
+        let token_key = idx.to_le_bytes();
+        let token_price = if let Some(prices) = bundle.token_prices.as_ref() {
+            u128::from(prices[i])
+        } else if i == 0 {
+            // the first token takes whatever can not be split evenly
+            share + price % num_tokens
+        } else {
+            share
+        };
+        // royalties are only paid on secondary sales
+        let royalties = if get_sale_num_type(&deps.storage, &token_key) == 1 {
+            effective_royalties(&deps.storage, &token_key)?
+        } else {
+            set_sale_num(deps, &token_key, 1);
+            None
+        };
+        royalties_paid += royalties
+            .map(|r| credit_royalties(&mut deps.storage, &token_key, &r, SALE_DENOM, token_price))
+            .transpose()?
+            .unwrap_or(0);
+        record_sale_stats(&mut deps.storage, idx, token_price)?;
+    }
+    // the marketplace fee is deducted from what is left after royalties
+    credit_sale_proceeds(
+        &mut deps.storage,
+        &bundle.seller,
+        SALE_DENOM,
+        price.saturating_sub(royalties_paid),
+    )?;
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![log("Bundle sold", bundle_id)],
+        data: Some(to_binary(&HandleAnswer::BuyBundle {
+            bundle_id,
+            price: vec![Coin {
+                denom: SALE_DENOM.to_string(),
+                amount: Uint128(price),
+            }],
+        })?),
+    })
+}
+
+/// Returns HandleResult
+///
+/// takes a bundle off the market and unlocks its tokens
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a reference to the Config
+/// * `priority` - u8 representation of highest status level this action is permitted at
+/// * `bundle_id` - id of the bundle
+pub fn cancel_bundle<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &Config,
+    priority: u8,
+    bundle_id: u32,
+) -> HandleResult {
+    check_status(config.status, priority)?;
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    let bundle = load_bundle(&deps.storage, bundle_id)?;
+    if bundle.seller != sender_raw {
+        return Err(StdError::generic_err(format!(
+            "Only the seller can cancel bundle {}",
+            bundle_id
+        )));
+    }
+    unlock_bundle(&mut deps.storage, bundle_id, &bundle)?;
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![log("Bundle cancelled", bundle_id)],
+        data: Some(to_binary(&HandleAnswer::CancelBundle { bundle_id })?),
+    })
+}
+
+/// Returns StdResult<StoredBundle> of a bundle that is for sale
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `bundle_id` - id of the bundle
+fn load_bundle<S: ReadonlyStorage>(storage: &S, bundle_id: u32) -> StdResult<StoredBundle> {
+    let bundle_store = ReadonlyPrefixedStorage::new(PREFIX_BUNDLES, storage);
+    may_load(&bundle_store, &bundle_id.to_le_bytes())?
+        .ok_or_else(|| StdError::generic_err(format!("Bundle {} is not for sale", bundle_id)))
+}
+
+/// Returns StdResult<()> after removing a bundle listing and unlocking its tokens
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `bundle_id` - id of the bundle
+/// * `bundle` - a reference to the bundle
+fn unlock_bundle<S: Storage>(
+    storage: &mut S,
+    bundle_id: u32,
+    bundle: &StoredBundle,
+) -> StdResult<()> {
+    for token_id in bundle.token_ids.iter() {
+        let (_token, idx) = get_token(storage, token_id, None)?;
+        let mut bundled_store = PrefixedStorage::new(PREFIX_BUNDLED_TOKENS, storage);
+        remove(&mut bundled_store, &idx.to_le_bytes());
+    }
+    let mut bundle_store = PrefixedStorage::new(PREFIX_BUNDLES, storage);
+    remove(&mut bundle_store, &bundle_id.to_le_bytes());
+    let mut for_sale: Vec<u32> = may_load(storage, BUNDLES_FOR_SALE_KEY)?.unwrap_or_default();
+    for_sale.retain(|id| *id != bundle_id);
+    save(storage, BUNDLES_FOR_SALE_KEY, &for_sale)
+}
+
+/// Returns StdResult<()> that is an error if the token is locked in a bundle listing
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `idx` - the token's index
+/// * `token_id` - token id string slice
+fn check_not_bundled<S: ReadonlyStorage>(storage: &S, idx: u32, token_id: &str) -> StdResult<()> {
+    let bundled_store = ReadonlyPrefixedStorage::new(PREFIX_BUNDLED_TOKENS, storage);
+    if let Some(bundle_id) = may_load::<u32, _>(&bundled_store, &idx.to_le_bytes())? {
+        return Err(StdError::generic_err(format!(
+            "Token {} is locked in bundle {}",
+            token_id, bundle_id
+        )));
+    }
+    Ok(())
+}
+
+/// Returns HandleResult
+///
+/// sends the message sender all of their unclaimed proceeds of the specified denomination
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a reference to the Config
+/// * `priority` - u8 representation of highest status level this action is permitted at
+/// * `denom` - denomination of the proceeds to claim
+pub fn claim_proceeds<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &Config,
+    priority: u8,
+    denom: String,
+) -> HandleResult {
+    check_status(config.status, priority)?;
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    let amount = take_proceeds(&mut deps.storage, &sender_raw, &denom)?;
+    if amount == 0 {
+        return Err(StdError::generic_err(format!(
+            "You do not have any {} proceeds to claim",
+            denom
+        )));
+    }
+    let claimed = Coin {
+        denom,
+        amount: Uint128(amount),
+    };
+    Ok(HandleResponse {
+        messages: vec![CosmosMsg::Bank(BankMsg::Send {
+            from_address: env.contract.address,
+            to_address: env.message.sender,
+            amount: vec![claimed.clone()],
+        })],
+        log: vec![log("Proceeds claimed", amount)],
+        data: Some(to_binary(&HandleAnswer::ClaimProceeds { amount: claimed })?),
+    })
+}
+
+fn get_sale_num_type<S: ReadonlyStorage>(storage: &S, token_key: &[u8]) -> (i32) {
+    let sale_num = ReadonlyPrefixedStorage::new(PREFIX_SALE_NUM, storage);
+    let num = load(&sale_num, token_key).unwrap();
+    return num;
+}
+
+fn set_sale_num<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    token_key: &[u8],
+    value: i32,
+) -> () {
+    let mut sale_num = PrefixedStorage::new(PREFIX_SALE_NUM, &mut deps.storage);
+    save(&mut sale_num, token_key, &value);
+}
+
+/// Returns StdResult<()> after reverting a token's sale status back to not for sale and
+/// price to 0
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a mutable reference to the Config
+/// * `token_id` - optional token id, if not specified, use token index
+fn revert_sale_status<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &mut Config,
+    token_id: &str,
+) -> StdResult<()> {
+    let (ptoken, pidx) = get_sale_info(&deps.storage, token_id, None)?;
+    let mut token_store = ptoken;
+    token_store.sale_status = SaleStatus::NotForSale;
+    token_store.token_price = Some(0);
+    token_store.denom = None;
+    token_store.expiration = None;
+    save_sale_info(&mut deps.storage, pidx, Some(&token_store))?;
+
+    let mut for_sale: Vec<String> = load(&deps.storage, FOR_SALE_KEY).unwrap_or_default();
+    let find_v = token_id.to_string();
+    if for_sale.iter().find(|&s| *s == find_v).is_some() {
+        for_sale.retain(|id| *id != find_v);
+        save(&mut deps.storage, FOR_SALE_KEY, &for_sale)?;
+    }
+    Ok(())
+}
+
+/// Returns HandleResult
+///
+/// Sets or updates token price
 ///
 /// # Arguments
 ///
@@ -847,16 +1552,14 @@ pub fn set_price<S: Storage, A: Api, Q: Querier>(
             ));
         }
         let (ptoken, pidx) = get_sale_info(&deps.storage, token_id, opt_err)?;
-        let token_key = pidx.to_le_bytes();
         if !(ptoken.sale_status == SaleStatus::ForSale) {
             return Err(StdError::generic_err(
                 "Token is not up for sale so setting price is meaningless",
             ));
         } else {
-            let mut sale_store = PrefixedStorage::new(PREFIX_TOKEN_SALE_INFO, &mut deps.storage);
-            let mut stored_token: TokenSaleInfo = json_load(&sale_store, &token_key)?;
+            let mut stored_token = ptoken;
             stored_token.token_price = Some(price);
-            json_save(&mut sale_store, &token_key, &stored_token)?;
+            save_sale_info(&mut deps.storage, pidx, Some(&stored_token))?;
         }
     }
 
@@ -903,6 +1606,47 @@ fn get_sale_info<S: ReadonlyStorage>(
     Ok((token, idx))
 }
 
+/// Returns Option<u128> of the price a token is listed at if it counts towards the floor price
+///
+/// The floor index is only updated when a listing is written, so an expired listing keeps
+/// counting towards the floor until it is sold, delisted or relisted
+///
+/// # Arguments
+///
+/// * `sale` - the token's optional sale information
+fn listed_price(sale: Option<&TokenSaleInfo>) -> Option<u128> {
+    // only uscrt listings count towards the floor
+    sale.filter(|s| s.sale_status == SaleStatus::ForSale)
+        .filter(|s| s.denom.as_deref().is_none_or(|d| d == SALE_DENOM))
+        .and_then(|s| s.token_price)
+        .filter(|p| *p > 0)
+        .map(u128::from)
+}
+
+/// Returns StdResult<()> after saving or removing a token's sale information and updating the
+/// floor price index
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `idx` - the token's index
+/// * `sale` - the token's new sale information, or None to remove it
+fn save_sale_info<S: Storage>(
+    storage: &mut S,
+    idx: u32,
+    sale: Option<&TokenSaleInfo>,
+) -> StdResult<()> {
+    let token_key = idx.to_le_bytes();
+    let mut sale_store = PrefixedStorage::new(PREFIX_TOKEN_SALE_INFO, storage);
+    let old: Option<TokenSaleInfo> = json_may_load(&sale_store, &token_key)?;
+    if let Some(info) = sale {
+        json_save(&mut sale_store, &token_key, info)?;
+    } else {
+        remove(&mut sale_store, &token_key);
+    }
+    update_floor_index(storage, idx, listed_price(old.as_ref()), listed_price(sale))
+}
+
 /// Returns HandleResult
 ///
 /// Set the sale status of token
@@ -959,6 +1703,9 @@ pub fn set_sale_status<S: Storage, A: Api, Q: Querier>(
                 "Only the owner of the token can change the sale status",
             ));
         }
+        if sale_status == SaleStatus::ForSale {
+            check_not_bundled(&deps.storage, idx, token_id)?;
+        }
         // if the sale status is for sale then TokenSaleInfo struct is populated with all the given arguments.
 
         if sale_status == SaleStatus::ForSale {
@@ -994,11 +1741,12 @@ pub fn set_sale_status<S: Storage, A: Api, Q: Querier>(
             token_id: token_id.to_string(),
             sale_status: sale_status.clone(),
             token_price: Some(_token_price),
+            denom: None,
+            expiration: None,
         };
 
         // save token sale information
-        let mut sale_store = PrefixedStorage::new(PREFIX_TOKEN_SALE_INFO, &mut deps.storage);
-        json_save(&mut sale_store, &idx.to_le_bytes(), &sale)?;
+        save_sale_info(&mut deps.storage, idx, Some(&sale))?;
     }
 
     Ok(HandleResponse {
@@ -1061,8 +1809,9 @@ pub fn mint<S: Storage, A: Api, Q: Querier>(
         royalty_info,
         transferable,
         memo,
+        listing: None,
     }];
-    let mut minted = mint_list(deps, &env, config, &sender_raw, mints)?;
+    let mut minted = mint_list(deps, &env, config, &sender_raw, mints, None)?;
 
     let minted_str = minted.pop().unwrap_or_default();
 
@@ -1101,7 +1850,7 @@ pub fn batch_mint<S: Storage, A: Api, Q: Querier>(
             "Only designated minters are allowed to mint",
         ));
     }
-    let minted = mint_list(deps, &env, config, &sender_raw, mints)?;
+    let minted = mint_list(deps, &env, config, &sender_raw, mints, None)?;
     Ok(HandleResponse {
         messages: vec![],
         log: vec![log("minted", format!("{:?}", &minted))],
@@ -1128,6 +1877,8 @@ pub fn batch_mint<S: Storage, A: Api, Q: Querier>(
 /// * `private_metadata` - optional private metadata viewable only by owner and whitelist
 /// * `royalty_info` - optional royalties information for these clones
 /// * `memo` - optional memo for the mint txs
+/// * `listing` - optional listing that puts every clone up for sale when minted
+/// * `editions` - optional edition overrides.  If given, the public metadata is shared
 #[allow(clippy::too_many_arguments)]
 pub fn mint_clones<S: Storage, A: Api, Q: Querier>(
     deps: &mut Extern<S, A, Q>,
@@ -1141,6 +1892,8 @@ pub fn mint_clones<S: Storage, A: Api, Q: Querier>(
     private_metadata: Option<Metadata>,
     royalty_info: Option<RoyaltyInfo>,
     memo: Option<String>,
+    listing: Option<MintListing>,
+    editions: Option<Vec<EditionOverride>>,
 ) -> HandleResult {
     check_status(config.status, priority)?;
     let sender_raw = deps.api.canonical_address(&env.message.sender)?;
@@ -1153,21 +1906,43 @@ pub fn mint_clones<S: Storage, A: Api, Q: Querier>(
     if quantity == 0 {
         return Err(StdError::generic_err("Quantity can not be zero"));
     }
-    let mint_run = mint_run_id
-        .map(|i| {
-            let key = i.as_bytes();
-            let mut run_store = PrefixedStorage::new(PREFIX_MINT_RUN_NUM, &mut deps.storage);
-            let last_num: u32 = may_load(&run_store, key)?.unwrap_or(0);
-            let this_num: u32 = last_num.checked_add(1).ok_or_else(|| {
-                StdError::generic_err(format!(
-                    "Mint run ID {} has already reached its maximum possible value",
-                    i
-                ))
-            })?;
-            save(&mut run_store, key, &this_num)?;
-            Ok(this_num)
-        })
-        .transpose()?;
+    // editions store the public metadata once instead of in every clone
+    let (clone_metadata, shared_metadata) = if let Some(overrides) = editions.as_ref() {
+        let shared = public_metadata
+            .ok_or_else(|| StdError::generic_err("Editions require public metadata to share"))?;
+        validate_metadata(&shared, "public_metadata")?;
+        if !overrides.is_empty() {
+            if overrides.len() != quantity as usize {
+                return Err(StdError::generic_err(
+                    "Provide either one edition override per clone or none",
+                ));
+            }
+            if shared.extension.is_none()
+                && overrides
+                    .iter()
+                    .any(|o| o.name.is_some() || o.attributes.is_some())
+            {
+                return Err(StdError::generic_err(
+                    "Edition overrides require the shared metadata to have an extension",
+                ));
+            }
+            // make sure each merged edition is still valid metadata
+            for (i, edition_override) in overrides.iter().enumerate() {
+                let edition = StoredEdition {
+                    shared: 0,
+                    edition: i as u32 + 1,
+                    editions: quantity,
+                    name: edition_override.name.clone(),
+                    attributes: edition_override.attributes.clone(),
+                };
+                validate_metadata(&edition.merge(shared.clone()), &format!("editions[{}]", i))?;
+            }
+        }
+        (None, Some(shared))
+    } else {
+        (public_metadata, None)
+    };
+    let mint_run = next_mint_run(&mut deps.storage, mint_run_id)?;
     let mut serial_number = SerialNumber {
         mint_run,
         serial_number: 1,
@@ -1178,16 +1953,55 @@ pub fn mint_clones<S: Storage, A: Api, Q: Querier>(
         mints.push(Mint {
             token_id: None,
             owner: owner.clone(),
-            public_metadata: public_metadata.clone(),
+            public_metadata: clone_metadata.clone(),
             private_metadata: private_metadata.clone(),
             serial_number: Some(serial_number.clone()),
             royalty_info: royalty_info.clone(),
             transferable: Some(true),
             memo: memo.clone(),
+            listing: listing.clone(),
         });
         serial_number.serial_number += 1;
     }
-    let mut minted = mint_list(deps, &env, config, &sender_raw, mints)?;
+    let first_idx = config.mint_cnt;
+    let mut minted = mint_list(deps, &env, config, &sender_raw, mints, mint_run_id)?;
+    // add the clones to the mint run registry
+    if let (Some(run_id), Some(run_number)) = (mint_run_id, mint_run) {
+        let reg_idx = register_mint_run(
+            &mut deps.storage,
+            run_id,
+            run_number,
+            &sender_raw,
+            quantity,
+            env.block.time,
+        )?;
+        for (i, id) in minted.iter().enumerate() {
+            add_to_mint_run(&mut deps.storage, reg_idx, first_idx + i as u32, id)?;
+        }
+    }
+    if let Some(shared) = shared_metadata {
+        let mut shared_store = PrefixedStorage::new(PREFIX_EDITION_META, &mut deps.storage);
+        save(&mut shared_store, &first_idx.to_le_bytes(), &shared)?;
+        let mut overrides = editions.unwrap_or_default().into_iter();
+        for i in 0..quantity {
+            let edition_override = overrides.next().unwrap_or_default();
+            let edition = StoredEdition {
+                shared: first_idx,
+                edition: i + 1,
+                editions: quantity,
+                name: edition_override.name,
+                attributes: edition_override.attributes,
+            };
+            update_trait_index(
+                &mut deps.storage,
+                first_idx + i,
+                None,
+                Some(&edition.merge(shared.clone())),
+            )?;
+            let mut edition_store = PrefixedStorage::new(PREFIX_EDITIONS, &mut deps.storage);
+            save(&mut edition_store, &(first_idx + i).to_le_bytes(), &edition)?;
+        }
+    }
     // if mint_list did not error, there must be at least one token id
     let first_minted = minted
         .first()
@@ -1210,254 +2024,1745 @@ pub fn mint_clones<S: Storage, A: Api, Q: Querier>(
     })
 }
 
+/// Returns StdResult<Option<u32>>
+///
+/// increments and returns the mint run number of a mint run ID
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `mint_run_id` - optional mint run ID
+fn next_mint_run<S: Storage>(
+    storage: &mut S,
+    mint_run_id: Option<&String>,
+) -> StdResult<Option<u32>> {
+    mint_run_id
+        .map(|i| {
+            let key = i.as_bytes();
+            let mut run_store = PrefixedStorage::new(PREFIX_MINT_RUN_NUM, storage);
+            let last_num: u32 = may_load(&run_store, key)?.unwrap_or(0);
+            let this_num: u32 = last_num.checked_add(1).ok_or_else(|| {
+                StdError::generic_err(format!(
+                    "Mint run ID {} has already reached its maximum possible value",
+                    i
+                ))
+            })?;
+            save(&mut run_store, key, &this_num)?;
+            Ok(this_num)
+        })
+        .transpose()
+}
+
+/// Returns StdResult<u32>
+///
+/// adds a mint run to the mint run registry and returns its registry index
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `run_id` - mint run ID given when minting
+/// * `run_number` - number of this mint run for its mint run ID
+/// * `creator` - a reference to the address minting the run
+/// * `quantity` - number of tokens in the mint run
+/// * `time` - time the mint run was started
+fn register_mint_run<S: Storage>(
+    storage: &mut S,
+    run_id: &str,
+    run_number: u32,
+    creator: &CanonicalAddr,
+    quantity: u32,
+    time: u64,
+) -> StdResult<u32> {
+    let reg_idx: u32 = may_load(storage, MINT_RUN_COUNT_KEY)?.unwrap_or(0);
+    let run = StoredMintRun {
+        run_id: run_id.to_string(),
+        run_number,
+        creator: creator.clone(),
+        quantity,
+        minted: 0,
+        first_token: None,
+        last_token: None,
+        time_of_minting: time,
+    };
+    let mut reg_store = PrefixedStorage::new(PREFIX_MINT_RUN_REGISTRY, storage);
+    save(&mut reg_store, &reg_idx.to_le_bytes(), &run)?;
+    let mut lookup_store =
+        PrefixedStorage::multilevel(&[PREFIX_MINT_RUN_LOOKUP, run_id.as_bytes()], storage);
+    save(&mut lookup_store, &run_number.to_le_bytes(), &reg_idx)?;
+    let mut creator_store = PrefixedStorage::new(PREFIX_CREATOR_RUNS, storage);
+    let mut runs: Vec<u32> = may_load(&creator_store, creator.as_slice())?.unwrap_or_default();
+    runs.push(reg_idx);
+    save(&mut creator_store, creator.as_slice(), &runs)?;
+    save(storage, MINT_RUN_COUNT_KEY, &(reg_idx + 1))?;
+    Ok(reg_idx)
+}
+
+/// Returns StdResult<()>
+///
+/// records that a token was minted as part of a registered mint run
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `reg_idx` - registry index of the mint run
+/// * `token_idx` - index of the minted token
+/// * `token_id` - id of the minted token
+fn add_to_mint_run<S: Storage>(
+    storage: &mut S,
+    reg_idx: u32,
+    token_idx: u32,
+    token_id: &str,
+) -> StdResult<()> {
+    let reg_key = reg_idx.to_le_bytes();
+    let mut reg_store = PrefixedStorage::new(PREFIX_MINT_RUN_REGISTRY, storage);
+    let mut run: StoredMintRun = load(&reg_store, &reg_key)?;
+    if run.first_token.is_none() {
+        run.first_token = Some(token_id.to_string());
+    }
+    run.last_token = Some(token_id.to_string());
+    let position = run.minted;
+    run.minted += 1;
+    save(&mut reg_store, &reg_key, &run)?;
+    let mut tok_store = PrefixedStorage::multilevel(&[PREFIX_MINT_RUN_TOKENS, &reg_key], storage);
+    save(&mut tok_store, &position.to_le_bytes(), &token_idx)?;
+    // a token listed at mint counts towards its mint run's floor price
+    let sale_store = ReadonlyPrefixedStorage::new(PREFIX_TOKEN_SALE_INFO, storage);
+    let sale: Option<TokenSaleInfo> = json_may_load(&sale_store, &token_idx.to_le_bytes())?;
+    set_token_mint_run(storage, token_idx, reg_idx, listed_price(sale.as_ref()))
+}
+
 /// Returns HandleResult
 ///
-/// sets new public and/or private metadata
+/// configures the public mint
 ///
 /// # Arguments
 ///
 /// * `deps` - mutable reference to Extern containing all the contract's external dependencies
 /// * `env` - Env of contract's environment
 /// * `config` - a reference to the Config
-/// * `priority` - u8 representation of highest status level this action is permitted at
-/// * `token_id` - token id String slice of token whose metadata should be updated
-/// * `public_metadata` - the optional new public metadata viewable by everyone
-/// * `private_metadata` - the optional new private metadata viewable by everyone
-pub fn set_metadata<S: Storage, A: Api, Q: Querier>(
+/// * `enabled` - true if the public mint should be open
+/// * `price` - price of each token in uscrt
+/// * `max_supply` - maximum number of tokens that can be minted through the public mint
+/// * `per_wallet_limit` - optional maximum number of tokens a single address can mint
+/// * `random_assignment` - optionally set whether each mint draws a random unused template
+#[allow(clippy::too_many_arguments)]
+pub fn set_public_mint<S: Storage, A: Api, Q: Querier>(
     deps: &mut Extern<S, A, Q>,
     env: Env,
     config: &Config,
-    priority: u8,
-    token_id: &str,
-    public_metadata: Option<Metadata>,
-    private_metadata: Option<Metadata>,
+    enabled: bool,
+    price: Uint128,
+    max_supply: u32,
+    per_wallet_limit: Option<u32>,
+    random_assignment: Option<bool>,
 ) -> HandleResult {
-    check_status(config.status, priority)?;
-    let custom_err = format!("Not authorized to update metadata of token {}", token_id);
-    // if token supply is private, don't leak that the token id does not exist
-    // instead just say they are not authorized for that token
-    let opt_err = if config.token_supply_is_public {
-        None
-    } else {
-        Some(&*custom_err)
-    };
-    let (token, idx) = get_token(&deps.storage, token_id, opt_err)?;
     let sender_raw = deps.api.canonical_address(&env.message.sender)?;
-    if !(token.owner == sender_raw && config.owner_may_update_metadata) {
-        let minters: Vec<CanonicalAddr> = may_load(&deps.storage, MINTERS_KEY)?.unwrap_or_default();
-        if !(minters.contains(&sender_raw) && config.minter_may_update_metadata) {
-            return Err(StdError::generic_err(custom_err));
-        }
+    if config.admin != sender_raw {
+        return Err(StdError::generic_err(
+            "This is an admin command and can only be run from the admin address",
+        ));
     }
-    if let Some(public) = public_metadata {
-        set_metadata_impl(&mut deps.storage, &token, idx, PREFIX_PUB_META, &public)?;
+    let mut public_mint: StoredPublicMint =
+        may_load(&deps.storage, PUBLIC_MINT_KEY)?.unwrap_or_default();
+    if max_supply < public_mint.minted {
+        return Err(StdError::generic_err(format!(
+            "The max supply can not be lower than the {} tokens already minted",
+            public_mint.minted
+        )));
     }
-    if let Some(private) = private_metadata {
-        set_metadata_impl(&mut deps.storage, &token, idx, PREFIX_PRIV_META, &private)?;
+    public_mint.enabled = enabled;
+    public_mint.price = price.u128();
+    public_mint.max_supply = max_supply;
+    public_mint.per_wallet_limit = per_wallet_limit;
+    if let Some(random) = random_assignment {
+        // without sealed metadata, the drawn template could be read as soon as it is minted
+        if random && !config.sealed_metadata_is_enabled {
+            return Err(StdError::generic_err(
+                "Random assignment requires sealed metadata to be enabled",
+            ));
+        }
+        public_mint.random_assignment = random;
     }
+    save(&mut deps.storage, PUBLIC_MINT_KEY, &public_mint)?;
     Ok(HandleResponse {
         messages: vec![],
         log: vec![],
-        data: Some(to_binary(&HandleAnswer::SetMetadata { status: Success })?),
+        data: Some(to_binary(&HandleAnswer::SetPublicMint { status: Success })?),
     })
 }
 
 /// Returns HandleResult
 ///
-/// sets new royalty information for a specified token or if no token ID is provided, sets new
-/// royalty information as the contract's default
+/// uploads metadata templates for tokens minted through the public mint
 ///
 /// # Arguments
 ///
 /// * `deps` - mutable reference to Extern containing all the contract's external dependencies
 /// * `env` - Env of contract's environment
 /// * `config` - a reference to the Config
-/// * `priority` - u8 representation of highest status level this action is permitted at
-/// * `token_id` - optional token id String slice of token whose royalty info should be updated
-/// * `royalty_info` - a optional reference to the new RoyaltyInfo
-pub fn set_royalty_info<S: Storage, A: Api, Q: Querier>(
+/// * `templates` - the metadata templates to add
+pub fn add_mint_templates<S: Storage, A: Api, Q: Querier>(
     deps: &mut Extern<S, A, Q>,
     env: Env,
     config: &Config,
-    priority: u8,
-    token_id: Option<&str>,
-    royalty_info: Option<&RoyaltyInfo>,
+    templates: Vec<MintTemplate>,
 ) -> HandleResult {
-    check_status(config.status, priority)?;
     let sender_raw = deps.api.canonical_address(&env.message.sender)?;
-    // set a token's royalties
-    if let Some(id) = token_id {
-        let custom_err = "A token's RoyaltyInfo may only be set by the token creator when they are also the token owner";
-        // if token supply is private, don't leak that the token id does not exist
-        // instead just say they are not authorized for that token
-        let opt_err = if config.token_supply_is_public {
-            None
-        } else {
-            Some(custom_err)
-        };
-        let (token, idx) = get_token(&deps.storage, id, opt_err)?;
-        if !token.transferable {
-            return Err(StdError::generic_err(
-                "Non-transferable tokens can not be sold, so royalties are meaningless",
-            ));
-        }
-        let token_key = idx.to_le_bytes();
-        let run_store = ReadonlyPrefixedStorage::new(PREFIX_MINT_RUN, &deps.storage);
-        let mint_run: StoredMintRunInfo = load(&run_store, &token_key)?;
-        if sender_raw != mint_run.token_creator || sender_raw != token.owner {
-            return Err(StdError::generic_err(custom_err));
+    if config.admin != sender_raw {
+        return Err(StdError::generic_err(
+            "This is an admin command and can only be run from the admin address",
+        ));
+    }
+    let mut public_mint: StoredPublicMint =
+        may_load(&deps.storage, PUBLIC_MINT_KEY)?.unwrap_or_default();
+    let mut tmpl_store = PrefixedStorage::new(PREFIX_MINT_TEMPLATES, &mut deps.storage);
+    for template in templates.iter() {
+        if let Some(pub_meta) = template.public_metadata.as_ref() {
+            validate_metadata(pub_meta, "public_metadata")?;
         }
-        let default_roy = royalty_info.as_ref().map_or_else(
-            || may_load::<StoredRoyaltyInfo, _>(&deps.storage, DEFAULT_ROYALTY_KEY),
-            |_r| Ok(None),
-        )?;
-        let mut roy_store = PrefixedStorage::new(PREFIX_ROYALTY_INFO, &mut deps.storage);
-        store_royalties(
-            &mut roy_store,
-            &deps.api,
-            royalty_info,
-            default_roy.as_ref(),
-            &token_key,
-        )?;
-    // set default royalty
-    } else {
-        let minters: Vec<CanonicalAddr> = may_load(&deps.storage, MINTERS_KEY)?.unwrap_or_default();
-        if !minters.contains(&sender_raw) {
-            return Err(StdError::generic_err(
-                "Only designated minters can set default royalties for the contract",
-            ));
+        if let Some(priv_meta) = template.private_metadata.as_ref() {
+            validate_metadata(priv_meta, "private_metadata")?;
         }
-        store_royalties(
-            &mut deps.storage,
-            &deps.api,
-            royalty_info,
-            None,
-            DEFAULT_ROYALTY_KEY,
+        save(
+            &mut tmpl_store,
+            &public_mint.templates.to_le_bytes(),
+            template,
         )?;
-    };
-
+        public_mint.templates = public_mint
+            .templates
+            .checked_add(1)
+            .ok_or_else(|| StdError::generic_err("Too many templates have been uploaded"))?;
+    }
+    save(&mut deps.storage, PUBLIC_MINT_KEY, &public_mint)?;
     Ok(HandleResponse {
         messages: vec![],
         log: vec![],
-        data: Some(to_binary(&HandleAnswer::SetRoyaltyInfo {
-            status: Success,
+        data: Some(to_binary(&HandleAnswer::AddMintTemplates {
+            templates_remaining: public_mint.templates.saturating_sub(public_mint.minted),
         })?),
     })
 }
 
 /// Returns HandleResult
 ///
-/// makes the sealed private metadata public
+/// replaces the phases of the public mint
 ///
 /// # Arguments
 ///
 /// * `deps` - mutable reference to Extern containing all the contract's external dependencies
 /// * `env` - Env of contract's environment
 /// * `config` - a reference to the Config
-/// * `priority` - u8 representation of highest status level this action is permitted at
-/// * `token_id` - token id String slice of token whose metadata should be updated
-pub fn reveal<S: Storage, A: Api, Q: Querier>(
+/// * `phases` - the new mint phases
+pub fn set_mint_phases<S: Storage, A: Api, Q: Querier>(
     deps: &mut Extern<S, A, Q>,
     env: Env,
     config: &Config,
-    priority: u8,
-    token_id: &str,
+    phases: Vec<MintPhase>,
 ) -> HandleResult {
-    check_status(config.status, priority)?;
-    if !config.sealed_metadata_is_enabled {
-        return Err(StdError::generic_err(
-            "Sealed metadata functionality is not enabled for this contract",
-        ));
-    }
     let sender_raw = deps.api.canonical_address(&env.message.sender)?;
-    let custom_err = format!("You do not own token {}", token_id);
-    // if token supply is private, don't leak that the token id does not exist
-    // instead just say they do not own that token
-    let opt_err = if config.token_supply_is_public {
-        None
-    } else {
-        Some(&*custom_err)
-    };
-    let (mut token, idx) = get_token(&deps.storage, token_id, opt_err)?;
-    if token.unwrapped {
+    if config.admin != sender_raw {
         return Err(StdError::generic_err(
-            "This token has already been unwrapped",
+            "This is an admin command and can only be run from the admin address",
         ));
     }
-    if token.owner != sender_raw {
-        return Err(StdError::generic_err(custom_err));
-    }
-    token.unwrapped = true;
-    let token_key = idx.to_le_bytes();
-    let mut info_store = PrefixedStorage::new(PREFIX_INFOS, &mut deps.storage);
-    json_save(&mut info_store, &token_key, &token)?;
-    if !config.unwrap_to_private {
-        let mut priv_store = PrefixedStorage::new(PREFIX_PRIV_META, &mut deps.storage);
-        let may_priv: Option<Metadata> = may_load(&priv_store, &token_key)?;
-        if let Some(metadata) = may_priv {
-            remove(&mut priv_store, &token_key);
-            let mut pub_store = PrefixedStorage::new(PREFIX_PUB_META, &mut deps.storage);
-            save(&mut pub_store, &token_key, &metadata)?;
+    // mint counts are kept by phase name, so a name can only be used once
+    for (i, phase) in phases.iter().enumerate() {
+        if phases[..i].iter().any(|p| p.name == phase.name) {
+            return Err(StdError::generic_err(format!(
+                "Mint phase name {} is used more than once",
+                phase.name
+            )));
         }
     }
+    let mut public_mint: StoredPublicMint =
+        may_load(&deps.storage, PUBLIC_MINT_KEY)?.unwrap_or_default();
+    public_mint.phases = phases
+        .into_iter()
+        .map(|p| p.into_stored())
+        .collect::<StdResult<Vec<_>>>()?;
+    save(&mut deps.storage, PUBLIC_MINT_KEY, &public_mint)?;
     Ok(HandleResponse {
         messages: vec![],
         log: vec![],
-        data: Some(to_binary(&HandleAnswer::Reveal { status: Success })?),
+        data: Some(to_binary(&HandleAnswer::SetMintPhases { status: Success })?),
     })
 }
 
 /// Returns HandleResult
 ///
-/// grants/revokes trasfer permission on a token
+/// mints tokens to the message sender in exchange for their price
 ///
 /// # Arguments
 ///
 /// * `deps` - mutable reference to Extern containing all the contract's external dependencies
 /// * `env` - Env of contract's environment
-/// * `config` - a reference to the Config
+/// * `config` - a mutable reference to the Config
 /// * `priority` - u8 representation of highest status level this action is permitted at
-/// * `spender` - a reference to the address being granted permission
-/// * `token_id` - string slice of the token id to grant permission to
-/// * `expires` - optional Expiration for this approval
-/// * `is_approve` - true if this is an Approve call
+/// * `quantity` - number of tokens to mint
+/// * `merkle_proof` - optional proof that the sender is on the active phase's allowlist
+/// * `entropy` - optional entropy String used when templates are randomly assigned
 #[allow(clippy::too_many_arguments)]
-pub fn approve_revoke<S: Storage, A: Api, Q: Querier>(
+pub fn public_mint<S: Storage, A: Api, Q: Querier>(
     deps: &mut Extern<S, A, Q>,
     env: Env,
-    config: &Config,
+    config: &mut Config,
     priority: u8,
-    spender: &HumanAddr,
-    token_id: &str,
-    expires: Option<Expiration>,
-    is_approve: bool,
+    quantity: u32,
+    merkle_proof: Option<Vec<Binary>>,
+    entropy: Option<String>,
 ) -> HandleResult {
     check_status(config.status, priority)?;
-    let address_raw = deps.api.canonical_address(spender)?;
     let sender_raw = deps.api.canonical_address(&env.message.sender)?;
-    let custom_err = format!(
-        "Not authorized to grant/revoke transfer permission for token {}",
-        token_id
-    );
-    // if token supply is private, don't leak that the token id does not exist
-    // instead just say they are not authorized for that token
-    let opt_err = if config.token_supply_is_public {
-        None
-    } else {
-        Some(&*custom_err)
-    };
-    let (token, idx) = get_token(&deps.storage, token_id, opt_err)?;
-    let mut all_perm: Option<Vec<Permission>> = None;
-    let mut from_oper = false;
-    let transfer_idx = PermissionType::Transfer.to_usize();
-    // if not called by the owner, check if message sender has operator status
-    if token.owner != sender_raw {
-        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
-        let may_list: Option<Vec<Permission>> = json_may_load(&all_store, token.owner.as_slice())?;
-        if let Some(list) = may_list.clone() {
-            if let Some(perm) = list.iter().find(|&p| p.address == sender_raw) {
-                if let Some(exp) = perm.expirations[transfer_idx] {
-                    if exp.is_expired(&env.block) {
+    let mut public_mint: StoredPublicMint =
+        may_load(&deps.storage, PUBLIC_MINT_KEY)?.unwrap_or_default();
+    if !public_mint.enabled {
+        return Err(StdError::generic_err("The public mint is not open"));
+    }
+    if quantity == 0 {
+        return Err(StdError::generic_err("Quantity can not be zero"));
+    }
+    let minted_after = public_mint
+        .minted
+        .checked_add(quantity)
+        .filter(|m| *m <= public_mint.max_supply)
+        .ok_or_else(|| {
+            StdError::generic_err(format!(
+                "Only {} tokens remain in the public mint",
+                public_mint.max_supply.saturating_sub(public_mint.minted)
+            ))
+        })?;
+    let wallet_store = ReadonlyPrefixedStorage::new(PREFIX_PUBLIC_MINTED, &deps.storage);
+    let wallet_minted: u32 = may_load(&wallet_store, sender_raw.as_slice())?.unwrap_or(0);
+    let wallet_after = wallet_minted.saturating_add(quantity);
+    if let Some(limit) = public_mint.per_wallet_limit {
+        if wallet_after > limit {
+            return Err(StdError::generic_err(format!(
+                "An address may only mint {} tokens through the public mint",
+                limit
+            )));
+        }
+    }
+    // if there are phases, the active one determines the price, allowlist, and its own limit
+    let mut price = public_mint.price;
+    let mut phase_count: Option<(String, u32)> = None;
+    if !public_mint.phases.is_empty() {
+        let phase = public_mint
+            .active_phase(&env.block)
+            .ok_or_else(|| StdError::generic_err("No mint phase is currently active"))?;
+        if let Some(root) = phase.merkle_root.as_ref() {
+            let proof = merkle_proof.unwrap_or_default();
+            if !verify_merkle_proof(root, env.message.sender.as_str(), &proof) {
+                return Err(StdError::generic_err(format!(
+                    "You are not on the allowlist for the {} phase",
+                    phase.name
+                )));
+            }
+        }
+        let phase_store = ReadonlyPrefixedStorage::multilevel(
+            &[PREFIX_PHASE_MINTED, phase.name.as_bytes()],
+            &deps.storage,
+        );
+        let phase_after = may_load::<u32, _>(&phase_store, sender_raw.as_slice())?
+            .unwrap_or(0)
+            .saturating_add(quantity);
+        if let Some(limit) = phase.per_wallet_limit {
+            if phase_after > limit {
+                return Err(StdError::generic_err(format!(
+                    "An address may only mint {} tokens during the {} phase",
+                    limit, phase.name
+                )));
+            }
+        }
+        price = phase.price;
+        phase_count = Some((phase.name.clone(), phase_after));
+    }
+    if minted_after > public_mint.templates {
+        return Err(StdError::generic_err(
+            "Not enough metadata templates have been uploaded for this mint",
+        ));
+    }
+    // check payment
+    let cost = U256::from(price) * U256::from(quantity);
+    let sent = if env.message.sent_funds.is_empty() {
+        0u128
+    } else if env.message.sent_funds.len() != 1 || env.message.sent_funds[0].denom != SALE_DENOM {
+        return Err(StdError::generic_err("Insufficient funds provided"));
+    } else {
+        env.message.sent_funds[0].amount.u128()
+    };
+    if U256::from(sent) < cost {
+        return Err(StdError::generic_err("Insufficient funds provided"));
+    }
+    if U256::from(sent) > cost {
+        return Err(StdError::generic_err("Funds sent exceeds funds needed"));
+    }
+    let mut wallet_store = PrefixedStorage::new(PREFIX_PUBLIC_MINTED, &mut deps.storage);
+    save(&mut wallet_store, sender_raw.as_slice(), &wallet_after)?;
+    if let Some((name, phase_after)) = phase_count {
+        let mut phase_store =
+            PrefixedStorage::multilevel(&[PREFIX_PHASE_MINTED, name.as_bytes()], &mut deps.storage);
+        save(&mut phase_store, sender_raw.as_slice(), &phase_after)?;
+    }
+    // templates below the minted count have been used.  If assigning randomly, draw one of the
+    // unused templates and move the template it replaces into the drawn slot
+    // the minter's entropy always advances the private seed, even without random assignment,
+    // so the seed stops being known to the admin once a public minter supplies secret entropy
+    let prng_seed: Vec<u8> = load(&deps.storage, PRNG_SEED_KEY)?;
+    let mut rng_entropy = Vec::new();
+    rng_entropy.extend_from_slice(&env.block.height.to_be_bytes());
+    rng_entropy.extend_from_slice(&env.block.time.to_be_bytes());
+    rng_entropy.extend_from_slice(sender_raw.as_slice());
+    rng_entropy.extend_from_slice(&public_mint.minted.to_le_bytes());
+    if let Some(ent) = entropy.as_ref() {
+        rng_entropy.extend_from_slice(ent.as_bytes());
+    }
+    let mut rng = Prng::new(&prng_seed, &rng_entropy);
+    let mut mints: Vec<Mint> = Vec::new();
+    let mut tmpl_store = PrefixedStorage::new(PREFIX_MINT_TEMPLATES, &mut deps.storage);
+    for i in public_mint.minted..minted_after {
+        let tmpl_key = i.to_le_bytes();
+        let mut template: MintTemplate = load(&tmpl_store, &tmpl_key)?;
+        if public_mint.random_assignment {
+            let mut rand_bytes = [0u8; 8];
+            rand_bytes.copy_from_slice(&rng.rand_bytes()[..8]);
+            let unused = (public_mint.templates - i) as u64;
+            let drawn = i + (u64::from_le_bytes(rand_bytes) % unused) as u32;
+            if drawn != i {
+                let drawn_key = drawn.to_le_bytes();
+                let drawn_tmpl: MintTemplate = load(&tmpl_store, &drawn_key)?;
+                save(&mut tmpl_store, &drawn_key, &template)?;
+                template = drawn_tmpl;
+            }
+        }
+        remove(&mut tmpl_store, &tmpl_key);
+        mints.push(Mint {
+            token_id: None,
+            owner: Some(env.message.sender.clone()),
+            public_metadata: template.public_metadata,
+            private_metadata: template.private_metadata,
+            serial_number: None,
+            royalty_info: None,
+            transferable: Some(true),
+            memo: None,
+            listing: None,
+        });
+    }
+    public_mint.minted = minted_after;
+    save(&mut deps.storage, PUBLIC_MINT_KEY, &public_mint)?;
+    // advance the seed so later draws can not be predicted from this one
+    save(&mut deps.storage, PRNG_SEED_KEY, &rng.rand_bytes().to_vec())?;
+    // the collection is minted by the admin, who is paid as the seller of a primary sale
+    let creator = config.admin.clone();
+    let token_ids = mint_list(deps, &env, config, &creator, mints, None)?;
+    credit_sale_proceeds(&mut deps.storage, &creator, SALE_DENOM, sent)?;
+    // minting is each token's first sale, so its first resale pays royalties
+    for token_id in token_ids.iter() {
+        let map2idx = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_INDEX, &deps.storage);
+        let idx: u32 = load(&map2idx, token_id.as_bytes())?;
+        let token_key = idx.to_le_bytes();
+        set_sale_num(deps, &token_key, 1);
+        if price > 0 {
+            record_sale_stats(&mut deps.storage, idx, price)?;
+        }
+    }
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![log("minted", token_ids.join(","))],
+        data: Some(to_binary(&HandleAnswer::PublicMint { token_ids })?),
+    })
+}
+
+/// Returns HandleResult
+///
+/// commits to the metadata of the collection before it is revealed
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a reference to the Config
+/// * `metadata_hash` - sha256 hash of the full metadata set
+/// * `seed_hash` - sha256 hash of the seed that will be published at the reveal
+/// * `supply` - number of tokens in the collection
+pub fn commit_reveal<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &Config,
+    metadata_hash: Binary,
+    seed_hash: Binary,
+    supply: u32,
+) -> HandleResult {
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    if config.admin != sender_raw {
+        return Err(StdError::generic_err(
+            "This is an admin command and can only be run from the admin address",
+        ));
+    }
+    if !config.sealed_metadata_is_enabled {
+        return Err(StdError::generic_err(
+            "Sealed metadata functionality is not enabled for this contract",
+        ));
+    }
+    if may_load::<StoredRevealCommitment, _>(&deps.storage, REVEAL_COMMITMENT_KEY)?.is_some() {
+        return Err(StdError::generic_err(
+            "A reveal commitment has already been made",
+        ));
+    }
+    if metadata_hash.len() != 32 || seed_hash.len() != 32 {
+        return Err(StdError::generic_err(
+            "The metadata and seed hashes must be 32 byte sha256 hashes",
+        ));
+    }
+    if supply == 0 {
+        return Err(StdError::generic_err(
+            "The supply of the collection can not be zero",
+        ));
+    }
+    // every mint must feed the entropy of the offset
+    if config.mint_cnt != 0 {
+        return Err(StdError::generic_err(
+            "The reveal commitment must be made before any tokens are minted",
+        ));
+    }
+    let commitment = StoredRevealCommitment {
+        metadata_hash: metadata_hash.0,
+        seed_hash: seed_hash.0,
+        supply,
+        mint_entropy: Vec::new(),
+        seed: None,
+        offset: None,
+        revealed: 0,
+        cycle_start: 0,
+        position: 0,
+        carrying: false,
+        carried: None,
+    };
+    save(&mut deps.storage, REVEAL_COMMITMENT_KEY, &commitment)?;
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::CommitReveal { status: Success })?),
+    })
+}
+
+/// Returns HandleResult
+///
+/// reveals a batch of the collection.  The sealed private metadata is rotated by the offset in
+/// place, one rotation cycle at a time, and each token is unwrapped once its final metadata has
+/// been set
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a reference to the Config
+/// * `priority` - u8 representation of highest ContractStatus level this action is permitted
+/// * `seed` - the seed committed to, required on the first call
+/// * `limit` - optional max number of tokens to reveal in this batch
+pub fn reveal_collection<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &Config,
+    priority: u8,
+    seed: Option<Binary>,
+    limit: Option<u32>,
+) -> HandleResult {
+    check_status(config.status, priority)?;
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    if config.admin != sender_raw {
+        return Err(StdError::generic_err(
+            "This is an admin command and can only be run from the admin address",
+        ));
+    }
+    let mut commitment: StoredRevealCommitment = may_load(&deps.storage, REVEAL_COMMITMENT_KEY)?
+        .ok_or_else(|| StdError::generic_err("No reveal commitment has been made"))?;
+    let supply = commitment.supply;
+    let offset = if let Some(off) = commitment.offset {
+        off
+    } else {
+        let seed = seed.ok_or_else(|| {
+            StdError::generic_err("The seed must be published to start the reveal")
+        })?;
+        if sha_256(seed.as_slice()).as_ref() != commitment.seed_hash.as_slice() {
+            return Err(StdError::generic_err(
+                "The seed does not match the one committed to",
+            ));
+        }
+        if config.mint_cnt != supply {
+            return Err(StdError::generic_err(format!(
+                "All {} tokens of the collection must be minted, and no others, before it is revealed",
+                supply
+            )));
+        }
+        // the seed was committed before the first mint and the mint entropy was fixed by the
+        // last mint.  The mint entropy includes the private seed, which public minters advance
+        // with their own entropy, so once any public minter has supplied secret entropy the admin
+        // can not predict the offset.  If the admin mints every token themselves, they know
+        // every input and could time the last mint to choose the offset
+        let mut entropy = seed.0.clone();
+        entropy.extend_from_slice(&commitment.mint_entropy);
+        let mut off_bytes = [0u8; 4];
+        off_bytes.copy_from_slice(&sha_256(&entropy)[..4]);
+        let off = u32::from_le_bytes(off_bytes) % supply;
+        commitment.seed = Some(seed.0);
+        commitment.offset = Some(off);
+        off
+    };
+    let mut budget = limit.unwrap_or(100);
+    while commitment.revealed < supply && budget > 0 {
+        let mut priv_store = PrefixedStorage::new(PREFIX_PRIV_META, &mut deps.storage);
+        if !commitment.carrying {
+            commitment.carried = may_load(&priv_store, &commitment.cycle_start.to_le_bytes())?;
+            commitment.carrying = true;
+        }
+        let position = commitment.position;
+        let source = ((position as u64 + offset as u64) % supply as u64) as u32;
+        let metadata: Option<Metadata> = if source == commitment.cycle_start {
+            commitment.carrying = false;
+            commitment.carried.take()
+        } else {
+            may_load(&priv_store, &source.to_le_bytes())?
+        };
+        let token_key = position.to_le_bytes();
+        if config.unwrap_to_private {
+            if let Some(meta) = metadata {
+                save(&mut priv_store, &token_key, &meta)?;
+            } else {
+                remove(&mut priv_store, &token_key);
+            }
+        } else {
+            remove(&mut priv_store, &token_key);
+            if let Some(meta) = metadata {
+                let old_pub = load_public_metadata(&deps.storage, &token_key)?;
+                update_trait_index(&mut deps.storage, position, old_pub.as_ref(), Some(&meta))?;
+                let mut pub_store = PrefixedStorage::new(PREFIX_PUB_META, &mut deps.storage);
+                save(&mut pub_store, &token_key, &meta)?;
+            }
+        }
+        let mut info_store = PrefixedStorage::new(PREFIX_INFOS, &mut deps.storage);
+        let may_token: Option<Token> = json_may_load(&info_store, &token_key)?;
+        if let Some(mut token) = may_token {
+            token.unwrapped = true;
+            json_save(&mut info_store, &token_key, &token)?;
+        }
+        commitment.revealed += 1;
+        budget -= 1;
+        if commitment.carrying {
+            commitment.position = source;
+        } else {
+            commitment.cycle_start += 1;
+            commitment.position = commitment.cycle_start;
+        }
+    }
+    save(&mut deps.storage, REVEAL_COMMITMENT_KEY, &commitment)?;
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::RevealCollection {
+            revealed: commitment.revealed,
+            remaining: supply - commitment.revealed,
+        })?),
+    })
+}
+
+/// Returns HandleResult
+///
+/// creates a lazy listing whose editions are minted when they are bought
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a reference to the Config
+/// * `priority` - u8 representation of highest ContractStatus level this action is permitted
+/// * `listing_id` - id used to buy the listing's editions
+/// * `mint_run_id` - optional mint run ID
+/// * `price` - price of each edition in uscrt
+/// * `editions` - number of editions that can be sold
+/// * `public_metadata` - optional public metadata of the editions
+/// * `private_metadata` - optional private metadata of the editions
+/// * `royalty_info` - optional royalty information of the editions
+#[allow(clippy::too_many_arguments)]
+pub fn create_lazy_listing<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &Config,
+    priority: u8,
+    listing_id: String,
+    mint_run_id: Option<&String>,
+    price: u32,
+    editions: u32,
+    public_metadata: Option<Metadata>,
+    private_metadata: Option<Metadata>,
+    royalty_info: Option<RoyaltyInfo>,
+) -> HandleResult {
+    check_status(config.status, priority)?;
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    let minters: Vec<CanonicalAddr> = may_load(&deps.storage, MINTERS_KEY)?.unwrap_or_default();
+    if !minters.contains(&sender_raw) {
+        return Err(StdError::generic_err(
+            "Only designated minters are allowed to mint",
+        ));
+    }
+    if price == 0 {
+        return Err(StdError::generic_err(
+            "Invalid input! Price cannot be set to 0.",
+        ));
+    }
+    if editions == 0 {
+        return Err(StdError::generic_err("Editions can not be zero"));
+    }
+    // verify now that the creator's role would allow minting every edition
+    let role_store = ReadonlyPrefixedStorage::new(PREFIX_MINTER_ROLES, &deps.storage);
+    if let Some(mut role) = may_load::<StoredMinterRole, _>(&role_store, sender_raw.as_slice())? {
+        role.use_quota(&env.block, editions, mint_run_id)?;
+    }
+    // numeric ids are the default token ids, and a listing keeps its id forever
+    if listing_id.parse::<u32>().is_ok() {
+        return Err(StdError::generic_err(format!(
+            "Lazy listing ID {} can not be a number, because numbers are used as default token IDs",
+            listing_id
+        )));
+    }
+    let map2idx = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_INDEX, &deps.storage);
+    let token_exists = may_load::<u32, _>(&map2idx, listing_id.as_bytes())?.is_some();
+    let lazy_store = ReadonlyPrefixedStorage::new(PREFIX_LAZY_LISTINGS, &deps.storage);
+    let lazy_exists =
+        may_load::<StoredLazyListing, _>(&lazy_store, listing_id.as_bytes())?.is_some();
+    if token_exists || lazy_exists {
+        return Err(StdError::generic_err(format!(
+            "ID {} is already in use",
+            listing_id
+        )));
+    }
+    if let Some(pub_meta) = public_metadata.as_ref() {
+        validate_metadata(pub_meta, "public_metadata")?;
+    }
+    if let Some(priv_meta) = private_metadata.as_ref() {
+        validate_metadata(priv_meta, "private_metadata")?;
+    }
+    if let Some(roy) = royalty_info.as_ref() {
+        check_royalty_rates(roy, config.max_royalty_bps)?;
+        // verify the addresses now instead of when an edition is bought
+        roy.to_stored(&deps.api)?;
+    }
+    let mint_run = next_mint_run(&mut deps.storage, mint_run_id)?;
+    let registry_index = if let (Some(run_id), Some(run_number)) = (mint_run_id, mint_run) {
+        Some(register_mint_run(
+            &mut deps.storage,
+            run_id,
+            run_number,
+            &sender_raw,
+            editions,
+            env.block.time,
+        )?)
+    } else {
+        None
+    };
+    let listing = StoredLazyListing {
+        creator: sender_raw,
+        price,
+        editions,
+        sold: 0,
+        mint_run,
+        mint_run_id: mint_run_id.cloned(),
+        public_metadata,
+        private_metadata,
+        royalty_info,
+        registry_index,
+    };
+    let mut lazy_store = PrefixedStorage::new(PREFIX_LAZY_LISTINGS, &mut deps.storage);
+    save(&mut lazy_store, listing_id.as_bytes(), &listing)?;
+    let mut active: Vec<String> = may_load(&deps.storage, LAZY_LISTINGS_KEY)?.unwrap_or_default();
+    active.push(listing_id.clone());
+    save(&mut deps.storage, LAZY_LISTINGS_KEY, &active)?;
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![log("lazy_listing", &listing_id)],
+        data: Some(to_binary(&HandleAnswer::CreateLazyListing { listing_id })?),
+    })
+}
+
+/// Returns HandleResult
+///
+/// stops selling the remaining editions of a lazy listing.  The listing is kept so its id can
+/// not be reused, and its registered mint run is shortened to the editions already sold
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a reference to the Config
+/// * `priority` - u8 representation of highest status level this action is permitted at
+/// * `listing_id` - id of the lazy listing
+pub fn cancel_lazy_listing<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &Config,
+    priority: u8,
+    listing_id: &str,
+) -> HandleResult {
+    check_status(config.status, priority)?;
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    let mut lazy_store = PrefixedStorage::new(PREFIX_LAZY_LISTINGS, &mut deps.storage);
+    let mut listing: StoredLazyListing = may_load(&lazy_store, listing_id.as_bytes())?
+        .filter(|l: &StoredLazyListing| l.sold < l.editions)
+        .ok_or_else(|| {
+            StdError::generic_err(format!(
+                "Lazy listing {} has no editions left to sell",
+                listing_id
+            ))
+        })?;
+    if listing.creator != sender_raw && config.admin != sender_raw {
+        return Err(StdError::generic_err(format!(
+            "Only the creator or the admin can cancel lazy listing {}",
+            listing_id
+        )));
+    }
+    listing.editions = listing.sold;
+    save(&mut lazy_store, listing_id.as_bytes(), &listing)?;
+    let mut active: Vec<String> = may_load(&deps.storage, LAZY_LISTINGS_KEY)?.unwrap_or_default();
+    active.retain(|id| id != listing_id);
+    save(&mut deps.storage, LAZY_LISTINGS_KEY, &active)?;
+    if let Some(reg_idx) = listing.registry_index {
+        let mut reg_store = PrefixedStorage::new(PREFIX_MINT_RUN_REGISTRY, &mut deps.storage);
+        let mut run: StoredMintRun = load(&reg_store, &reg_idx.to_le_bytes())?;
+        run.quantity = run.minted;
+        save(&mut reg_store, &reg_idx.to_le_bytes(), &run)?;
+    }
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![log("Lazy listing cancelled", listing_id)],
+        data: Some(to_binary(&HandleAnswer::CancelLazyListing {
+            listing_id: listing_id.to_string(),
+        })?),
+    })
+}
+
+/// Returns HandleResult
+///
+/// registers or removes the public key that signs the sender's mint vouchers
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a reference to the Config
+/// * `priority` - u8 representation of highest status level this action is permitted at
+/// * `pubkey` - optional compressed secp256k1 public key
+pub fn set_voucher_signer<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &Config,
+    priority: u8,
+    pubkey: Option<Binary>,
+) -> HandleResult {
+    check_status(config.status, priority)?;
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    let minters: Vec<CanonicalAddr> = may_load(&deps.storage, MINTERS_KEY)?.unwrap_or_default();
+    if !minters.contains(&sender_raw) {
+        return Err(StdError::generic_err(
+            "Only designated minters are allowed to register a voucher signer",
+        ));
+    }
+    let mut signer_store = PrefixedStorage::new(PREFIX_VOUCHER_SIGNERS, &mut deps.storage);
+    if let Some(key) = pubkey {
+        if key.len() != 33 {
+            return Err(StdError::generic_err(
+                "The voucher signer must be a 33 byte compressed secp256k1 public key",
+            ));
+        }
+        save(&mut signer_store, sender_raw.as_slice(), &key.0)?;
+    } else {
+        remove(&mut signer_store, sender_raw.as_slice());
+    }
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::SetVoucherSigner {
+            status: Success,
+        })?),
+    })
+}
+
+/// Returns HandleResult
+///
+/// mints the token described by a mint voucher signed by its creator's registered key
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a mutable reference to the Config
+/// * `priority` - u8 representation of highest status level this action is permitted at
+/// * `voucher` - the signed voucher
+/// * `signature` - the secp256k1 signature of the sha256 hash of the voucher's sign-doc
+pub fn redeem_mint_voucher<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &mut Config,
+    priority: u8,
+    voucher: MintVoucher,
+    signature: Binary,
+) -> HandleResult {
+    check_status(config.status, priority)?;
+    if voucher.contract != env.contract.address {
+        return Err(StdError::generic_err(
+            "This voucher was issued for a different contract",
+        ));
+    }
+    if voucher.expiration.is_some_and(|e| e.is_expired(&env.block)) {
+        return Err(StdError::generic_err("This voucher has expired"));
+    }
+    let creator_raw = deps.api.canonical_address(&voucher.creator)?;
+    let minters: Vec<CanonicalAddr> = may_load(&deps.storage, MINTERS_KEY)?.unwrap_or_default();
+    if !minters.contains(&creator_raw) {
+        return Err(StdError::generic_err(format!(
+            "{} is not a minter",
+            voucher.creator
+        )));
+    }
+    let signer_store = ReadonlyPrefixedStorage::new(PREFIX_VOUCHER_SIGNERS, &deps.storage);
+    let pubkey: Vec<u8> = may_load(&signer_store, creator_raw.as_slice())?.ok_or_else(|| {
+        StdError::generic_err(format!(
+            "{} has not registered a voucher signer",
+            voucher.creator
+        ))
+    })?;
+    if signature.len() != 64 {
+        return Err(StdError::generic_err(
+            "The voucher signature must be 64 bytes",
+        ));
+    }
+    let hash = sha_256(&voucher.sign_doc()?);
+    let verified = deps
+        .api
+        .secp256k1_verify(&hash, signature.as_slice(), &pubkey)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+    if !verified {
+        return Err(StdError::generic_err(
+            "The voucher was not signed by the creator's voucher signer",
+        ));
+    }
+    // check payment
+    let price = voucher.price.u128();
+    let sent = if env.message.sent_funds.is_empty() {
+        0u128
+    } else if env.message.sent_funds.len() != 1 || env.message.sent_funds[0].denom != SALE_DENOM {
+        return Err(StdError::generic_err("Insufficient funds provided"));
+    } else {
+        env.message.sent_funds[0].amount.u128()
+    };
+    if sent < price {
+        return Err(StdError::generic_err("Insufficient funds provided"));
+    }
+    if sent > price {
+        return Err(StdError::generic_err("Funds sent exceeds funds needed"));
+    }
+    // each nonce may only be redeemed once
+    let nonce_key = voucher.nonce.to_le_bytes();
+    let mut nonce_store = PrefixedStorage::multilevel(
+        &[PREFIX_VOUCHER_NONCES, creator_raw.as_slice()],
+        &mut deps.storage,
+    );
+    if may_load::<bool, _>(&nonce_store, &nonce_key)?.is_some() {
+        return Err(StdError::generic_err(format!(
+            "Voucher nonce {} has already been redeemed",
+            voucher.nonce
+        )));
+    }
+    save(&mut nonce_store, &nonce_key, &true)?;
+    let mints = vec![Mint {
+        token_id: voucher.token_id,
+        owner: Some(voucher.recipient),
+        public_metadata: voucher.public_metadata,
+        private_metadata: voucher.private_metadata,
+        serial_number: None,
+        royalty_info: voucher.royalty_info,
+        transferable: Some(true),
+        memo: None,
+        listing: None,
+    }];
+    let idx = config.mint_cnt;
+    let token_id = mint_list(deps, &env, config, &creator_raw, mints, None)?
+        .pop()
+        .ok_or_else(|| StdError::generic_err("List of minted tokens is empty"))?;
+    // redeeming is the token's first sale, so its first resale pays royalties
+    set_sale_num(deps, &idx.to_le_bytes(), 1);
+    if price > 0 {
+        credit_sale_proceeds(&mut deps.storage, &creator_raw, SALE_DENOM, price)?;
+        record_sale_stats(&mut deps.storage, idx, price)?;
+    }
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![log("minted", &token_id)],
+        data: Some(to_binary(&HandleAnswer::RedeemMintVoucher { token_id })?),
+    })
+}
+
+/// Returns HandleResult
+///
+/// mints the next edition of a lazy listing to the buyer
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a mutable reference to the Config
+/// * `listing_id` - id of the lazy listing
+/// * `listing` - the lazy listing
+fn buy_lazy_listing<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &mut Config,
+    listing_id: &str,
+    mut listing: StoredLazyListing,
+) -> HandleResult {
+    if listing.sold >= listing.editions {
+        return Err(StdError::generic_err(format!(
+            "Lazy listing {} has sold out",
+            listing_id
+        )));
+    }
+    let price = u128::from(listing.price);
+    if env.message.sent_funds.len() != 1
+        || env.message.sent_funds[0].amount.u128() < price
+        || env.message.sent_funds[0].denom != SALE_DENOM
+    {
+        return Err(StdError::generic_err("Insufficient funds provided"));
+    }
+    if env.message.sent_funds[0].amount.u128() > price {
+        return Err(StdError::generic_err("Funds sent exceeds funds needed"));
+    }
+    // the creator mints each edition, so they must still be a minter.  The quotas of their
+    // current role are enforced when the edition is minted
+    let minters: Vec<CanonicalAddr> = may_load(&deps.storage, MINTERS_KEY)?.unwrap_or_default();
+    if !minters.contains(&listing.creator) {
+        return Err(StdError::generic_err(format!(
+            "The creator of lazy listing {} is no longer a minter",
+            listing_id
+        )));
+    }
+    listing.sold += 1;
+    let mints = vec![Mint {
+        token_id: None,
+        owner: Some(env.message.sender.clone()),
+        public_metadata: listing.public_metadata.clone(),
+        private_metadata: listing.private_metadata.clone(),
+        serial_number: Some(SerialNumber {
+            mint_run: listing.mint_run,
+            serial_number: listing.sold,
+            quantity_minted_this_run: Some(listing.editions),
+        }),
+        royalty_info: listing.royalty_info.clone(),
+        transferable: Some(true),
+        memo: None,
+        listing: None,
+    }];
+    let creator = listing.creator.clone();
+    let token_id = mint_list(
+        deps,
+        &env,
+        config,
+        &creator,
+        mints,
+        listing.mint_run_id.as_ref(),
+    )?
+    .pop()
+    .ok_or_else(|| StdError::generic_err("List of minted tokens is empty"))?;
+    let map2idx = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_INDEX, &deps.storage);
+    let idx: u32 = load(&map2idx, token_id.as_bytes())?;
+    let token_key = idx.to_le_bytes();
+    // this purchase is the edition's first sale
+    set_sale_num(deps, &token_key, 1);
+    if let Some(reg_idx) = listing.registry_index {
+        add_to_mint_run(&mut deps.storage, reg_idx, idx, &token_id)?;
+    }
+    credit_sale_proceeds(&mut deps.storage, &creator, SALE_DENOM, price)?;
+    record_sale_stats(&mut deps.storage, idx, price)?;
+    let mut lazy_store = PrefixedStorage::new(PREFIX_LAZY_LISTINGS, &mut deps.storage);
+    save(&mut lazy_store, listing_id.as_bytes(), &listing)?;
+    if listing.sold == listing.editions {
+        let mut active: Vec<String> =
+            may_load(&deps.storage, LAZY_LISTINGS_KEY)?.unwrap_or_default();
+        active.retain(|id| id != listing_id);
+        save(&mut deps.storage, LAZY_LISTINGS_KEY, &active)?;
+    }
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![log("Token sold", &token_id)],
+        data: Some(to_binary(&HandleAnswer::BuyToken {
+            token_id,
+            price: vec![Coin {
+                denom: SALE_DENOM.to_string(),
+                amount: Uint128(price),
+            }],
+        })?),
+    })
+}
+
+/// Returns HandleResult
+///
+/// sets new public and/or private metadata
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a reference to the Config
+/// * `priority` - u8 representation of highest status level this action is permitted at
+/// * `token_id` - token id String slice of token whose metadata should be updated
+/// * `public_metadata` - the optional new public metadata viewable by everyone
+/// * `private_metadata` - the optional new private metadata viewable by everyone
+pub fn set_metadata<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &Config,
+    priority: u8,
+    token_id: &str,
+    public_metadata: Option<Metadata>,
+    private_metadata: Option<Metadata>,
+) -> HandleResult {
+    check_status(config.status, priority)?;
+    let custom_err = format!("Not authorized to update metadata of token {}", token_id);
+    // if token supply is private, don't leak that the token id does not exist
+    // instead just say they are not authorized for that token
+    let opt_err = if config.token_supply_is_public {
+        None
+    } else {
+        Some(&*custom_err)
+    };
+    let (token, idx) = get_token(&deps.storage, token_id, opt_err)?;
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    check_metadata_updater(
+        &deps.storage,
+        config,
+        &env.block,
+        &token,
+        &sender_raw,
+        custom_err,
+    )?;
+    if is_metadata_frozen(&deps.storage, &idx.to_le_bytes())? {
+        return Err(StdError::generic_err(format!(
+            "The metadata of token {} is frozen",
+            token_id
+        )));
+    }
+    if let Some(public) = public_metadata {
+        set_metadata_impl(
+            &mut deps.storage,
+            &token,
+            idx,
+            PREFIX_PUB_META,
+            &public,
+            &sender_raw,
+            &env.block,
+        )?;
+    }
+    if let Some(private) = private_metadata {
+        set_metadata_impl(
+            &mut deps.storage,
+            &token,
+            idx,
+            PREFIX_PRIV_META,
+            &private,
+            &sender_raw,
+            &env.block,
+        )?;
+    }
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::SetMetadata { status: Success })?),
+    })
+}
+
+/// Returns HandleResult
+///
+/// permanently prevents the metadata of a token from being updated
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a reference to the Config
+/// * `priority` - u8 representation of highest status level this action is permitted at
+/// * `token_id` - token id String slice of token whose metadata should be frozen
+pub fn freeze_metadata<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &Config,
+    priority: u8,
+    token_id: &str,
+) -> HandleResult {
+    check_status(config.status, priority)?;
+    let custom_err = format!(
+        "Not authorized to freeze the metadata of token {}",
+        token_id
+    );
+    // if token supply is private, don't leak that the token id does not exist
+    // instead just say they are not authorized for that token
+    let opt_err = if config.token_supply_is_public {
+        None
+    } else {
+        Some(&*custom_err)
+    };
+    let (token, idx) = get_token(&deps.storage, token_id, opt_err)?;
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    check_metadata_updater(
+        &deps.storage,
+        config,
+        &env.block,
+        &token,
+        &sender_raw,
+        custom_err,
+    )?;
+    let mut frozen_store = PrefixedStorage::new(PREFIX_FROZEN_METADATA, &mut deps.storage);
+    save(&mut frozen_store, &idx.to_le_bytes(), &true)?;
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::FreezeMetadata {
+            status: Success,
+        })?),
+    })
+}
+
+/// Returns HandleResult
+///
+/// sets or removes a token's unlockable content
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a reference to the Config
+/// * `priority` - u8 representation of highest status level this action is permitted at
+/// * `token_id` - token id String slice of token whose unlockable content should be set
+/// * `unlockable` - the optional unlockable content
+pub fn set_unlockable<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &Config,
+    priority: u8,
+    token_id: &str,
+    unlockable: Option<String>,
+) -> HandleResult {
+    check_status(config.status, priority)?;
+    let custom_err = format!(
+        "Not authorized to set the unlockable content of token {}",
+        token_id
+    );
+    // if token supply is private, don't leak that the token id does not exist
+    // instead just say they are not authorized for that token
+    let opt_err = if config.token_supply_is_public {
+        None
+    } else {
+        Some(&*custom_err)
+    };
+    let (token, idx) = get_token(&deps.storage, token_id, opt_err)?;
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    if token.owner != sender_raw {
+        return Err(StdError::generic_err(custom_err));
+    }
+    let token_key = idx.to_le_bytes();
+    // the buyer must receive the content the listing advertised
+    let sale_store = ReadonlyPrefixedStorage::new(PREFIX_TOKEN_SALE_INFO, &deps.storage);
+    let sale_info: Option<TokenSaleInfo> = json_may_load(&sale_store, &token_key)?;
+    if sale_info.is_some_and(|s| s.sale_status == SaleStatus::ForSale) {
+        return Err(StdError::generic_err(format!(
+            "The unlockable content of token {} can not be changed while it is listed for sale",
+            token_id
+        )));
+    }
+    check_not_bundled(&deps.storage, idx, token_id)?;
+    let mut unlock_store = PrefixedStorage::new(PREFIX_UNLOCKABLE, &mut deps.storage);
+    if let Some(content) = unlockable {
+        save(&mut unlock_store, &token_key, &content)?;
+    } else {
+        remove(&mut unlock_store, &token_key);
+    }
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::SetUnlockable { status: Success })?),
+    })
+}
+
+/// Returns HandleResult
+///
+/// sets the trait types an address may update, or removes the address if no trait types are
+/// given
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a reference to the Config
+/// * `priority` - u8 representation of highest status level this action is permitted at
+/// * `updater` - a reference to the address of the attribute updater
+/// * `trait_types` - the trait types the updater may update
+pub fn set_attribute_updater<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &Config,
+    priority: u8,
+    updater: &HumanAddr,
+    trait_types: Vec<String>,
+) -> HandleResult {
+    check_status(config.status, priority)?;
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    if config.admin != sender_raw {
+        return Err(StdError::generic_err(
+            "This is an admin command and can only be run from the admin address",
+        ));
+    }
+    let updater_raw = deps.api.canonical_address(updater)?;
+    let mut updaters: Vec<StoredAttributeUpdater> =
+        may_load(&deps.storage, ATTRIBUTE_UPDATERS_KEY)?.unwrap_or_default();
+    updaters.retain(|u| u.updater != updater_raw);
+    if !trait_types.is_empty() {
+        updaters.push(StoredAttributeUpdater {
+            updater: updater_raw,
+            trait_types,
+        });
+    }
+    save(&mut deps.storage, ATTRIBUTE_UPDATERS_KEY, &updaters)?;
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::SetAttributeUpdater {
+            status: Success,
+        })?),
+    })
+}
+
+/// Returns HandleResult
+///
+/// adds or replaces attributes of a token's public and/or private metadata
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a reference to the Config
+/// * `priority` - u8 representation of highest status level this action is permitted at
+/// * `token_id` - token id String slice of token whose attributes should be updated
+/// * `public_attributes` - optional attributes to update in the public metadata
+/// * `private_attributes` - optional attributes to update in the private metadata
+pub fn update_attributes<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &Config,
+    priority: u8,
+    token_id: &str,
+    public_attributes: Option<Vec<Trait>>,
+    private_attributes: Option<Vec<Trait>>,
+) -> HandleResult {
+    check_status(config.status, priority)?;
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    let updaters: Vec<StoredAttributeUpdater> =
+        may_load(&deps.storage, ATTRIBUTE_UPDATERS_KEY)?.unwrap_or_default();
+    let allowed = updaters
+        .into_iter()
+        .find(|u| u.updater == sender_raw)
+        .map(|u| u.trait_types)
+        .ok_or_else(|| {
+            StdError::generic_err("Only attribute updaters may update the attributes of a token")
+        })?;
+    let custom_err = format!("Not authorized to update attributes of token {}", token_id);
+    // if token supply is private, don't leak that the token id does not exist
+    // instead just say they are not authorized for that token
+    let opt_err = if config.token_supply_is_public {
+        None
+    } else {
+        Some(&*custom_err)
+    };
+    let (token, idx) = get_token(&deps.storage, token_id, opt_err)?;
+    let token_key = idx.to_le_bytes();
+    if is_metadata_frozen(&deps.storage, &token_key)? {
+        return Err(StdError::generic_err(format!(
+            "The metadata of token {} is frozen",
+            token_id
+        )));
+    }
+    let mut logs = Vec::new();
+    for (prefix, updates) in [
+        (PREFIX_PUB_META, public_attributes),
+        (PREFIX_PRIV_META, private_attributes),
+    ] {
+        if let Some(updates) = updates {
+            let private = prefix == PREFIX_PRIV_META;
+            let old_meta: Option<Metadata> = if private {
+                let meta_store = ReadonlyPrefixedStorage::new(PREFIX_PRIV_META, &deps.storage);
+                may_load(&meta_store, &token_key)?
+            } else {
+                load_public_metadata(&deps.storage, &token_key)?
+            };
+            let metadata = apply_attribute_updates(old_meta, &updates, &allowed, token_id)?;
+            set_metadata_impl(
+                &mut deps.storage,
+                &token,
+                idx,
+                prefix,
+                &metadata,
+                &sender_raw,
+                &env.block,
+            )?;
+            let key = if private {
+                "updated_private_trait"
+            } else {
+                "updated_public_trait"
+            };
+            // only the trait types are logged so private values are not exposed
+            logs.extend(
+                updates
+                    .iter()
+                    .filter_map(|t| t.trait_type.as_ref().map(|tt| log(key, tt))),
+            );
+        }
+    }
+    Ok(HandleResponse {
+        messages: vec![],
+        log: logs,
+        data: Some(to_binary(&HandleAnswer::UpdateAttributes {
+            status: Success,
+        })?),
+    })
+}
+
+/// Returns StdResult<Metadata> after replacing or appending the updated attributes
+///
+/// # Arguments
+///
+/// * `metadata` - the token's current metadata, if any
+/// * `updates` - the attributes to update
+/// * `allowed` - the trait types the updater may update
+/// * `token_id` - token id String slice of the token being updated
+fn apply_attribute_updates(
+    metadata: Option<Metadata>,
+    updates: &[Trait],
+    allowed: &[String],
+    token_id: &str,
+) -> StdResult<Metadata> {
+    let mut metadata = metadata.unwrap_or(Metadata {
+        token_uri: None,
+        extension: None,
+    });
+    if metadata.token_uri.is_some() {
+        return Err(StdError::generic_err(format!(
+            "The attributes of token {} can not be updated because its metadata is off-chain",
+            token_id
+        )));
+    }
+    let attributes = metadata
+        .extension
+        .get_or_insert_with(Extension::default)
+        .attributes
+        .get_or_insert_with(Vec::new);
+    for update in updates.iter() {
+        let trait_type = update
+            .trait_type
+            .as_ref()
+            .ok_or_else(|| StdError::generic_err("Updated attributes must specify a trait_type"))?;
+        if !allowed.contains(trait_type) {
+            return Err(StdError::generic_err(format!(
+                "Not authorized to update the {} attribute",
+                trait_type
+            )));
+        }
+        if let Some(existing) = attributes
+            .iter_mut()
+            .find(|a| a.trait_type.as_ref() == Some(trait_type))
+        {
+            *existing = update.clone();
+        } else {
+            attributes.push(update.clone());
+        }
+    }
+    Ok(metadata)
+}
+
+/// Returns HandleResult
+///
+/// permanently prevents the metadata of every token from being updated
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a reference to the Config
+/// * `priority` - u8 representation of highest status level this action is permitted at
+pub fn freeze_all_metadata<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &Config,
+    priority: u8,
+) -> HandleResult {
+    check_status(config.status, priority)?;
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    if config.admin != sender_raw {
+        return Err(StdError::generic_err(
+            "This is an admin command and can only be run from the admin address",
+        ));
+    }
+    save(&mut deps.storage, METADATA_FROZEN_KEY, &true)?;
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::FreezeAllMetadata {
+            status: Success,
+        })?),
+    })
+}
+
+/// Returns HandleResult
+///
+/// sets new royalty information for a specified token or if no token ID is provided, sets new
+/// royalty information as the contract's default
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a reference to the Config
+/// * `priority` - u8 representation of highest status level this action is permitted at
+/// * `token_id` - optional token id String slice of token whose royalty info should be updated
+/// * `royalty_info` - a optional reference to the new RoyaltyInfo
+pub fn set_royalty_info<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &mut Config,
+    priority: u8,
+    token_id: Option<&str>,
+    royalty_info: Option<&RoyaltyInfo>,
+) -> HandleResult {
+    check_status(config.status, priority)?;
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    // set a token's royalties
+    if let Some(id) = token_id {
+        let custom_err = "A token's RoyaltyInfo may only be set by the token creator when they are also the token owner";
+        // if token supply is private, don't leak that the token id does not exist
+        // instead just say they are not authorized for that token
+        let opt_err = if config.token_supply_is_public {
+            None
+        } else {
+            Some(custom_err)
+        };
+        let (token, idx) = get_token(&deps.storage, id, opt_err)?;
+        if !token.transferable {
+            return Err(StdError::generic_err(
+                "Non-transferable tokens can not be sold, so royalties are meaningless",
+            ));
+        }
+        let token_key = idx.to_le_bytes();
+        let run_store = ReadonlyPrefixedStorage::new(PREFIX_MINT_RUN, &deps.storage);
+        let mint_run: StoredMintRunInfo = load(&run_store, &token_key)?;
+        // once a token has been sold, its buyers are relying on the royalties it was sold with,
+        // so only the creator or admin may change them, and only to lower or redirect them
+        let sold = get_sale_num_type(&deps.storage, &token_key) == 1;
+        let prev_roy = if sold {
+            if sender_raw != mint_run.token_creator && sender_raw != config.admin {
+                return Err(StdError::generic_err(
+                    "After a token's first sale, its royalties may only be changed by the token creator or the admin",
+                ));
+            }
+            Some(effective_royalties(&deps.storage, &token_key)?)
+        } else {
+            if sender_raw != mint_run.token_creator || sender_raw != token.owner {
+                return Err(StdError::generic_err(custom_err));
+            }
+            None
+        };
+        let default_roy = royalty_info.as_ref().map_or_else(
+            || may_load::<StoredRoyaltyInfo, _>(&deps.storage, DEFAULT_ROYALTY_KEY),
+            |_r| Ok(None),
+        )?;
+        let mut roy_store = PrefixedStorage::new(PREFIX_ROYALTY_INFO, &mut deps.storage);
+        let old_roy: Option<StoredRoyaltyInfo> = may_load(&roy_store, &token_key)?;
+        store_royalties(
+            &mut roy_store,
+            &deps.api,
+            royalty_info,
+            default_roy.as_ref(),
+            &token_key,
+            config.max_royalty_bps,
+        )?;
+        let new_roy: Option<StoredRoyaltyInfo> = may_load(&roy_store, &token_key)?;
+        update_royalty_index(
+            &mut deps.storage,
+            idx,
+            &royalty_recipients(old_roy.as_ref()),
+            &royalty_recipients(new_roy.as_ref()),
+        )?;
+        if let Some(prev) = prev_roy {
+            let new_roy = effective_royalties(&deps.storage, &token_key)?;
+            let (prev_num, prev_den) = royalty_total(prev.as_ref());
+            let (new_num, new_den) = royalty_total(new_roy.as_ref());
+            if new_num * prev_den > prev_num * new_den {
+                return Err(StdError::generic_err(
+                    "After a token's first sale, its royalties may only be lowered or redirected",
+                ));
+            }
+        }
+        store_royalty_change(
+            &mut deps.storage,
+            config,
+            &env.block,
+            id.to_string(),
+            sender_raw,
+            &token.owner,
+        )?;
+        save(&mut deps.storage, CONFIG_KEY, &config)?;
+    // set default royalty
+    } else {
+        let minters: Vec<CanonicalAddr> = may_load(&deps.storage, MINTERS_KEY)?.unwrap_or_default();
+        if !minters.contains(&sender_raw) {
+            return Err(StdError::generic_err(
+                "Only designated minters can set default royalties for the contract",
+            ));
+        }
+        store_royalties(
+            &mut deps.storage,
+            &deps.api,
+            royalty_info,
+            None,
+            DEFAULT_ROYALTY_KEY,
+            config.max_royalty_bps,
+        )?;
+    };
+
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::SetRoyaltyInfo {
+            status: Success,
+        })?),
+    })
+}
+
+/// Returns HandleResult
+///
+/// makes the sealed private metadata public
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a reference to the Config
+/// * `priority` - u8 representation of highest status level this action is permitted at
+/// * `token_id` - token id String slice of token whose metadata should be updated
+pub fn reveal<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &Config,
+    priority: u8,
+    token_id: &str,
+) -> HandleResult {
+    check_status(config.status, priority)?;
+    if !config.sealed_metadata_is_enabled {
+        return Err(StdError::generic_err(
+            "Sealed metadata functionality is not enabled for this contract",
+        ));
+    }
+    if may_load::<StoredRevealCommitment, _>(&deps.storage, REVEAL_COMMITMENT_KEY)?.is_some() {
+        return Err(StdError::generic_err(
+            "This collection is revealed by the admin with RevealCollection",
+        ));
+    }
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    let custom_err = format!("You do not own token {}", token_id);
+    // if token supply is private, don't leak that the token id does not exist
+    // instead just say they do not own that token
+    let opt_err = if config.token_supply_is_public {
+        None
+    } else {
+        Some(&*custom_err)
+    };
+    let (mut token, idx) = get_token(&deps.storage, token_id, opt_err)?;
+    if token.unwrapped {
+        return Err(StdError::generic_err(
+            "This token has already been unwrapped",
+        ));
+    }
+    if token.owner != sender_raw {
+        return Err(StdError::generic_err(custom_err));
+    }
+    token.unwrapped = true;
+    let token_key = idx.to_le_bytes();
+    let mut info_store = PrefixedStorage::new(PREFIX_INFOS, &mut deps.storage);
+    json_save(&mut info_store, &token_key, &token)?;
+    if !config.unwrap_to_private {
+        let mut priv_store = PrefixedStorage::new(PREFIX_PRIV_META, &mut deps.storage);
+        let may_priv: Option<Metadata> = may_load(&priv_store, &token_key)?;
+        if let Some(metadata) = may_priv {
+            remove(&mut priv_store, &token_key);
+            let old_pub = load_public_metadata(&deps.storage, &token_key)?;
+            update_trait_index(&mut deps.storage, idx, old_pub.as_ref(), Some(&metadata))?;
+            let mut pub_store = PrefixedStorage::new(PREFIX_PUB_META, &mut deps.storage);
+            save(&mut pub_store, &token_key, &metadata)?;
+        }
+    }
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::Reveal { status: Success })?),
+    })
+}
+
+/// Returns HandleResult
+///
+/// grants/revokes trasfer permission on a token
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a reference to the Config
+/// * `priority` - u8 representation of highest status level this action is permitted at
+/// * `spender` - a reference to the address being granted permission
+/// * `token_id` - string slice of the token id to grant permission to
+/// * `expires` - optional Expiration for this approval
+/// * `is_approve` - true if this is an Approve call
+#[allow(clippy::too_many_arguments)]
+pub fn approve_revoke<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &Config,
+    priority: u8,
+    spender: &HumanAddr,
+    token_id: &str,
+    expires: Option<Expiration>,
+    is_approve: bool,
+) -> HandleResult {
+    check_status(config.status, priority)?;
+    let address_raw = deps.api.canonical_address(spender)?;
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    let custom_err = format!(
+        "Not authorized to grant/revoke transfer permission for token {}",
+        token_id
+    );
+    // if token supply is private, don't leak that the token id does not exist
+    // instead just say they are not authorized for that token
+    let opt_err = if config.token_supply_is_public {
+        None
+    } else {
+        Some(&*custom_err)
+    };
+    let (token, idx) = get_token(&deps.storage, token_id, opt_err)?;
+    let mut all_perm: Option<Vec<Permission>> = None;
+    let mut from_oper = false;
+    let transfer_idx = PermissionType::Transfer.to_usize();
+    // if not called by the owner, check if message sender has operator status
+    if token.owner != sender_raw {
+        let all_store = ReadonlyPrefixedStorage::new(PREFIX_ALL_PERMISSIONS, &deps.storage);
+        let may_list: Option<Vec<Permission>> = json_may_load(&all_store, token.owner.as_slice())?;
+        if let Some(list) = may_list.clone() {
+            if let Some(perm) = list.iter().find(|&p| p.address == sender_raw) {
+                if let Some(exp) = perm.expirations[transfer_idx] {
+                    if exp.is_expired(&env.block) {
                         return Err(StdError::generic_err(format!(
                             "Transfer authority for all tokens of {} has expired",
                             &deps.api.human_address(&token.owner)?
@@ -1497,43 +3802,129 @@ pub fn approve_revoke<S: Storage, A: Api, Q: Querier>(
         &address_raw,
         &owner,
         &mut proc_info,
-        all_perm,
+        all_perm,
+    )?;
+    let res = HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&response)?),
+    };
+    Ok(res)
+}
+
+/// Returns HandleResult
+///
+/// makes an address' token ownership private
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a reference to the Config
+/// * `priority` - u8 representation of highest status level this action is permitted at
+pub fn make_owner_private<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &Config,
+    priority: u8,
+) -> HandleResult {
+    check_status(config.status, priority)?;
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    // only need to do this if the contract has public ownership
+    if config.owner_is_public {
+        let mut priv_store = PrefixedStorage::new(PREFIX_OWNER_PRIV, &mut deps.storage);
+        save(&mut priv_store, sender_raw.as_slice(), &false)?
+    }
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::MakeOwnershipPrivate {
+            status: Success,
+        })?),
+    })
+}
+
+/// Returns HandleResult
+///
+/// adds/revokes access for everyone
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a reference to the Config
+/// * `priority` - u8 representation of highest status level this action is permitted at
+/// * `token_id` - optional token id to apply approvals to
+/// * `view_owner` - optional access level for viewing token ownership
+/// * `view_private_metadata` - optional access level for viewing private metadata
+/// * `expires` - optional Expiration for this approval
+#[allow(clippy::too_many_arguments)]
+pub fn set_global_approval<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &Config,
+    priority: u8,
+    token_id: Option<String>,
+    view_owner: Option<AccessLevel>,
+    view_private_metadata: Option<AccessLevel>,
+    expires: Option<Expiration>,
+) -> HandleResult {
+    check_status(config.status, priority)?;
+    let token_given: bool;
+    // use this "address" to represent global permission
+    let global_raw = CanonicalAddr(Binary::from(b"public"));
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    let mut custom_err = String::new();
+    let (token, idx) = if let Some(id) = token_id {
+        token_given = true;
+        custom_err = format!("You do not own token {}", id);
+        // if token supply is private, don't leak that the token id does not exist
+        // instead just say they do not own that token
+        let opt_err = if config.token_supply_is_public {
+            None
+        } else {
+            Some(&*custom_err)
+        };
+        get_token(&deps.storage, &id, opt_err)?
+    } else {
+        token_given = false;
+        (
+            Token {
+                owner: sender_raw.clone(),
+                permissions: Vec::new(),
+                unwrapped: false,
+                transferable: true,
+            },
+            0,
+        )
+    };
+    // if trying to set token permissions when you are not the owner
+    if token_given && token.owner != sender_raw {
+        return Err(StdError::generic_err(custom_err));
+    }
+    let mut accesses: [Option<AccessLevel>; 3] = [None, None, None];
+    accesses[PermissionType::ViewOwner.to_usize()] = view_owner;
+    accesses[PermissionType::ViewMetadata.to_usize()] = view_private_metadata;
+    let mut proc_info = ProcessAccInfo {
+        token,
+        idx,
+        token_given,
+        accesses,
+        expires,
+        from_oper: false,
+    };
+    process_accesses(
+        &mut deps.storage,
+        &env,
+        &global_raw,
+        &sender_raw,
+        &mut proc_info,
+        None,
     )?;
-    let res = HandleResponse {
-        messages: vec![],
-        log: vec![],
-        data: Some(to_binary(&response)?),
-    };
-    Ok(res)
-}
-
-/// Returns HandleResult
-///
-/// makes an address' token ownership private
-///
-/// # Arguments
-///
-/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
-/// * `env` - Env of contract's environment
-/// * `config` - a reference to the Config
-/// * `priority` - u8 representation of highest status level this action is permitted at
-pub fn make_owner_private<S: Storage, A: Api, Q: Querier>(
-    deps: &mut Extern<S, A, Q>,
-    env: Env,
-    config: &Config,
-    priority: u8,
-) -> HandleResult {
-    check_status(config.status, priority)?;
-    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
-    // only need to do this if the contract has public ownership
-    if config.owner_is_public {
-        let mut priv_store = PrefixedStorage::new(PREFIX_OWNER_PRIV, &mut deps.storage);
-        save(&mut priv_store, sender_raw.as_slice(), &false)?
-    }
     Ok(HandleResponse {
         messages: vec![],
         log: vec![],
-        data: Some(to_binary(&HandleAnswer::MakeOwnershipPrivate {
+        data: Some(to_binary(&HandleAnswer::SetGlobalApproval {
             status: Success,
         })?),
     })
@@ -1541,7 +3932,7 @@ pub fn make_owner_private<S: Storage, A: Api, Q: Querier>(
 
 /// Returns HandleResult
 ///
-/// adds/revokes access for everyone
+/// sets specified permissions for an address
 ///
 /// # Arguments
 ///
@@ -1549,25 +3940,30 @@ pub fn make_owner_private<S: Storage, A: Api, Q: Querier>(
 /// * `env` - Env of contract's environment
 /// * `config` - a reference to the Config
 /// * `priority` - u8 representation of highest status level this action is permitted at
+/// * `address` - a reference to the address being granted permission
 /// * `token_id` - optional token id to apply approvals to
 /// * `view_owner` - optional access level for viewing token ownership
 /// * `view_private_metadata` - optional access level for viewing private metadata
+/// * `transfer` - optional access level for transferring tokens
 /// * `expires` - optional Expiration for this approval
+/// * `response_type` - which response to return for SetWhitelistedApproval, ApproveAll, or RevokeAll
 #[allow(clippy::too_many_arguments)]
-pub fn set_global_approval<S: Storage, A: Api, Q: Querier>(
+pub fn set_whitelisted_approval<S: Storage, A: Api, Q: Querier>(
     deps: &mut Extern<S, A, Q>,
     env: Env,
     config: &Config,
     priority: u8,
+    address: &HumanAddr,
     token_id: Option<String>,
     view_owner: Option<AccessLevel>,
     view_private_metadata: Option<AccessLevel>,
+    transfer: Option<AccessLevel>,
     expires: Option<Expiration>,
+    response_type: SetAppResp,
 ) -> HandleResult {
     check_status(config.status, priority)?;
     let token_given: bool;
-    // use this "address" to represent global permission
-    let global_raw = CanonicalAddr(Binary::from(b"public"));
+    let address_raw = deps.api.canonical_address(address)?;
     let sender_raw = deps.api.canonical_address(&env.message.sender)?;
     let mut custom_err = String::new();
     let (token, idx) = if let Some(id) = token_id {
@@ -1600,6 +3996,7 @@ pub fn set_global_approval<S: Storage, A: Api, Q: Querier>(
     let mut accesses: [Option<AccessLevel>; 3] = [None, None, None];
     accesses[PermissionType::ViewOwner.to_usize()] = view_owner;
     accesses[PermissionType::ViewMetadata.to_usize()] = view_private_metadata;
+    accesses[PermissionType::Transfer.to_usize()] = transfer;
     let mut proc_info = ProcessAccInfo {
         token,
         idx,
@@ -1611,15 +4008,284 @@ pub fn set_global_approval<S: Storage, A: Api, Q: Querier>(
     process_accesses(
         &mut deps.storage,
         &env,
-        &global_raw,
+        &address_raw,
         &sender_raw,
         &mut proc_info,
         None,
     )?;
+    let response = match response_type {
+        SetAppResp::SetWhitelistedApproval => {
+            HandleAnswer::SetWhitelistedApproval { status: Success }
+        }
+        SetAppResp::ApproveAll => HandleAnswer::ApproveAll { status: Success },
+        SetAppResp::RevokeAll => HandleAnswer::RevokeAll { status: Success },
+    };
+    let res = HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&response)?),
+    };
+    Ok(res)
+}
+
+/// Returns HandleResult
+///
+/// burns many tokens
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a mutable reference to the Config
+/// * `priority` - u8 representation of highest ContractStatus level this action is permitted
+/// * `burns` - the list of burns to perform
+pub fn batch_burn_nft<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &mut Config,
+    priority: u8,
+    burns: Vec<Burn>,
+) -> HandleResult {
+    check_status(config.status, priority)?;
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    burn_list(deps, &env.block, config, &sender_raw, burns)?;
+    let res = HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::BatchBurnNft { status: Success })?),
+    };
+    Ok(res)
+}
+
+/// Returns HandleResult
+///
+/// burns a token
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a mutable reference to the Config
+/// * `priority` - u8 representation of highest ContractStatus level this action is permitted
+/// * `token_id` - token id String of token to be burnt
+/// * `memo` - optional memo for the burn tx
+fn burn_nft<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &mut Config,
+    priority: u8,
+    token_id: String,
+    memo: Option<String>,
+) -> HandleResult {
+    check_status(config.status, priority)?;
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    let burns = vec![Burn {
+        token_ids: vec![token_id],
+        memo,
+    }];
+    burn_list(deps, &env.block, config, &sender_raw, burns)?;
+    let res = HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::BurnNft { status: Success })?),
+    };
+    Ok(res)
+}
+
+/// Returns HandleResult
+///
+/// revokes a non-transferable token by burning it
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a mutable reference to the Config
+/// * `priority` - u8 representation of highest ContractStatus level this action is permitted
+/// * `token_id` - token id String of token to be revoked
+/// * `memo` - optional memo for the revoke tx
+fn revoke_non_transferable<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &mut Config,
+    priority: u8,
+    token_id: String,
+    memo: Option<String>,
+) -> HandleResult {
+    check_status(config.status, priority)?;
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    let (token, idx) = get_issued_token(&deps.storage, config, &sender_raw, &token_id, "revoke")?;
+    let mut inv_updates: Vec<InventoryUpdate> = Vec::new();
+    remove_token(deps, config, &token, idx, &token_id, &mut inv_updates)?;
+    store_revoke(
+        &mut deps.storage,
+        config,
+        &env.block,
+        token_id,
+        token.owner,
+        sender_raw,
+        memo,
+    )?;
+    save(&mut deps.storage, CONFIG_KEY, &config)?;
+    update_owner_inventory(
+        &mut deps.storage,
+        &inv_updates,
+        PermissionType::ViewOwner.num_types(),
+    )?;
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::RevokeNonTransferable {
+            status: Success,
+        })?),
+    })
+}
+
+/// Returns HandleResult
+///
+/// moves a non-transferable token to a new owner
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a mutable reference to the Config
+/// * `priority` - u8 representation of highest ContractStatus level this action is permitted
+/// * `token_id` - token id String of token to be reissued
+/// * `new_owner` - a reference to the token's new owner
+/// * `memo` - optional memo for the reissue tx
+fn reissue_non_transferable<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &mut Config,
+    priority: u8,
+    token_id: String,
+    new_owner: &HumanAddr,
+    memo: Option<String>,
+) -> HandleResult {
+    check_status(config.status, priority)?;
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    let (mut token, idx) =
+        get_issued_token(&deps.storage, config, &sender_raw, &token_id, "reissue")?;
+    let recipient = deps.api.canonical_address(new_owner)?;
+    let old_owner = token.owner.clone();
+    if old_owner == recipient {
+        return Err(StdError::generic_err(format!(
+            "Attempting to reissue token ID: {} to the address that already owns it",
+            &token_id
+        )));
+    }
+    let mut inv_updates: Vec<InventoryUpdate> = Vec::new();
+    change_owner(deps, &mut token, idx, &recipient, &mut inv_updates)?;
+    store_reissue(
+        &mut deps.storage,
+        config,
+        &env.block,
+        token_id,
+        old_owner,
+        sender_raw,
+        recipient,
+        memo,
+    )?;
+    save(&mut deps.storage, CONFIG_KEY, &config)?;
+    update_owner_inventory(
+        &mut deps.storage,
+        &inv_updates,
+        PermissionType::ViewOwner.num_types(),
+    )?;
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::ReissueNonTransferable {
+            status: Success,
+        })?),
+    })
+}
+
+/// Returns StdResult<(Token, u32)>
+///
+/// returns a non-transferable token and its index if the sender was its original minter
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `config` - a reference to the Config
+/// * `sender_raw` - a reference to the message sender address
+/// * `token_id` - token id String slice of the token
+/// * `action` - string slice describing the action for error messages
+fn get_issued_token<S: ReadonlyStorage>(
+    storage: &S,
+    config: &Config,
+    sender_raw: &CanonicalAddr,
+    token_id: &str,
+    action: &str,
+) -> StdResult<(Token, u32)> {
+    let custom_err = format!(
+        "Only the original minter of token {} can {} it",
+        token_id, action
+    );
+    // if token supply is private, don't leak that the token id does not exist
+    // instead just say they are not authorized for that token
+    let opt_err = if config.token_supply_is_public {
+        None
+    } else {
+        Some(&*custom_err)
+    };
+    let (token, idx) = get_token(storage, token_id, opt_err)?;
+    let run_store = ReadonlyPrefixedStorage::new(PREFIX_MINT_RUN, storage);
+    let mint_run: Option<StoredMintRunInfo> = may_load(&run_store, &idx.to_le_bytes())?;
+    if mint_run.map_or(true, |r| r.token_creator != *sender_raw) {
+        return Err(StdError::generic_err(custom_err));
+    }
+    if token.transferable {
+        return Err(StdError::generic_err(
+            "Only non-transferable tokens can be revoked or reissued by their minter",
+        ));
+    }
+    Ok((token, idx))
+}
+
+/// Returns HandleResult
+///
+/// registers a SNIP-721 contract whose tokens can be attached to this contract's tokens, and
+/// registers this contract's BatchReceiveNft with it
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a reference to the Config
+/// * `priority` - u8 representation of highest ContractStatus level this action is permitted
+/// * `contract` - a reference to the address of the child contract
+/// * `code_hash` - code hash of the child contract
+fn register_child_contract<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &Config,
+    priority: u8,
+    contract: &HumanAddr,
+    code_hash: String,
+) -> HandleResult {
+    check_status(config.status, priority)?;
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    if config.admin != sender_raw {
+        return Err(StdError::generic_err(
+            "This is an admin command and can only be run from the admin address",
+        ));
+    }
+    let contract_raw = deps.api.canonical_address(contract)?;
+    let mut contract_store = PrefixedStorage::new(PREFIX_CHILD_CONTRACTS, &mut deps.storage);
+    save(&mut contract_store, contract_raw.as_slice(), &code_hash)?;
+    let register_msg = Snip721HandleMsg::RegisterReceiveNft {
+        code_hash: env.contract_code_hash,
+        also_implements_batch_receive_nft: Some(true),
+        padding: None,
+    }
+    .to_cosmos_msg(code_hash, contract.clone(), None)?;
     Ok(HandleResponse {
-        messages: vec![],
+        messages: vec![register_msg],
         log: vec![],
-        data: Some(to_binary(&HandleAnswer::SetGlobalApproval {
+        data: Some(to_binary(&HandleAnswer::RegisterChildContract {
             status: Success,
         })?),
     })
@@ -1627,134 +4293,152 @@ pub fn set_global_approval<S: Storage, A: Api, Q: Querier>(
 
 /// Returns HandleResult
 ///
-/// sets specified permissions for an address
+/// attaches a token of this contract to a parent token, escrowing the child in this contract
 ///
 /// # Arguments
 ///
 /// * `deps` - mutable reference to Extern containing all the contract's external dependencies
 /// * `env` - Env of contract's environment
-/// * `config` - a reference to the Config
-/// * `priority` - u8 representation of highest status level this action is permitted at
-/// * `address` - a reference to the address being granted permission
-/// * `token_id` - optional token id to apply approvals to
-/// * `view_owner` - optional access level for viewing token ownership
-/// * `view_private_metadata` - optional access level for viewing private metadata
-/// * `transfer` - optional access level for transferring tokens
-/// * `expires` - optional Expiration for this approval
-/// * `response_type` - which response to return for SetWhitelistedApproval, ApproveAll, or RevokeAll
-#[allow(clippy::too_many_arguments)]
-pub fn set_whitelisted_approval<S: Storage, A: Api, Q: Querier>(
+/// * `config` - a mutable reference to the Config
+/// * `priority` - u8 representation of highest ContractStatus level this action is permitted
+/// * `parent_token_id` - token id String slice of the parent token
+/// * `child_token_id` - token id String of the child token
+fn attach_child<S: Storage, A: Api, Q: Querier>(
     deps: &mut Extern<S, A, Q>,
     env: Env,
-    config: &Config,
+    config: &mut Config,
     priority: u8,
-    address: &HumanAddr,
-    token_id: Option<String>,
-    view_owner: Option<AccessLevel>,
-    view_private_metadata: Option<AccessLevel>,
-    transfer: Option<AccessLevel>,
-    expires: Option<Expiration>,
-    response_type: SetAppResp,
+    parent_token_id: &str,
+    child_token_id: String,
 ) -> HandleResult {
     check_status(config.status, priority)?;
-    let token_given: bool;
-    let address_raw = deps.api.canonical_address(address)?;
     let sender_raw = deps.api.canonical_address(&env.message.sender)?;
-    let mut custom_err = String::new();
-    let (token, idx) = if let Some(id) = token_id {
-        token_given = true;
-        custom_err = format!("You do not own token {}", id);
-        // if token supply is private, don't leak that the token id does not exist
-        // instead just say they do not own that token
-        let opt_err = if config.token_supply_is_public {
-            None
-        } else {
-            Some(&*custom_err)
-        };
-        get_token(&deps.storage, &id, opt_err)?
+    let parent_idx = get_parent(&deps.storage, config, &sender_raw, parent_token_id)?;
+    let custom_err = format!("You must own token {} to attach it", child_token_id);
+    // if token supply is private, don't leak that the token id does not exist
+    // instead just say they are not authorized for that token
+    let opt_err = if config.token_supply_is_public {
+        None
     } else {
-        token_given = false;
-        (
-            Token {
-                owner: sender_raw.clone(),
-                permissions: Vec::new(),
-                unwrapped: false,
-                transferable: true,
-            },
-            0,
-        )
+        Some(&*custom_err)
     };
-    // if trying to set token permissions when you are not the owner
-    if token_given && token.owner != sender_raw {
+    let (mut child, child_idx) = get_token(&deps.storage, &child_token_id, opt_err)?;
+    if child.owner != sender_raw {
         return Err(StdError::generic_err(custom_err));
     }
-    let mut accesses: [Option<AccessLevel>; 3] = [None, None, None];
-    accesses[PermissionType::ViewOwner.to_usize()] = view_owner;
-    accesses[PermissionType::ViewMetadata.to_usize()] = view_private_metadata;
-    accesses[PermissionType::Transfer.to_usize()] = transfer;
-    let mut proc_info = ProcessAccInfo {
-        token,
-        idx,
-        token_given,
-        accesses,
-        expires,
-        from_oper: false,
-    };
-    process_accesses(
+    if child_idx == parent_idx {
+        return Err(StdError::generic_err(
+            "A token can not be attached to itself",
+        ));
+    }
+    if !child.transferable {
+        return Err(StdError::generic_err(format!(
+            "Non-transferable token {} can not be attached",
+            child_token_id
+        )));
+    }
+    let sale_store = ReadonlyPrefixedStorage::new(PREFIX_TOKEN_SALE_INFO, &deps.storage);
+    let sale_info: Option<TokenSaleInfo> = json_may_load(&sale_store, &child_idx.to_le_bytes())?;
+    if sale_info.is_some_and(|s| s.sale_status == SaleStatus::ForSale) {
+        return Err(StdError::generic_err(format!(
+            "Token {} can not be attached while it is listed for sale",
+            child_token_id
+        )));
+    }
+    check_not_bundled(&deps.storage, child_idx, &child_token_id)?;
+    // escrow the child in this contract
+    let my_address: CanonicalAddr = load(&deps.storage, MY_ADDRESS_KEY)?;
+    let mut inv_updates: Vec<InventoryUpdate> = Vec::new();
+    change_owner(deps, &mut child, child_idx, &my_address, &mut inv_updates)?;
+    store_transfer(
         &mut deps.storage,
-        &env,
-        &address_raw,
-        &sender_raw,
-        &mut proc_info,
+        config,
+        &env.block,
+        child_token_id.clone(),
+        sender_raw,
+        None,
+        my_address,
         None,
     )?;
-    let response = match response_type {
-        SetAppResp::SetWhitelistedApproval => {
-            HandleAnswer::SetWhitelistedApproval { status: Success }
-        }
-        SetAppResp::ApproveAll => HandleAnswer::ApproveAll { status: Success },
-        SetAppResp::RevokeAll => HandleAnswer::RevokeAll { status: Success },
-    };
-    let res = HandleResponse {
+    save(&mut deps.storage, CONFIG_KEY, &config)?;
+    update_owner_inventory(
+        &mut deps.storage,
+        &inv_updates,
+        PermissionType::ViewOwner.num_types(),
+    )?;
+    add_children(
+        &mut deps.storage,
+        parent_idx,
+        vec![StoredChild {
+            contract: None,
+            token_id: child_token_id,
+        }],
+    )?;
+    Ok(HandleResponse {
         messages: vec![],
         log: vec![],
-        data: Some(to_binary(&response)?),
-    };
-    Ok(res)
+        data: Some(to_binary(&HandleAnswer::AttachChild { status: Success })?),
+    })
 }
 
 /// Returns HandleResult
 ///
-/// burns many tokens
+/// attaches tokens a registered child contract sent to this contract to a parent token
 ///
 /// # Arguments
 ///
 /// * `deps` - mutable reference to Extern containing all the contract's external dependencies
 /// * `env` - Env of contract's environment
-/// * `config` - a mutable reference to the Config
+/// * `config` - a reference to the Config
 /// * `priority` - u8 representation of highest ContractStatus level this action is permitted
-/// * `burns` - the list of burns to perform
-pub fn batch_burn_nft<S: Storage, A: Api, Q: Querier>(
+/// * `from` - a reference to the previous owner of the sent tokens
+/// * `token_ids` - list of the sent tokens
+/// * `msg` - optional AttachChildMsg naming the parent token
+/// * `answer` - the HandleAnswer to return
+#[allow(clippy::too_many_arguments)]
+fn receive_children<S: Storage, A: Api, Q: Querier>(
     deps: &mut Extern<S, A, Q>,
     env: Env,
-    config: &mut Config,
+    config: &Config,
     priority: u8,
-    burns: Vec<Burn>,
+    from: &HumanAddr,
+    token_ids: Vec<String>,
+    msg: Option<Binary>,
+    answer: HandleAnswer,
 ) -> HandleResult {
     check_status(config.status, priority)?;
-    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
-    burn_list(deps, &env.block, config, &sender_raw, burns)?;
-    let res = HandleResponse {
+    let contract_raw = deps.api.canonical_address(&env.message.sender)?;
+    let contract_store = ReadonlyPrefixedStorage::new(PREFIX_CHILD_CONTRACTS, &deps.storage);
+    if may_load::<String, _>(&contract_store, contract_raw.as_slice())?.is_none() {
+        return Err(StdError::generic_err(format!(
+            "{} is not a registered child contract",
+            env.message.sender
+        )));
+    }
+    let attach: AttachChildMsg =
+        from_binary(&msg.ok_or_else(|| {
+            StdError::generic_err("Received tokens must include an AttachChildMsg")
+        })?)?;
+    let from_raw = deps.api.canonical_address(from)?;
+    let parent_idx = get_parent(&deps.storage, config, &from_raw, &attach.parent_token_id)?;
+    let children = token_ids
+        .into_iter()
+        .map(|token_id| StoredChild {
+            contract: Some(contract_raw.clone()),
+            token_id,
+        })
+        .collect();
+    add_children(&mut deps.storage, parent_idx, children)?;
+    Ok(HandleResponse {
         messages: vec![],
         log: vec![],
-        data: Some(to_binary(&HandleAnswer::BatchBurnNft { status: Success })?),
-    };
-    Ok(res)
+        data: Some(to_binary(&answer)?),
+    })
 }
 
 /// Returns HandleResult
 ///
-/// burns a token
+/// detaches a child from a parent token and gives it to the parent's owner
 ///
 /// # Arguments
 ///
@@ -1762,29 +4446,139 @@ pub fn batch_burn_nft<S: Storage, A: Api, Q: Querier>(
 /// * `env` - Env of contract's environment
 /// * `config` - a mutable reference to the Config
 /// * `priority` - u8 representation of highest ContractStatus level this action is permitted
-/// * `token_id` - token id String of token to be burnt
-/// * `memo` - optional memo for the burn tx
-fn burn_nft<S: Storage, A: Api, Q: Querier>(
+/// * `parent_token_id` - token id String slice of the parent token
+/// * `child` - the child to detach
+fn detach_child<S: Storage, A: Api, Q: Querier>(
     deps: &mut Extern<S, A, Q>,
     env: Env,
     config: &mut Config,
     priority: u8,
-    token_id: String,
-    memo: Option<String>,
+    parent_token_id: &str,
+    child: ChildToken,
 ) -> HandleResult {
     check_status(config.status, priority)?;
     let sender_raw = deps.api.canonical_address(&env.message.sender)?;
-    let burns = vec![Burn {
-        token_ids: vec![token_id],
-        memo,
-    }];
-    burn_list(deps, &env.block, config, &sender_raw, burns)?;
-    let res = HandleResponse {
-        messages: vec![],
+    let parent_idx = get_parent(&deps.storage, config, &sender_raw, parent_token_id)?;
+    let stored = child.to_stored(&deps.api)?;
+    let mut child_store = PrefixedStorage::new(PREFIX_CHILDREN, &mut deps.storage);
+    let mut children: Vec<StoredChild> =
+        may_load(&child_store, &parent_idx.to_le_bytes())?.unwrap_or_default();
+    let pos = children.iter().position(|c| *c == stored).ok_or_else(|| {
+        StdError::generic_err(format!(
+            "Token {} does not hold token {}",
+            parent_token_id, child.token_id
+        ))
+    })?;
+    children.remove(pos);
+    if children.is_empty() {
+        remove(&mut child_store, &parent_idx.to_le_bytes());
+    } else {
+        save(&mut child_store, &parent_idx.to_le_bytes(), &children)?;
+    }
+    let mut messages = Vec::new();
+    if let Some(contract) = stored.contract {
+        let contract_store = ReadonlyPrefixedStorage::new(PREFIX_CHILD_CONTRACTS, &deps.storage);
+        let code_hash: String = load(&contract_store, contract.as_slice())?;
+        messages.push(
+            Snip721HandleMsg::TransferNft {
+                recipient: env.message.sender.clone(),
+                token_id: stored.token_id,
+                memo: None,
+                padding: None,
+            }
+            .to_cosmos_msg(code_hash, deps.api.human_address(&contract)?, None)?,
+        );
+    } else {
+        let (mut token, idx) = get_token(&deps.storage, &stored.token_id, None)?;
+        let my_address = token.owner.clone();
+        let mut inv_updates: Vec<InventoryUpdate> = Vec::new();
+        change_owner(deps, &mut token, idx, &sender_raw, &mut inv_updates)?;
+        store_transfer(
+            &mut deps.storage,
+            config,
+            &env.block,
+            stored.token_id,
+            my_address,
+            None,
+            sender_raw,
+            None,
+        )?;
+        save(&mut deps.storage, CONFIG_KEY, &config)?;
+        update_owner_inventory(
+            &mut deps.storage,
+            &inv_updates,
+            PermissionType::ViewOwner.num_types(),
+        )?;
+    }
+    Ok(HandleResponse {
+        messages,
         log: vec![],
-        data: Some(to_binary(&HandleAnswer::BurnNft { status: Success })?),
+        data: Some(to_binary(&HandleAnswer::DetachChild { status: Success })?),
+    })
+}
+
+/// Returns StdResult<u32>
+///
+/// returns the index of a parent token if it is owned by the specified address and is not
+/// listed for sale
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `config` - a reference to the Config
+/// * `owner` - a reference to the address that must own the parent
+/// * `parent_token_id` - token id String slice of the parent token
+fn get_parent<S: ReadonlyStorage>(
+    storage: &S,
+    config: &Config,
+    owner: &CanonicalAddr,
+    parent_token_id: &str,
+) -> StdResult<u32> {
+    let custom_err = format!(
+        "Only the owner of token {} can change its children",
+        parent_token_id
+    );
+    // if token supply is private, don't leak that the token id does not exist
+    // instead just say they are not authorized for that token
+    let opt_err = if config.token_supply_is_public {
+        None
+    } else {
+        Some(&*custom_err)
     };
-    Ok(res)
+    let (parent, idx) = get_token(storage, parent_token_id, opt_err)?;
+    if parent.owner != *owner {
+        return Err(StdError::generic_err(custom_err));
+    }
+    // a buyer must receive the children that were held when the parent was listed
+    let sale_store = ReadonlyPrefixedStorage::new(PREFIX_TOKEN_SALE_INFO, storage);
+    let sale_info: Option<TokenSaleInfo> = json_may_load(&sale_store, &idx.to_le_bytes())?;
+    if sale_info.is_some_and(|s| s.sale_status == SaleStatus::ForSale) {
+        return Err(StdError::generic_err(format!(
+            "The children of token {} can not be changed while it is listed for sale",
+            parent_token_id
+        )));
+    }
+    check_not_bundled(storage, idx, parent_token_id)?;
+    Ok(idx)
+}
+
+/// Returns StdResult<()> after adding children to a parent token
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `parent_idx` - the index of the parent token
+/// * `new_children` - the children to add
+fn add_children<S: Storage>(
+    storage: &mut S,
+    parent_idx: u32,
+    new_children: Vec<StoredChild>,
+) -> StdResult<()> {
+    let mut child_store = PrefixedStorage::new(PREFIX_CHILDREN, storage);
+    let mut children: Vec<StoredChild> =
+        may_load(&child_store, &parent_idx.to_le_bytes())?.unwrap_or_default();
+    children.extend(new_children);
+    save(&mut child_store, &parent_idx.to_le_bytes(), &children)
 }
 
 /// Returns HandleResult
@@ -2115,6 +4909,10 @@ pub fn remove_minters<S: Storage, A: Api, Q: Querier>(
             .map(|x| deps.api.canonical_address(x))
             .collect::<StdResult<Vec<CanonicalAddr>>>()?;
         minters.retain(|m| !no_raw.contains(m));
+        let mut role_store = PrefixedStorage::new(PREFIX_MINTER_ROLES, &mut deps.storage);
+        for raw in no_raw.iter() {
+            role_store.remove(raw.as_slice());
+        }
         let new_len = minters.len();
         if new_len > 0 {
             if old_len != new_len {
@@ -2133,7 +4931,62 @@ pub fn remove_minters<S: Storage, A: Api, Q: Querier>(
 
 /// Returns HandleResult
 ///
-/// define the exact list of minters
+/// define the exact list of minters
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a reference to the Config
+/// * `priority` - u8 representation of highest ContractStatus level this action is permitted
+/// * `human_minters` - exact list of minter addresses
+pub fn set_minters<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &Config,
+    priority: u8,
+    human_minters: &[HumanAddr],
+) -> HandleResult {
+    check_status(config.status, priority)?;
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    if config.admin != sender_raw {
+        return Err(StdError::generic_err(
+            "This is an admin command and can only be run from the admin address",
+        ));
+    }
+    // remove duplicates from the minters list
+    let minters_raw: Vec<CanonicalAddr> = human_minters
+        .iter()
+        .map(|x| deps.api.canonical_address(x))
+        .collect::<StdResult<Vec<CanonicalAddr>>>()?;
+    let mut sortable: Vec<&[u8]> = minters_raw.iter().map(|x| x.as_slice()).collect();
+    sortable.sort_unstable();
+    sortable.dedup();
+    let minters: Vec<CanonicalAddr> = sortable
+        .iter()
+        .map(|x| CanonicalAddr(Binary(x.to_vec())))
+        .collect();
+    // drop the roles of the addresses that are no longer minters
+    let old_minters: Vec<CanonicalAddr> = may_load(&deps.storage, MINTERS_KEY)?.unwrap_or_default();
+    let mut role_store = PrefixedStorage::new(PREFIX_MINTER_ROLES, &mut deps.storage);
+    for old in old_minters.iter().filter(|m| !minters.contains(m)) {
+        role_store.remove(old.as_slice());
+    }
+    if minters.is_empty() {
+        remove(&mut deps.storage, MINTERS_KEY);
+    } else {
+        save(&mut deps.storage, MINTERS_KEY, &minters)?;
+    }
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::SetMinters { status: Success })?),
+    })
+}
+
+/// Returns HandleResult
+///
+/// set the authority and quotas of a minter
 ///
 /// # Arguments
 ///
@@ -2141,13 +4994,22 @@ pub fn remove_minters<S: Storage, A: Api, Q: Querier>(
 /// * `env` - Env of contract's environment
 /// * `config` - a reference to the Config
 /// * `priority` - u8 representation of highest ContractStatus level this action is permitted
-/// * `human_minters` - exact list of minter addresses
-pub fn set_minters<S: Storage, A: Api, Q: Querier>(
+/// * `minter` - a reference to the address of the minter
+/// * `may_update_metadata` - optionally true if the minter may update metadata
+/// * `max_tokens` - optional maximum number of tokens the minter may mint from now on
+/// * `allowed_mint_run_ids` - optional list of the only mint run IDs the minter may mint with
+/// * `expiration` - optional time the minter's authority expires
+#[allow(clippy::too_many_arguments)]
+pub fn set_minter_role<S: Storage, A: Api, Q: Querier>(
     deps: &mut Extern<S, A, Q>,
     env: Env,
     config: &Config,
     priority: u8,
-    human_minters: &[HumanAddr],
+    minter: &HumanAddr,
+    may_update_metadata: Option<bool>,
+    max_tokens: Option<u32>,
+    allowed_mint_run_ids: Option<Vec<String>>,
+    expiration: Option<Expiration>,
 ) -> HandleResult {
     check_status(config.status, priority)?;
     let sender_raw = deps.api.canonical_address(&env.message.sender)?;
@@ -2156,27 +5018,25 @@ pub fn set_minters<S: Storage, A: Api, Q: Querier>(
             "This is an admin command and can only be run from the admin address",
         ));
     }
-    // remove duplicates from the minters list
-    let minters_raw: Vec<CanonicalAddr> = human_minters
-        .iter()
-        .map(|x| deps.api.canonical_address(x))
-        .collect::<StdResult<Vec<CanonicalAddr>>>()?;
-    let mut sortable: Vec<&[u8]> = minters_raw.iter().map(|x| x.as_slice()).collect();
-    sortable.sort_unstable();
-    sortable.dedup();
-    let minters: Vec<CanonicalAddr> = sortable
-        .iter()
-        .map(|x| CanonicalAddr(Binary(x.to_vec())))
-        .collect();
-    if minters.is_empty() {
-        remove(&mut deps.storage, MINTERS_KEY);
-    } else {
-        save(&mut deps.storage, MINTERS_KEY, &minters)?;
+    let minter_raw = deps.api.canonical_address(minter)?;
+    let minters: Vec<CanonicalAddr> = may_load(&deps.storage, MINTERS_KEY)?.unwrap_or_default();
+    if !minters.contains(&minter_raw) {
+        return Err(StdError::generic_err(format!("{} is not a minter", minter)));
     }
+    // the quota counts the tokens minted after the role is set
+    let role = StoredMinterRole {
+        may_update_metadata: may_update_metadata.unwrap_or(true),
+        max_tokens,
+        minted: 0,
+        allowed_mint_run_ids,
+        expiration,
+    };
+    let mut role_store = PrefixedStorage::new(PREFIX_MINTER_ROLES, &mut deps.storage);
+    save(&mut role_store, minter_raw.as_slice(), &role)?;
     Ok(HandleResponse {
         messages: vec![],
         log: vec![],
-        data: Some(to_binary(&HandleAnswer::SetMinters { status: Success })?),
+        data: Some(to_binary(&HandleAnswer::SetMinterRole { status: Success })?),
     })
 }
 
@@ -2253,6 +5113,44 @@ pub fn set_contract_status<S: Storage, A: Api, Q: Querier>(
     })
 }
 
+/// Returns HandleResult
+///
+/// set the maximum sum of royalty rates allowed on any royalty info set from now on
+///
+/// # Arguments
+///
+/// * `deps` - mutable reference to Extern containing all the contract's external dependencies
+/// * `env` - Env of contract's environment
+/// * `config` - a mutable reference to the Config
+/// * `max_royalty_bps` - the new maximum, in basis points
+pub fn set_max_royalty<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    env: Env,
+    config: &mut Config,
+    max_royalty_bps: u16,
+) -> HandleResult {
+    let sender_raw = deps.api.canonical_address(&env.message.sender)?;
+    if config.admin != sender_raw {
+        return Err(StdError::generic_err(
+            "This is an admin command and can only be run from the admin address",
+        ));
+    }
+    if max_royalty_bps > MAX_BPS {
+        return Err(StdError::generic_err(
+            "The maximum royalty can not exceed 10000 basis points",
+        ));
+    }
+    if config.max_royalty_bps != max_royalty_bps {
+        config.max_royalty_bps = max_royalty_bps;
+        save(&mut deps.storage, CONFIG_KEY, &config)?;
+    }
+    Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::SetMaxRoyalty { status: Success })?),
+    })
+}
+
 /// Returns HandleResult
 ///
 /// revoke the ability to use a specified permit
@@ -2287,6 +5185,64 @@ pub fn query<S: Storage, A: Api, Q: Querier>(deps: &Extern<S, A, Q>, msg: QueryM
     let response = match msg {
         QueryMsg::TokensForSale {} => query_tokens_for_sale(deps),
         QueryMsg::SaleInfo { token_id } => query_sale_info(deps, &token_id),
+        QueryMsg::PendingProceeds {
+            address,
+            viewing_key,
+        } => {
+            let viewer = Some(ViewerInfo {
+                address,
+                viewing_key,
+            });
+            query_pending_proceeds(deps, viewer, None)
+        }
+        QueryMsg::Accounting {
+            address,
+            viewing_key,
+        } => {
+            let viewer = Some(ViewerInfo {
+                address,
+                viewing_key,
+            });
+            query_accounting(deps, viewer, None)
+        }
+        QueryMsg::PublicMintInfo {} => query_public_mint(&deps.storage),
+        QueryMsg::RevealCommitment {} => query_reveal_commitment(&deps.storage),
+        QueryMsg::LazyListings {} => query_lazy_listings(deps),
+        QueryMsg::MintRuns {
+            creator,
+            viewer,
+            start_after,
+            limit,
+        } => query_mint_runs(deps, creator, viewer, start_after, limit, None),
+        QueryMsg::TokensInMintRun {
+            run_id,
+            run_number,
+            viewer,
+            start_after,
+            limit,
+        } => query_tokens_in_mint_run(deps, &run_id, run_number, viewer, start_after, limit, None),
+        QueryMsg::MetadataHistory {
+            token_id,
+            viewer,
+            page,
+            page_size,
+        } => query_metadata_history(deps, &token_id, viewer, page, page_size, None),
+        QueryMsg::Children { token_id, viewer } => query_children(deps, &token_id, viewer, None),
+        QueryMsg::CollectionStats { mint_run, viewer } => {
+            query_collection_stats(deps, mint_run, viewer, None)
+        }
+        QueryMsg::MyRoyalties {
+            address,
+            viewing_key,
+            page,
+            page_size,
+        } => {
+            let viewer = Some(ViewerInfo {
+                address,
+                viewing_key,
+            });
+            query_my_royalties(deps, viewer, page, page_size, None)
+        }
         QueryMsg::ContractInfo {} => query_contract_info(&deps.storage),
         QueryMsg::ContractCreator {} => query_contract_creator(deps),
         QueryMsg::RoyaltyInfo { token_id, viewer } => {
@@ -2294,7 +5250,19 @@ pub fn query<S: Storage, A: Api, Q: Querier>(deps: &Extern<S, A, Q>, msg: QueryM
         }
         QueryMsg::ContractConfig {} => query_config(&deps.storage),
         QueryMsg::Minters {} => query_minters(deps),
+        QueryMsg::AttributeUpdaters {} => query_attribute_updaters(deps),
         QueryMsg::NumTokens { viewer } => query_num_tokens(deps, viewer, None),
+        QueryMsg::TraitCounts { viewer } => query_trait_counts(deps, viewer, None),
+        QueryMsg::TokenRarity { token_id, viewer } => {
+            query_token_rarity(deps, &token_id, viewer, None)
+        }
+        QueryMsg::TokensByTrait {
+            trait_type,
+            value,
+            viewer,
+            start_after,
+            limit,
+        } => query_tokens_by_trait(deps, &trait_type, &value, viewer, start_after, limit, None),
         QueryMsg::AllTokens {
             viewer,
             start_after,
@@ -2394,7 +5362,7 @@ pub fn query<S: Storage, A: Api, Q: Querier>(deps: &Extern<S, A, Q>, msg: QueryM
     pad_query_result(response, BLOCK_SIZE)
 }
 
-/// Returns QueryResult displaying all token ids up for sale
+/// Returns QueryResult displaying all token ids and bundles up for sale
 ///
 /// # Arguments
 ///
@@ -2403,7 +5371,12 @@ pub fn query_tokens_for_sale<S: Storage, A: Api, Q: Querier>(
     deps: &Extern<S, A, Q>,
 ) -> QueryResult {
     let for_sale: Vec<String> = may_load(&deps.storage, FOR_SALE_KEY)?.unwrap_or_default();
-    to_binary(&QueryAnswer::TokensForSale { for_sale })
+    let bundle_ids: Vec<u32> = may_load(&deps.storage, BUNDLES_FOR_SALE_KEY)?.unwrap_or_default();
+    let bundles = bundle_ids
+        .into_iter()
+        .map(|id| load_bundle(&deps.storage, id).map(|b| b.into_display(id)))
+        .collect::<StdResult<Vec<Bundle>>>()?;
+    to_binary(&QueryAnswer::TokensForSale { for_sale, bundles })
 }
 
 /// Returns QueryResult displaying all SaleInfo of the specified token ids
@@ -2417,7 +5390,367 @@ pub fn query_sale_info<S: Storage, A: Api, Q: Querier>(
     token_id: &str,
 ) -> QueryResult {
     let (sale_store, idx) = get_sale_info(&deps.storage, token_id, None)?;
-    to_binary(&QueryAnswer::SaleInfo { sale_store })
+    let unlock_store = ReadonlyPrefixedStorage::new(PREFIX_UNLOCKABLE, &deps.storage);
+    let has_unlockable = may_load::<String, _>(&unlock_store, &idx.to_le_bytes())?.is_some();
+    to_binary(&QueryAnswer::SaleInfo {
+        sale_store,
+        has_unlockable,
+    })
+}
+
+/// Returns QueryResult displaying the sale proceeds and royalties an address has not claimed yet
+///
+/// # Arguments
+///
+/// * `deps` - a reference to Extern containing all the contract's external dependencies
+/// * `viewer` - optional address and key making an authenticated query request
+/// * `from_permit` - address derived from an Owner permit, if applicable
+pub fn query_pending_proceeds<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    viewer: Option<ViewerInfo>,
+    from_permit: Option<CanonicalAddr>,
+) -> QueryResult {
+    let address_raw = get_querier(deps, viewer, from_permit)?.ok_or_else(|| {
+        StdError::generic_err("This is being called incorrectly if there is no querier address")
+    })?;
+    let balances = get_proceeds(&deps.storage, &address_raw)?
+        .into_iter()
+        .map(|b| b.into_coin())
+        .collect();
+    to_binary(&QueryAnswer::PendingProceeds { balances })
+}
+
+/// Returns QueryResult displaying the public mint settings
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+pub fn query_public_mint<S: ReadonlyStorage>(storage: &S) -> QueryResult {
+    let public_mint: StoredPublicMint = may_load(storage, PUBLIC_MINT_KEY)?.unwrap_or_default();
+    let block: BlockInfo = may_load(storage, BLOCK_KEY)?.unwrap_or_else(|| BlockInfo {
+        height: 1,
+        time: 1,
+        chain_id: "not used".to_string(),
+    });
+    to_binary(&QueryAnswer::PublicMintInfo {
+        info: public_mint.to_display(&block),
+    })
+}
+
+/// Returns QueryResult displaying the commitment made for revealing the collection
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+pub fn query_reveal_commitment<S: ReadonlyStorage>(storage: &S) -> QueryResult {
+    let commitment: Option<StoredRevealCommitment> = may_load(storage, REVEAL_COMMITMENT_KEY)?;
+    to_binary(&QueryAnswer::RevealCommitment {
+        commitment: commitment.map(|c| c.to_display()),
+    })
+}
+
+/// Returns QueryResult displaying the lazy listings that have editions left
+///
+/// # Arguments
+///
+/// * `deps` - a reference to Extern containing all the contract's external dependencies
+pub fn query_lazy_listings<S: Storage, A: Api, Q: Querier>(deps: &Extern<S, A, Q>) -> QueryResult {
+    let active: Vec<String> = may_load(&deps.storage, LAZY_LISTINGS_KEY)?.unwrap_or_default();
+    let lazy_store = ReadonlyPrefixedStorage::new(PREFIX_LAZY_LISTINGS, &deps.storage);
+    let listings = active
+        .into_iter()
+        .map(|id| {
+            let listing: StoredLazyListing = load(&lazy_store, id.as_bytes())?;
+            listing.to_display(&deps.api, id)
+        })
+        .collect::<StdResult<Vec<LazyListing>>>()?;
+    to_binary(&QueryAnswer::LazyListings { listings })
+}
+
+/// Returns QueryResult displaying the registered mint runs
+///
+/// # Arguments
+///
+/// * `deps` - a reference to Extern containing all the contract's external dependencies
+/// * `creator` - optional creator whose mint runs should be displayed
+/// * `viewer` - optional address and key making an authenticated query request
+/// * `start_after` - optionally only display mint runs after this registry index
+/// * `limit` - optional max number of mint runs to display
+/// * `from_permit` - address derived from an Owner permit, if applicable
+pub fn query_mint_runs<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    creator: Option<HumanAddr>,
+    viewer: Option<ViewerInfo>,
+    start_after: Option<u32>,
+    limit: Option<u32>,
+    from_permit: Option<CanonicalAddr>,
+) -> QueryResult {
+    check_view_supply(deps, viewer, from_permit)?;
+    let cut_off = limit.unwrap_or(30) as usize;
+    let indices: Vec<u32> = if let Some(creator) = creator {
+        let creator_raw = deps.api.canonical_address(&creator)?;
+        let creator_store = ReadonlyPrefixedStorage::new(PREFIX_CREATOR_RUNS, &deps.storage);
+        may_load(&creator_store, creator_raw.as_slice())?.unwrap_or_default()
+    } else {
+        let count: u32 = may_load(&deps.storage, MINT_RUN_COUNT_KEY)?.unwrap_or(0);
+        let first = start_after.map_or(0, |s| s.saturating_add(1));
+        (first..count).take(cut_off).collect()
+    };
+    let reg_store = ReadonlyPrefixedStorage::new(PREFIX_MINT_RUN_REGISTRY, &deps.storage);
+    let mint_runs = indices
+        .into_iter()
+        .filter(|i| start_after.is_none_or(|s| *i > s))
+        .take(cut_off)
+        .map(|i| {
+            let run: StoredMintRun = load(&reg_store, &i.to_le_bytes())?;
+            run.to_human(&deps.api, i)
+        })
+        .collect::<StdResult<Vec<MintRun>>>()?;
+    to_binary(&QueryAnswer::MintRuns { mint_runs })
+}
+
+/// Returns QueryResult displaying the token ids of a registered mint run
+///
+/// # Arguments
+///
+/// * `deps` - a reference to Extern containing all the contract's external dependencies
+/// * `run_id` - mint run ID given when minting
+/// * `run_number` - number of the mint run for its mint run ID
+/// * `viewer` - optional address and key making an authenticated query request
+/// * `start_after` - optionally only display tokens after this position in the mint run
+/// * `limit` - optional max number of token ids to display
+/// * `from_permit` - address derived from an Owner permit, if applicable
+pub fn query_tokens_in_mint_run<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    run_id: &str,
+    run_number: u32,
+    viewer: Option<ViewerInfo>,
+    start_after: Option<u32>,
+    limit: Option<u32>,
+    from_permit: Option<CanonicalAddr>,
+) -> QueryResult {
+    check_view_supply(deps, viewer, from_permit)?;
+    let lookup_store = ReadonlyPrefixedStorage::multilevel(
+        &[PREFIX_MINT_RUN_LOOKUP, run_id.as_bytes()],
+        &deps.storage,
+    );
+    let reg_idx: u32 = may_load(&lookup_store, &run_number.to_le_bytes())?.ok_or_else(|| {
+        StdError::generic_err(format!(
+            "Mint run {} of mint run ID {} is not registered",
+            run_number, run_id
+        ))
+    })?;
+    let reg_key = reg_idx.to_le_bytes();
+    let reg_store = ReadonlyPrefixedStorage::new(PREFIX_MINT_RUN_REGISTRY, &deps.storage);
+    let run: StoredMintRun = load(&reg_store, &reg_key)?;
+    let tok_store =
+        ReadonlyPrefixedStorage::multilevel(&[PREFIX_MINT_RUN_TOKENS, &reg_key], &deps.storage);
+    let map2id = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_ID, &deps.storage);
+    let cut_off = limit.unwrap_or(30) as usize;
+    let first = start_after.map_or(0, |s| s.saturating_add(1));
+    let mut token_ids: Vec<String> = Vec::new();
+    let mut last_position: Option<u32> = None;
+    for position in first..run.minted {
+        if token_ids.len() >= cut_off {
+            break;
+        }
+        let token_idx: u32 = load(&tok_store, &position.to_le_bytes())?;
+        // burned tokens no longer have an id
+        if let Some(id) = may_load::<String, _>(&map2id, &token_idx.to_le_bytes())? {
+            token_ids.push(id);
+            last_position = Some(position);
+        }
+    }
+    to_binary(&QueryAnswer::TokensInMintRun {
+        token_ids,
+        last_position,
+    })
+}
+
+/// Returns QueryResult displaying the sale stats of the collection, and optionally of one of
+/// its mint runs
+///
+/// # Arguments
+///
+/// * `deps` - a reference to Extern containing all the contract's external dependencies
+/// * `mint_run` - optional mint run number whose stats should also be displayed
+/// * `viewer` - optional address and key making an authenticated query request
+/// * `from_permit` - address derived from an Owner permit, if applicable
+pub fn query_collection_stats<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    mint_run: Option<MintRunRef>,
+    viewer: Option<ViewerInfo>,
+    from_permit: Option<CanonicalAddr>,
+) -> QueryResult {
+    let config: Config = load(&deps.storage, CONFIG_KEY)?;
+    check_minter_view(
+        deps,
+        viewer,
+        from_permit,
+        config.sale_stats_are_public,
+        "The sale stats of this contract are private",
+    )?;
+    let collection: StoredSaleStats =
+        may_load(&deps.storage, COLLECTION_STATS_KEY)?.unwrap_or_default();
+    let run_stats = mint_run
+        .map(|run| {
+            let lookup_store = ReadonlyPrefixedStorage::multilevel(
+                &[PREFIX_MINT_RUN_LOOKUP, run.run_id.as_bytes()],
+                &deps.storage,
+            );
+            let reg_idx: u32 =
+                may_load(&lookup_store, &run.run_number.to_le_bytes())?.ok_or_else(|| {
+                    StdError::generic_err(format!(
+                        "Mint run {} of mint run ID {} is not registered",
+                        run.run_number, run.run_id
+                    ))
+                })?;
+            let stats_store = ReadonlyPrefixedStorage::new(PREFIX_MINT_RUN_STATS, &deps.storage);
+            let stats: StoredSaleStats =
+                may_load(&stats_store, &reg_idx.to_le_bytes())?.unwrap_or_default();
+            Ok(stats.into_display(get_floor_price(&deps.storage, Some(reg_idx))?))
+        })
+        .transpose()?;
+    to_binary(&QueryAnswer::CollectionStats {
+        collection: collection.into_display(get_floor_price(&deps.storage, None)?),
+        mint_run: run_stats,
+    })
+}
+
+/// Returns QueryResult displaying the tokens that pay royalties to the querier, along with its
+/// rate and the royalties it has received from each of them
+///
+/// # Arguments
+///
+/// * `deps` - a reference to Extern containing all the contract's external dependencies
+/// * `viewer` - optional address and key making an authenticated query request
+/// * `page` - optional page to display
+/// * `page_size` - optional number of tokens to display
+/// * `from_permit` - address derived from an Owner permit, if applicable
+pub fn query_my_royalties<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    viewer: Option<ViewerInfo>,
+    page: Option<u32>,
+    page_size: Option<u32>,
+    from_permit: Option<CanonicalAddr>,
+) -> QueryResult {
+    let address_raw = get_querier(deps, viewer, from_permit)?.ok_or_else(|| {
+        StdError::generic_err("This is being called incorrectly if there is no querier address")
+    })?;
+    let size = page_size.unwrap_or(30);
+    let (indices, count) = get_royalty_tokens(
+        &deps.storage,
+        &address_raw,
+        page.unwrap_or(0).saturating_mul(size) as usize,
+        size as usize,
+    )?;
+    let map2id = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_ID, &deps.storage);
+    let roy_store = ReadonlyPrefixedStorage::new(PREFIX_ROYALTY_INFO, &deps.storage);
+    let earn_store = ReadonlyPrefixedStorage::multilevel(
+        &[PREFIX_ROYALTY_EARNINGS, address_raw.as_slice()],
+        &deps.storage,
+    );
+    let mut royalties = Vec::new();
+    for idx in indices.iter() {
+        let token_key = idx.to_le_bytes();
+        let token_id: String = load(&map2id, &token_key)?;
+        // the index is kept in step with the royalty records, but a missing record should not
+        // fail the whole query
+        let roy_inf: StoredRoyaltyInfo = if let Some(roy) = may_load(&roy_store, &token_key)? {
+            roy
+        } else {
+            continue;
+        };
+        // an address may be listed more than once in a token's royalties
+        let rate = roy_inf
+            .royalties
+            .iter()
+            .filter(|r| r.recipient == address_raw)
+            .map(|r| r.rate)
+            .fold(0u16, |acc, r| acc.saturating_add(r));
+        let earned: Vec<StoredBalance> = may_load(&earn_store, &token_key)?.unwrap_or_default();
+        royalties.push(RecipientRoyalty {
+            token_id,
+            decimal_places_in_rates: roy_inf.decimal_places_in_rates,
+            rate,
+            received: earned.into_iter().map(|b| b.into_coin()).collect(),
+        });
+    }
+    let total_store = ReadonlyPrefixedStorage::new(PREFIX_ROYALTY_TOTALS, &deps.storage);
+    let totals: Vec<StoredBalance> =
+        may_load(&total_store, address_raw.as_slice())?.unwrap_or_default();
+    to_binary(&QueryAnswer::MyRoyalties {
+        count,
+        royalties,
+        total_received: totals.into_iter().map(|b| b.into_coin()).collect(),
+    })
+}
+
+/// Returns QueryResult reconciling the contract's balance of each denomination against the
+/// funds it holds in escrow and the fees it has earned
+///
+/// # Arguments
+///
+/// * `deps` - a reference to Extern containing all the contract's external dependencies
+/// * `viewer` - optional address and key making an authenticated query request
+/// * `from_permit` - address derived from an Owner permit, if applicable
+pub fn query_accounting<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    viewer: Option<ViewerInfo>,
+    from_permit: Option<CanonicalAddr>,
+) -> QueryResult {
+    let querier = get_querier(deps, viewer, from_permit)?.ok_or_else(|| {
+        StdError::generic_err("This is being called incorrectly if there is no querier address")
+    })?;
+    let config: Config = load(&deps.storage, CONFIG_KEY)?;
+    if config.admin != querier {
+        return Err(StdError::generic_err(
+            "This is an admin command and can only be run from the admin address",
+        ));
+    }
+    let my_address = deps
+        .api
+        .human_address(&load::<CanonicalAddr, _>(&deps.storage, MY_ADDRESS_KEY)?)?;
+    let balances = deps.querier.query_all_balances(my_address)?;
+    let escrow: Vec<StoredBalance> = may_load(&deps.storage, ESCROW_KEY)?.unwrap_or_default();
+    let fees: Vec<StoredBalance> = may_load(&deps.storage, EARNED_FEES_KEY)?.unwrap_or_default();
+    let mut denoms: Vec<String> = balances.iter().map(|c| c.denom.clone()).collect();
+    for denom in escrow.iter().chain(fees.iter()).map(|b| &b.denom) {
+        if !denoms.contains(denom) {
+            denoms.push(denom.clone());
+        }
+    }
+    denoms.sort();
+    let accounts = denoms
+        .into_iter()
+        .map(|denom| {
+            let balance = balances
+                .iter()
+                .find(|c| c.denom == denom)
+                .map(|c| c.amount.u128())
+                .unwrap_or(0);
+            let escrowed = escrow
+                .iter()
+                .find(|b| b.denom == denom)
+                .map(|b| b.amount)
+                .unwrap_or(0);
+            let earned_fees = fees
+                .iter()
+                .find(|b| b.denom == denom)
+                .map(|b| b.amount)
+                .unwrap_or(0);
+            let liabilities = escrowed.saturating_add(earned_fees);
+            DenomAccounting {
+                denom,
+                balance: Uint128(balance),
+                escrowed: Uint128(escrowed),
+                earned_fees: Uint128(earned_fees),
+                surplus: Uint128(balance.saturating_sub(liabilities)),
+                shortfall: Uint128(liabilities.saturating_sub(balance)),
+            }
+        })
+        .collect();
+    to_binary(&QueryAnswer::Accounting { accounts })
 }
 
 /// Returns QueryResult from validating a permit and then using its creator's address when
@@ -2484,6 +5817,24 @@ pub fn permit_queries<S: Storage, A: Api, Q: Querier>(
             query_transactions(deps, None, page, page_size, Some(querier))
         }
         QueryWithPermit::NumTokens {} => query_num_tokens(deps, None, Some(querier)),
+        QueryWithPermit::TraitCounts {} => query_trait_counts(deps, None, Some(querier)),
+        QueryWithPermit::TokenRarity { token_id } => {
+            query_token_rarity(deps, &token_id, None, Some(querier))
+        }
+        QueryWithPermit::TokensByTrait {
+            trait_type,
+            value,
+            start_after,
+            limit,
+        } => query_tokens_by_trait(
+            deps,
+            &trait_type,
+            &value,
+            None,
+            start_after,
+            limit,
+            Some(querier),
+        ),
         QueryWithPermit::AllTokens { start_after, limit } => {
             query_all_tokens(deps, None, start_after, limit, Some(querier))
         }
@@ -2502,6 +5853,41 @@ pub fn permit_queries<S: Storage, A: Api, Q: Querier>(
         QueryWithPermit::NumTokensOfOwner { owner } => {
             query_num_owner_tokens(deps, &owner, None, None, Some(querier))
         }
+        QueryWithPermit::PendingProceeds {} => query_pending_proceeds(deps, None, Some(querier)),
+        QueryWithPermit::Accounting {} => query_accounting(deps, None, Some(querier)),
+        QueryWithPermit::CollectionStats { mint_run } => {
+            query_collection_stats(deps, mint_run, None, Some(querier))
+        }
+        QueryWithPermit::MyRoyalties { page, page_size } => {
+            query_my_royalties(deps, None, page, page_size, Some(querier))
+        }
+        QueryWithPermit::MintRuns {
+            creator,
+            start_after,
+            limit,
+        } => query_mint_runs(deps, creator, None, start_after, limit, Some(querier)),
+        QueryWithPermit::TokensInMintRun {
+            run_id,
+            run_number,
+            start_after,
+            limit,
+        } => query_tokens_in_mint_run(
+            deps,
+            &run_id,
+            run_number,
+            None,
+            start_after,
+            limit,
+            Some(querier),
+        ),
+        QueryWithPermit::MetadataHistory {
+            token_id,
+            page,
+            page_size,
+        } => query_metadata_history(deps, &token_id, None, page, page_size, Some(querier)),
+        QueryWithPermit::Children { token_id } => {
+            query_children(deps, &token_id, None, Some(querier))
+        }
     }
 }
 
@@ -2624,22 +6010,51 @@ pub fn query_config<S: ReadonlyStorage>(storage: &S) -> QueryResult {
         minter_may_update_metadata: config.minter_may_update_metadata,
         owner_may_update_metadata: config.owner_may_update_metadata,
         burn_is_enabled: config.burn_is_enabled,
+        max_royalty_bps: config.max_royalty_bps,
+        sale_stats_are_public: config.sale_stats_are_public,
     })
 }
 
-/// Returns QueryResult displaying the list of authorized minters
+/// Returns QueryResult displaying the list of authorized minters and their roles
 ///
 /// # Arguments
 ///
 /// * `deps` - a reference to Extern containing all the contract's external dependencies
 pub fn query_minters<S: Storage, A: Api, Q: Querier>(deps: &Extern<S, A, Q>) -> QueryResult {
     let minters: Vec<CanonicalAddr> = may_load(&deps.storage, MINTERS_KEY)?.unwrap_or_default();
+    let role_store = ReadonlyPrefixedStorage::new(PREFIX_MINTER_ROLES, &deps.storage);
+    let roles = minters
+        .iter()
+        .map(|m| {
+            let role: StoredMinterRole = may_load(&role_store, m.as_slice())?.unwrap_or_default();
+            role.to_display(&deps.api, m)
+        })
+        .collect::<StdResult<Vec<MinterInfo>>>()?;
 
     to_binary(&QueryAnswer::Minters {
         minters: minters
             .iter()
             .map(|m| deps.api.human_address(m))
             .collect::<StdResult<Vec<HumanAddr>>>()?,
+        roles,
+    })
+}
+
+/// Returns QueryResult displaying the addresses allowed to update attributes
+///
+/// # Arguments
+///
+/// * `deps` - a reference to Extern containing all the contract's external dependencies
+pub fn query_attribute_updaters<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+) -> QueryResult {
+    let updaters: Vec<StoredAttributeUpdater> =
+        may_load(&deps.storage, ATTRIBUTE_UPDATERS_KEY)?.unwrap_or_default();
+    to_binary(&QueryAnswer::AttributeUpdaters {
+        updaters: updaters
+            .iter()
+            .map(|u| u.to_display(&deps.api))
+            .collect::<StdResult<Vec<_>>>()?,
     })
 }
 
@@ -2663,6 +6078,110 @@ pub fn query_num_tokens<S: Storage, A: Api, Q: Querier>(
     })
 }
 
+/// Returns QueryResult displaying the number of tokens with each public trait value
+///
+/// # Arguments
+///
+/// * `deps` - a reference to Extern containing all the contract's external dependencies
+/// * `viewer` - optional address and key making an authenticated query request
+/// * `from_permit` - address derived from an Owner permit, if applicable
+pub fn query_trait_counts<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    viewer: Option<ViewerInfo>,
+    from_permit: Option<CanonicalAddr>,
+) -> QueryResult {
+    // authenticate permission to view token supply
+    check_view_supply(deps, viewer, from_permit)?;
+    let config: Config = load(&deps.storage, CONFIG_KEY)?;
+    to_binary(&QueryAnswer::TraitCounts {
+        total_tokens: config.token_cnt,
+        traits: get_trait_counts(&deps.storage)?,
+    })
+}
+
+/// Returns QueryResult displaying how many tokens share each of a token's public trait
+/// values and the token's rarity score
+///
+/// # Arguments
+///
+/// * `deps` - a reference to Extern containing all the contract's external dependencies
+/// * `token_id` - string slice of the token id
+/// * `viewer` - optional address and key making an authenticated query request
+/// * `from_permit` - address derived from an Owner permit, if applicable
+pub fn query_token_rarity<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    token_id: &str,
+    viewer: Option<ViewerInfo>,
+    from_permit: Option<CanonicalAddr>,
+) -> QueryResult {
+    // authenticate permission to view token supply
+    check_view_supply(deps, viewer, from_permit)?;
+    let config: Config = load(&deps.storage, CONFIG_KEY)?;
+    let (_, idx) = get_token(&deps.storage, token_id, None)?;
+    let metadata = load_public_metadata(&deps.storage, &idx.to_le_bytes())?;
+    let mut rarity_score = 0u64;
+    let mut traits = Vec::new();
+    for (trait_type, value) in rarity_traits(metadata.as_ref()).into_iter() {
+        let count = get_trait_count(&deps.storage, &trait_type, &value)?;
+        if count > 0 {
+            rarity_score += config.token_cnt as u64 * 1000 / count as u64;
+        }
+        traits.push(TraitCount {
+            trait_type,
+            value,
+            count,
+        });
+    }
+    to_binary(&QueryAnswer::TokenRarity {
+        token_id: token_id.to_string(),
+        rarity_score,
+        traits,
+    })
+}
+
+/// Returns QueryResult displaying the list of tokens with a public trait value
+///
+/// # Arguments
+///
+/// * `deps` - a reference to Extern containing all the contract's external dependencies
+/// * `trait_type` - string slice of the trait type to search
+/// * `value` - string slice of the trait value to search for
+/// * `viewer` - optional address and key making an authenticated query request
+/// * `start_after` - optionally only display token ids that come after this one
+/// * `limit` - optional max number of tokens to display
+/// * `from_permit` - address derived from an Owner permit, if applicable
+pub fn query_tokens_by_trait<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    trait_type: &str,
+    value: &str,
+    viewer: Option<ViewerInfo>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+    from_permit: Option<CanonicalAddr>,
+) -> QueryResult {
+    // authenticate permission to view token supply
+    check_view_supply(deps, viewer, from_permit)?;
+    let start = start_after
+        .map(|id| {
+            let map2idx = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_INDEX, &deps.storage);
+            may_load::<u32, _>(&map2idx, id.as_bytes())?
+                .ok_or_else(|| StdError::generic_err(format!("Token ID: {} not found", id)))
+        })
+        .transpose()?;
+    let map2id = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_ID, &deps.storage);
+    let tokens = get_trait_tokens(
+        &deps.storage,
+        trait_type,
+        value,
+        start,
+        limit.unwrap_or(300) as usize,
+    )?
+    .into_iter()
+    .map(|idx| load::<String, _>(&map2id, &idx.to_le_bytes()))
+    .collect::<StdResult<Vec<String>>>()?;
+    to_binary(&QueryAnswer::TokenList { tokens })
+}
+
 /// Returns QueryResult displaying the list of tokens that the contract controls
 ///
 /// # Arguments
@@ -2748,14 +6267,15 @@ pub fn query_nft_info<S: ReadonlyStorage>(storage: &S, token_id: &str) -> QueryR
     let may_idx: Option<u32> = may_load(&map2idx, token_id.as_bytes())?;
     // if token id was found
     if let Some(idx) = may_idx {
-        let meta_store = ReadonlyPrefixedStorage::new(PREFIX_PUB_META, storage);
-        let meta: Metadata = may_load(&meta_store, &idx.to_le_bytes())?.unwrap_or(Metadata {
-            token_uri: None,
-            extension: None,
-        });
+        let meta: Metadata =
+            load_public_metadata(storage, &idx.to_le_bytes())?.unwrap_or(Metadata {
+                token_uri: None,
+                extension: None,
+            });
         return to_binary(&QueryAnswer::NftInfo {
             token_uri: meta.token_uri,
             extension: meta.extension,
+            frozen: is_metadata_frozen(storage, &idx.to_le_bytes())?,
         });
     }
     let config: Config = load(storage, CONFIG_KEY)?;
@@ -2771,6 +6291,7 @@ pub fn query_nft_info<S: ReadonlyStorage>(storage: &S, token_id: &str) -> QueryR
     to_binary(&QueryAnswer::NftInfo {
         token_uri: None,
         extension: None,
+        frozen: false,
     })
 }
 
@@ -2812,9 +6333,98 @@ pub fn query_private_meta<S: Storage, A: Api, Q: Querier>(
         token_uri: None,
         extension: None,
     });
+    // unlockable content is only revealed to the current owner
+    let unlockable = if prep_info.viewer_raw.as_ref() == Some(&prep_info.token.owner) {
+        let unlock_store = ReadonlyPrefixedStorage::new(PREFIX_UNLOCKABLE, &deps.storage);
+        may_load(&unlock_store, &prep_info.idx.to_le_bytes())?
+    } else {
+        None
+    };
     to_binary(&QueryAnswer::PrivateMetadata {
         token_uri: meta.token_uri,
         extension: meta.extension,
+        unlockable,
+    })
+}
+
+/// Returns QueryResult displaying the revisions of a token's metadata.  Private metadata
+/// revisions are only displayed if the querier is permitted to view the private metadata
+///
+/// # Arguments
+///
+/// * `deps` - a reference to Extern containing all the contract's external dependencies
+/// * `token_id` - string slice of the token id
+/// * `viewer` - optional address and key making an authenticated query request
+/// * `page` - optional page to display
+/// * `page_size` - optional number of revisions per page
+/// * `from_permit` - address derived from an Owner permit, if applicable
+pub fn query_metadata_history<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    token_id: &str,
+    viewer: Option<ViewerInfo>,
+    page: Option<u32>,
+    page_size: Option<u32>,
+    from_permit: Option<CanonicalAddr>,
+) -> QueryResult {
+    let prep_info = query_token_prep(deps, token_id, viewer, from_permit)?;
+    let include_private = check_permission(
+        deps,
+        &prep_info.block,
+        &prep_info.token,
+        token_id,
+        prep_info.viewer_raw.as_ref(),
+        PermissionType::ViewMetadata,
+        &mut Vec::new(),
+        &prep_info.err_msg,
+        prep_info.owner_is_public,
+    )
+    .is_ok();
+    let (revisions, total) = get_metadata_history(
+        &deps.api,
+        &deps.storage,
+        &prep_info.idx.to_le_bytes(),
+        include_private,
+        page.unwrap_or(0),
+        page_size.unwrap_or(30),
+    )?;
+    to_binary(&QueryAnswer::MetadataHistory { total, revisions })
+}
+
+/// Returns QueryResult displaying the tokens held by a parent token if permitted to view the
+/// parent's owner
+///
+/// # Arguments
+///
+/// * `deps` - a reference to Extern containing all the contract's external dependencies
+/// * `token_id` - string slice of the token id
+/// * `viewer` - optional address and key making an authenticated query request
+/// * `from_permit` - address derived from an Owner permit, if applicable
+pub fn query_children<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    token_id: &str,
+    viewer: Option<ViewerInfo>,
+    from_permit: Option<CanonicalAddr>,
+) -> QueryResult {
+    let prep_info = query_token_prep(deps, token_id, viewer, from_permit)?;
+    check_permission(
+        deps,
+        &prep_info.block,
+        &prep_info.token,
+        token_id,
+        prep_info.viewer_raw.as_ref(),
+        PermissionType::ViewOwner,
+        &mut Vec::new(),
+        &prep_info.err_msg,
+        prep_info.owner_is_public,
+    )?;
+    let child_store = ReadonlyPrefixedStorage::new(PREFIX_CHILDREN, &deps.storage);
+    let children: Vec<StoredChild> =
+        may_load(&child_store, &prep_info.idx.to_le_bytes())?.unwrap_or_default();
+    to_binary(&QueryAnswer::Children {
+        children: children
+            .iter()
+            .map(|c| c.to_display(&deps.api))
+            .collect::<StdResult<Vec<ChildToken>>>()?,
     })
 }
 
@@ -2836,8 +6446,7 @@ pub fn query_all_nft_info<S: Storage, A: Api, Q: Querier>(
 ) -> QueryResult {
     let (owner, approvals, idx) =
         process_cw721_owner_of(deps, token_id, viewer, include_expired, from_permit)?;
-    let meta_store = ReadonlyPrefixedStorage::new(PREFIX_PUB_META, &deps.storage);
-    let info: Option<Metadata> = may_load(&meta_store, &idx.to_le_bytes())?;
+    let info: Option<Metadata> = load_public_metadata(&deps.storage, &idx.to_le_bytes())?;
     let access = Cw721OwnerOfResponse { owner, approvals };
     to_binary(&QueryAnswer::AllNftInfo { access, info })
 }
@@ -2882,6 +6491,7 @@ pub fn query_nft_dossier<S: Storage, A: Api, Q: Querier>(
         private_metadata_is_public_expiration: dossier.private_metadata_is_public_expiration,
         token_approvals: dossier.token_approvals,
         inventory_approvals: dossier.inventory_approvals,
+        frozen: dossier.frozen,
     })
 }
 
@@ -3907,7 +7517,34 @@ fn check_view_supply<S: Storage, A: Api, Q: Querier>(
     from_permit: Option<CanonicalAddr>,
 ) -> StdResult<()> {
     let config: Config = load(&deps.storage, CONFIG_KEY)?;
-    let mut is_auth = config.token_supply_is_public;
+    check_minter_view(
+        deps,
+        viewer,
+        from_permit,
+        config.token_supply_is_public,
+        "The token supply of this contract is private",
+    )
+}
+
+/// Returns StdResult<()>
+///
+/// returns Ok if the info is public or the querier is a minter, Err otherwise
+///
+/// # Arguments
+///
+/// * `deps` - a reference to Extern containing all the contract's external dependencies
+/// * `viewer` - optional address and key making an authenticated query request
+/// * `from_permit` - address derived from an Owner permit, if applicable
+/// * `is_public` - true if anyone may view the info
+/// * `private_err` - error message to return if the querier may not view the info
+fn check_minter_view<S: Storage, A: Api, Q: Querier>(
+    deps: &Extern<S, A, Q>,
+    viewer: Option<ViewerInfo>,
+    from_permit: Option<CanonicalAddr>,
+    is_public: bool,
+    private_err: &str,
+) -> StdResult<()> {
+    let mut is_auth = is_public;
     if !is_auth {
         let querier = get_querier(deps, viewer, from_permit)?;
         if let Some(viewer_raw) = querier {
@@ -3916,9 +7553,7 @@ fn check_view_supply<S: Storage, A: Api, Q: Querier>(
             is_auth = minters.contains(&viewer_raw);
         }
         if !is_auth {
-            return Err(StdError::generic_err(
-                "The token supply of this contract is private",
-            ));
+            return Err(StdError::generic_err(private_err));
         }
     }
     Ok(())
@@ -4197,51 +7832,165 @@ fn get_token<S: ReadonlyStorage>(
     Ok((token, idx))
 }
 
-/// Returns StdResult<()> that will error if the priority level of the action is not
-/// equal to or greater than the current contract status level
+/// Returns StdResult<()> that will error if the priority level of the action is not
+/// equal to or greater than the current contract status level
+///
+/// # Arguments
+///
+/// * `contract_status` - u8 representation of the current contract status
+/// * `priority` - u8 representing the highest status level this action may execute at
+fn check_status(contract_status: u8, priority: u8) -> StdResult<()> {
+    if priority < contract_status {
+        return Err(StdError::generic_err(
+            "The contract admin has temporarily disabled this action",
+        ));
+    }
+    Ok(())
+}
+
+/// Returns StdResult<()>
+///
+/// sets new metadata
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `token` - a reference to the token whose metadata should be updated
+/// * `idx` - the token identifier index
+/// * `prefix` - storage prefix for the type of metadata being updated
+/// * `metadata` - a reference to the new metadata
+/// * `changer` - a reference to the address updating the metadata
+/// * `block` - a reference to the current BlockInfo
+#[allow(clippy::too_many_arguments)]
+fn set_metadata_impl<S: Storage>(
+    storage: &mut S,
+    token: &Token,
+    idx: u32,
+    prefix: &[u8],
+    metadata: &Metadata,
+    changer: &CanonicalAddr,
+    block: &BlockInfo,
+) -> StdResult<()> {
+    // do not allow the altering of sealed metadata
+    if !token.unwrapped && prefix == PREFIX_PRIV_META {
+        return Err(StdError::generic_err(
+            "The private metadata of a sealed token can not be modified",
+        ));
+    }
+    let field = if prefix == PREFIX_PUB_META {
+        "public_metadata"
+    } else {
+        "private_metadata"
+    };
+    validate_metadata(metadata, field)?;
+    let token_key = idx.to_le_bytes();
+    // record hashes of the old and new metadata in the token's revision log
+    let private = prefix == PREFIX_PRIV_META;
+    let old_meta: Option<Metadata> = if private {
+        let meta_store = ReadonlyPrefixedStorage::new(prefix, storage);
+        may_load(&meta_store, &token_key)?
+    } else {
+        load_public_metadata(storage, &token_key)?
+    };
+    let revision = StoredMetadataRevision {
+        changer: changer.clone(),
+        block_height: block.height,
+        block_time: block.time,
+        private,
+        old_hash: old_meta
+            .as_ref()
+            .map(|m| to_vec(m).map(|v| sha_256(&v).to_vec()))
+            .transpose()?,
+        new_hash: sha_256(&to_vec(metadata)?).to_vec(),
+    };
+    store_metadata_revision(storage, &token_key, &revision)?;
+    if !private {
+        update_trait_index(storage, idx, old_meta.as_ref(), Some(metadata))?;
+    }
+    let mut meta_store = PrefixedStorage::new(prefix, storage);
+    save(&mut meta_store, &token_key, metadata)?;
+    // an edition given its own public metadata no longer uses the shared metadata
+    if prefix == PREFIX_PUB_META {
+        let mut edition_store = PrefixedStorage::new(PREFIX_EDITIONS, storage);
+        remove(&mut edition_store, &token_key);
+    }
+    Ok(())
+}
+
+/// Returns StdResult<()>
+///
+/// makes sure the sender may update a token's metadata, either as its owner or as a minter,
+/// depending on the config and the minter's role
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `config` - a reference to the Config
+/// * `block` - a reference to the current BlockInfo
+/// * `token` - a reference to the token
+/// * `sender_raw` - a reference to the message sender address
+/// * `custom_err` - the error message to return if the sender is not authorized
+fn check_metadata_updater<S: ReadonlyStorage>(
+    storage: &S,
+    config: &Config,
+    block: &BlockInfo,
+    token: &Token,
+    sender_raw: &CanonicalAddr,
+    custom_err: String,
+) -> StdResult<()> {
+    if token.owner == *sender_raw && config.owner_may_update_metadata {
+        return Ok(());
+    }
+    let minters: Vec<CanonicalAddr> = may_load(storage, MINTERS_KEY)?.unwrap_or_default();
+    if !(minters.contains(sender_raw) && config.minter_may_update_metadata) {
+        return Err(StdError::generic_err(custom_err));
+    }
+    let role_store = ReadonlyPrefixedStorage::new(PREFIX_MINTER_ROLES, storage);
+    let role: StoredMinterRole = may_load(&role_store, sender_raw.as_slice())?.unwrap_or_default();
+    if !role.may_update_metadata || role.is_expired(block) {
+        return Err(StdError::generic_err(custom_err));
+    }
+    Ok(())
+}
+
+/// Returns StdResult<bool>, true if a token's metadata can no longer be updated
 ///
 /// # Arguments
 ///
-/// * `contract_status` - u8 representation of the current contract status
-/// * `priority` - u8 representing the highest status level this action may execute at
-fn check_status(contract_status: u8, priority: u8) -> StdResult<()> {
-    if priority < contract_status {
-        return Err(StdError::generic_err(
-            "The contract admin has temporarily disabled this action",
-        ));
+/// * `storage` - a reference to the contract's storage
+/// * `token_key` - the token's index as a byte slice
+fn is_metadata_frozen<S: ReadonlyStorage>(storage: &S, token_key: &[u8]) -> StdResult<bool> {
+    if may_load::<bool, _>(storage, METADATA_FROZEN_KEY)?.unwrap_or(false) {
+        return Ok(true);
     }
-    Ok(())
+    let frozen_store = ReadonlyPrefixedStorage::new(PREFIX_FROZEN_METADATA, storage);
+    Ok(may_load::<bool, _>(&frozen_store, token_key)?.unwrap_or(false))
 }
 
-/// Returns StdResult<()>
-///
-/// sets new metadata
+/// Returns StdResult<Option<Metadata>> of a token's public metadata.  If the token does not
+/// have its own public metadata but is an edition, the shared metadata of its editions is
+/// merged with the token's override
 ///
 /// # Arguments
 ///
-/// * `storage` - a mutable reference to the contract's storage
-/// * `token` - a reference to the token whose metadata should be updated
-/// * `idx` - the token identifier index
-/// * `prefix` - storage prefix for the type of metadata being updated
-/// * `metadata` - a reference to the new metadata
-#[allow(clippy::too_many_arguments)]
-fn set_metadata_impl<S: Storage>(
-    storage: &mut S,
-    token: &Token,
-    idx: u32,
-    prefix: &[u8],
-    metadata: &Metadata,
-) -> StdResult<()> {
-    // do not allow the altering of sealed metadata
-    if !token.unwrapped && prefix == PREFIX_PRIV_META {
-        return Err(StdError::generic_err(
-            "The private metadata of a sealed token can not be modified",
-        ));
+/// * `storage` - a reference to the contract's storage
+/// * `token_key` - the token's index as a byte slice
+fn load_public_metadata<S: ReadonlyStorage>(
+    storage: &S,
+    token_key: &[u8],
+) -> StdResult<Option<Metadata>> {
+    let pub_store = ReadonlyPrefixedStorage::new(PREFIX_PUB_META, storage);
+    let may_meta: Option<Metadata> = may_load(&pub_store, token_key)?;
+    if may_meta.is_some() {
+        return Ok(may_meta);
     }
-    enforce_metadata_field_exclusion(metadata)?;
-    let mut meta_store = PrefixedStorage::new(prefix, storage);
-    save(&mut meta_store, &idx.to_le_bytes(), metadata)?;
-    Ok(())
+    let edition_store = ReadonlyPrefixedStorage::new(PREFIX_EDITIONS, storage);
+    if let Some(edition) = may_load::<StoredEdition, _>(&edition_store, token_key)? {
+        let shared_store = ReadonlyPrefixedStorage::new(PREFIX_EDITION_META, storage);
+        let shared: Metadata = load(&shared_store, &edition.shared.to_le_bytes())?;
+        return Ok(Some(edition.merge(shared)));
+    }
+    Ok(None)
 }
 
 // enum used to return correct response from SetWhitelistedApproval
@@ -4872,7 +8621,8 @@ fn transfer_impl<S: Storage, A: Api, Q: Querier>(
             token_id
         )));
     }
-    let old_owner = token.owner;
+    check_not_bundled(&deps.storage, idx, &token_id)?;
+    let old_owner = token.owner.clone();
     // throw error if ownership would not change
     if old_owner == recipient {
         return Err(StdError::generic_err(format!(
@@ -4880,10 +8630,49 @@ fn transfer_impl<S: Storage, A: Api, Q: Querier>(
             &token_id
         )));
     }
+    change_owner(deps, &mut token, idx, &recipient, inv_updates)?;
+
+    let sndr = if old_owner == *sender {
+        None
+    } else {
+        Some(sender.clone())
+    };
+    // store the tx
+    store_transfer(
+        &mut deps.storage,
+        config,
+        block,
+        token_id,
+        old_owner.clone(),
+        sndr,
+        recipient,
+        memo,
+    )?;
+    Ok(old_owner)
+}
+
+/// Returns StdResult<()>
+///
+/// gives a token to a new owner, clears the token's permissions and logs the inventory changes
+///
+/// # Arguments
+///
+/// * `deps` - a mutable reference to Extern containing all the contract's external dependencies
+/// * `token` - a mutable reference to the token changing owners
+/// * `idx` - the token's index
+/// * `recipient` - a reference to the new owner's address
+/// * `inv_updates` - a mutable reference to the list of token inventories to update
+fn change_owner<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    token: &mut Token,
+    idx: u32,
+    recipient: &CanonicalAddr,
+    inv_updates: &mut Vec<InventoryUpdate>,
+) -> StdResult<()> {
+    let update_addrs = vec![recipient.clone(), token.owner.clone()];
     token.owner = recipient.clone();
     token.permissions.clear();
 
-    let update_addrs = vec![recipient.clone(), old_owner.clone()];
     // save updated token info
     let mut info_store = PrefixedStorage::new(PREFIX_INFOS, &mut deps.storage);
     json_save(&mut info_store, &idx.to_le_bytes(), &token)?;
@@ -4904,7 +8693,7 @@ fn transfer_impl<S: Storage, A: Api, Q: Querier>(
             })?
         };
         // if updating the recipient's inventory
-        if inv_upd.inventory.owner == recipient {
+        if inv_upd.inventory.owner == *recipient {
             inv_upd.inventory.insert(&mut deps.storage, idx, false)?;
         // else updating the old owner's inventory
         } else {
@@ -4912,24 +8701,7 @@ fn transfer_impl<S: Storage, A: Api, Q: Querier>(
             inv_upd.remove.insert(idx);
         }
     }
-
-    let sndr = if old_owner == *sender {
-        None
-    } else {
-        Some(sender.clone())
-    };
-    // store the tx
-    store_transfer(
-        &mut deps.storage,
-        config,
-        block,
-        token_id,
-        old_owner.clone(),
-        sndr,
-        recipient,
-        memo,
-    )?;
-    Ok(old_owner)
+    Ok(())
 }
 
 // list of tokens sent from one previous owner
@@ -5065,48 +8837,7 @@ fn burn_list<S: Storage, A: Api, Q: Querier>(
                     "Burn functionality is not enabled for this token",
                 ));
             }
-            // log the inventory change
-            let inv_upd = if let Some(inv) = inv_updates
-                .iter_mut()
-                .find(|i| i.inventory.owner == token.owner)
-            {
-                inv
-            } else {
-                let inventory = Inventory::new(&deps.storage, token.owner.clone())?;
-                let new_inv = InventoryUpdate {
-                    inventory,
-                    remove: HashSet::new(),
-                };
-                inv_updates.push(new_inv);
-                inv_updates.last_mut().ok_or_else(|| {
-                    StdError::generic_err("Just pushed an InventoryUpdate so this can not happen")
-                })?
-            };
-            inv_upd.inventory.remove(&mut deps.storage, idx, false)?;
-            inv_upd.remove.insert(idx);
-            let token_key = idx.to_le_bytes();
-            // decrement token count
-            config.token_cnt = config.token_cnt.saturating_sub(1);
-            // remove from maps
-            let mut map2idx = PrefixedStorage::new(PREFIX_MAP_TO_INDEX, &mut deps.storage);
-            remove(&mut map2idx, token_id.as_bytes());
-            let mut map2id = PrefixedStorage::new(PREFIX_MAP_TO_ID, &mut deps.storage);
-            remove(&mut map2id, &token_key);
-            // remove the token info
-            let mut info_store = PrefixedStorage::new(PREFIX_INFOS, &mut deps.storage);
-            remove(&mut info_store, &token_key);
-            // remove metadata if existent
-            let mut pub_store = PrefixedStorage::new(PREFIX_PUB_META, &mut deps.storage);
-            remove(&mut pub_store, &token_key);
-            let mut priv_store = PrefixedStorage::new(PREFIX_PRIV_META, &mut deps.storage);
-            remove(&mut priv_store, &token_key);
-            // remove mint run info if existent
-            let mut run_store = PrefixedStorage::new(PREFIX_MINT_RUN, &mut deps.storage);
-            remove(&mut run_store, &token_key);
-            // remove royalty info if existent
-            let mut roy_store = PrefixedStorage::new(PREFIX_ROYALTY_INFO, &mut deps.storage);
-            remove(&mut roy_store, &token_key);
-
+            remove_token(deps, config, &token, idx, &token_id, &mut inv_updates)?;
             let brnr = if token.owner == *sender {
                 None
             } else {
@@ -5129,6 +8860,100 @@ fn burn_list<S: Storage, A: Api, Q: Querier>(
     Ok(())
 }
 
+/// Returns StdResult<()>
+///
+/// removes a token and all of its data from storage
+///
+/// # Arguments
+///
+/// * `deps` - a mutable reference to Extern containing all the contract's external dependencies
+/// * `config` - a mutable reference to the Config
+/// * `token` - a reference to the token being removed
+/// * `idx` - the token's index
+/// * `token_id` - token id String slice of the token being removed
+/// * `inv_updates` - a mutable reference to the list of token inventories to update
+fn remove_token<S: Storage, A: Api, Q: Querier>(
+    deps: &mut Extern<S, A, Q>,
+    config: &mut Config,
+    token: &Token,
+    idx: u32,
+    token_id: &str,
+    inv_updates: &mut Vec<InventoryUpdate>,
+) -> StdResult<()> {
+    let child_store = ReadonlyPrefixedStorage::new(PREFIX_CHILDREN, &deps.storage);
+    if may_load::<Vec<StoredChild>, _>(&child_store, &idx.to_le_bytes())?.is_some() {
+        return Err(StdError::generic_err(format!(
+            "Token {} must have its children detached before it is burned",
+            token_id
+        )));
+    }
+    check_not_bundled(&deps.storage, idx, token_id)?;
+    // log the inventory change
+    let inv_upd = if let Some(inv) = inv_updates
+        .iter_mut()
+        .find(|i| i.inventory.owner == token.owner)
+    {
+        inv
+    } else {
+        let inventory = Inventory::new(&deps.storage, token.owner.clone())?;
+        let new_inv = InventoryUpdate {
+            inventory,
+            remove: HashSet::new(),
+        };
+        inv_updates.push(new_inv);
+        inv_updates.last_mut().ok_or_else(|| {
+            StdError::generic_err("Just pushed an InventoryUpdate so this can not happen")
+        })?
+    };
+    inv_upd.inventory.remove(&mut deps.storage, idx, false)?;
+    inv_upd.remove.insert(idx);
+    let token_key = idx.to_le_bytes();
+    // decrement token count
+    config.token_cnt = config.token_cnt.saturating_sub(1);
+    // remove from maps
+    let mut map2idx = PrefixedStorage::new(PREFIX_MAP_TO_INDEX, &mut deps.storage);
+    remove(&mut map2idx, token_id.as_bytes());
+    let mut map2id = PrefixedStorage::new(PREFIX_MAP_TO_ID, &mut deps.storage);
+    remove(&mut map2id, &token_key);
+    // remove the token info
+    let mut info_store = PrefixedStorage::new(PREFIX_INFOS, &mut deps.storage);
+    remove(&mut info_store, &token_key);
+    // remove metadata if existent
+    let old_pub = load_public_metadata(&deps.storage, &token_key)?;
+    update_trait_index(&mut deps.storage, idx, old_pub.as_ref(), None)?;
+    let mut pub_store = PrefixedStorage::new(PREFIX_PUB_META, &mut deps.storage);
+    remove(&mut pub_store, &token_key);
+    let mut edition_store = PrefixedStorage::new(PREFIX_EDITIONS, &mut deps.storage);
+    remove(&mut edition_store, &token_key);
+    let mut priv_store = PrefixedStorage::new(PREFIX_PRIV_META, &mut deps.storage);
+    remove(&mut priv_store, &token_key);
+    let mut unlock_store = PrefixedStorage::new(PREFIX_UNLOCKABLE, &mut deps.storage);
+    remove(&mut unlock_store, &token_key);
+    // take the token off the market
+    save_sale_info(&mut deps.storage, idx, None)?;
+    let mut for_sale: Vec<String> = may_load(&deps.storage, FOR_SALE_KEY)?.unwrap_or_default();
+    if for_sale.iter().any(|id| id == token_id) {
+        for_sale.retain(|id| id != token_id);
+        save(&mut deps.storage, FOR_SALE_KEY, &for_sale)?;
+    }
+    // remove mint run info if existent
+    let mut run_store = PrefixedStorage::new(PREFIX_MINT_RUN, &mut deps.storage);
+    remove(&mut run_store, &token_key);
+    let mut token_run_store = PrefixedStorage::new(PREFIX_TOKEN_MINT_RUN, &mut deps.storage);
+    remove(&mut token_run_store, &token_key);
+    // remove royalty info if existent
+    let mut roy_store = PrefixedStorage::new(PREFIX_ROYALTY_INFO, &mut deps.storage);
+    let old_roy: Option<StoredRoyaltyInfo> = may_load(&roy_store, &token_key)?;
+    remove(&mut roy_store, &token_key);
+    update_royalty_index(
+        &mut deps.storage,
+        idx,
+        &royalty_recipients(old_roy.as_ref()),
+        &[],
+    )?;
+    Ok(())
+}
+
 /// Returns <Vec<String>>
 ///
 /// mints a list of new tokens and returns the ids of the tokens minted
@@ -5140,30 +8965,49 @@ fn burn_list<S: Storage, A: Api, Q: Querier>(
 /// * `config` - a mutable reference to the Config
 /// * `sender_raw` - a reference to the message sender address
 /// * `mints` - list of mints to perform
+/// * `mint_run_id` - optional mint run ID the tokens are minted with
 fn mint_list<S: Storage, A: Api, Q: Querier>(
     deps: &mut Extern<S, A, Q>,
     env: &Env,
     config: &mut Config,
     sender_raw: &CanonicalAddr,
     mints: Vec<Mint>,
+    mint_run_id: Option<&String>,
 ) -> StdResult<Vec<String>> {
+    // enforce the quotas of the minter's role, if it has one
+    let mut role_store = PrefixedStorage::new(PREFIX_MINTER_ROLES, &mut deps.storage);
+    if let Some(mut role) = may_load::<StoredMinterRole, _>(&role_store, sender_raw.as_slice())? {
+        role.use_quota(&env.block, mints.len() as u32, mint_run_id)?;
+        save(&mut role_store, sender_raw.as_slice(), &role)?;
+    }
     let mut inventories: Vec<Inventory> = Vec::new();
     let mut minted: Vec<String> = Vec::new();
     let default_roy: Option<StoredRoyaltyInfo> = may_load(&deps.storage, DEFAULT_ROYALTY_KEY)?;
+    // ids of tokens listed at mint, added to the for sale list once after all the mints
+    let mut listed: Vec<String> = Vec::new();
 
     for mint in mints.into_iter() {
+        if let Some(pub_meta) = mint.public_metadata.as_ref() {
+            validate_metadata(pub_meta, "public_metadata")?;
+        }
+        if let Some(priv_meta) = mint.private_metadata.as_ref() {
+            validate_metadata(priv_meta, "private_metadata")?;
+        }
         let id = mint.token_id.unwrap_or(format!("{}", config.mint_cnt));
         let id_ = id.clone();
 
         // check if id already exists
-        let mut map2idx = PrefixedStorage::new(PREFIX_MAP_TO_INDEX, &mut deps.storage);
+        let map2idx = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_INDEX, &deps.storage);
         let may_exist: Option<u32> = may_load(&map2idx, id.as_bytes())?;
-        if may_exist.is_some() {
+        let lazy_store = ReadonlyPrefixedStorage::new(PREFIX_LAZY_LISTINGS, &deps.storage);
+        let is_lazy = may_load::<StoredLazyListing, _>(&lazy_store, id.as_bytes())?.is_some();
+        if may_exist.is_some() || is_lazy {
             return Err(StdError::generic_err(format!(
                 "Token ID {} is already in use",
                 id
             )));
         }
+        let mut map2idx = PrefixedStorage::new(PREFIX_MAP_TO_INDEX, &mut deps.storage);
 
         // increment token count
         config.token_cnt = config.token_cnt.checked_add(1).ok_or_else(|| {
@@ -5220,12 +9064,11 @@ fn mint_list<S: Storage, A: Api, Q: Querier>(
         //
         // save the metadata
         if let Some(pub_meta) = mint.public_metadata {
-            enforce_metadata_field_exclusion(&pub_meta)?;
+            update_trait_index(&mut deps.storage, config.mint_cnt, None, Some(&pub_meta))?;
             let mut pub_store = PrefixedStorage::new(PREFIX_PUB_META, &mut deps.storage);
             save(&mut pub_store, &token_key, &pub_meta)?;
         }
         if let Some(priv_meta) = mint.private_metadata {
-            enforce_metadata_field_exclusion(&priv_meta)?;
             let mut priv_store = PrefixedStorage::new(PREFIX_PRIV_META, &mut deps.storage);
             save(&mut priv_store, &token_key, &priv_meta)?;
         }
@@ -5258,7 +9101,28 @@ fn mint_list<S: Storage, A: Api, Q: Querier>(
                 mint.royalty_info.as_ref(),
                 default_roy.as_ref(),
                 &token_key,
+                config.max_royalty_bps,
+            )?;
+            let stored: Option<StoredRoyaltyInfo> = may_load(&roy_store, &token_key)?;
+            update_royalty_index(
+                &mut deps.storage,
+                config.mint_cnt,
+                &[],
+                &royalty_recipients(stored.as_ref()),
+            )?;
+        }
+        // put the token up for sale if a listing was provided
+        if let Some(listing) = mint.listing {
+            list_minted_token(
+                &mut deps.storage,
+                &env.block,
+                sender_raw,
+                &token,
+                config.mint_cnt,
+                &id,
+                listing,
             )?;
+            listed.push(id.clone());
         }
 
         // store the tx
@@ -5277,15 +9141,94 @@ fn mint_list<S: Storage, A: Api, Q: Querier>(
             StdError::generic_err("Attempting to mint more times than the implementation limit")
         })?;
     }
+    if !listed.is_empty() {
+        let mut for_sale: Vec<String> = may_load(&deps.storage, FOR_SALE_KEY)?.unwrap_or_default();
+        for_sale.extend(listed);
+        save(&mut deps.storage, FOR_SALE_KEY, &for_sale)?;
+    }
     // save all the updated inventories
     for inventory in inventories.iter() {
         inventory.save(&mut deps.storage)?;
     }
     save(&mut deps.storage, CONFIG_KEY, &config)?;
+    // fold the mint into the entropy of the reveal offset.  The private seed is advanced with
+    // the entropy of public minters, so it can not be predicted by the admin after any public
+    // mint that supplied secret entropy
+    if let Some(mut commitment) =
+        may_load::<StoredRevealCommitment, _>(&deps.storage, REVEAL_COMMITMENT_KEY)?
+    {
+        if commitment.offset.is_none() {
+            let prng_seed: Vec<u8> = load(&deps.storage, PRNG_SEED_KEY)?;
+            let mut entropy = commitment.mint_entropy.clone();
+            entropy.extend_from_slice(&prng_seed);
+            entropy.extend_from_slice(&env.block.height.to_be_bytes());
+            entropy.extend_from_slice(&env.block.time.to_be_bytes());
+            entropy.extend_from_slice(env.message.sender.as_str().as_bytes());
+            entropy.extend_from_slice(&config.mint_cnt.to_le_bytes());
+            commitment.mint_entropy = sha_256(&entropy).to_vec();
+            save(&mut deps.storage, REVEAL_COMMITMENT_KEY, &commitment)?;
+        }
+    }
 
     Ok(minted)
 }
 
+/// Returns StdResult<()>
+///
+/// saves the sale information of a freshly minted token.  The caller adds the token to the
+/// for sale list
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `block` - a reference to the current BlockInfo
+/// * `minter` - a reference to the address of the minter
+/// * `token` - a reference to the minted Token
+/// * `token_idx` - index of the token
+/// * `token_id` - id of the token
+/// * `listing` - the listing parameters
+fn list_minted_token<S: Storage>(
+    storage: &mut S,
+    block: &BlockInfo,
+    minter: &CanonicalAddr,
+    token: &Token,
+    token_idx: u32,
+    token_id: &str,
+    listing: MintListing,
+) -> StdResult<()> {
+    // a minter can not put a price on a token it gave to someone else
+    if token.owner != *minter {
+        return Err(StdError::generic_err(
+            "Only tokens minted to the minter can be listed for sale at mint",
+        ));
+    }
+    if !token.transferable {
+        return Err(StdError::generic_err(
+            "Non-transferable tokens can not be listed for sale",
+        ));
+    }
+    if listing.price == 0 {
+        return Err(StdError::generic_err(
+            "Invalid input! Price cannot be set to 0.",
+        ));
+    }
+    if let Some(exp) = listing.expiration.as_ref() {
+        if exp.is_expired(block) {
+            return Err(StdError::generic_err(
+                "The listing's expiration has already passed",
+            ));
+        }
+    }
+    let sale = TokenSaleInfo {
+        token_id: token_id.to_string(),
+        sale_status: SaleStatus::ForSale,
+        token_price: Some(listing.price),
+        denom: listing.denom,
+        expiration: listing.expiration,
+    };
+    save_sale_info(storage, token_idx, Some(&sale))
+}
+
 /// Returns StdResult<()>
 ///
 /// verifies the royalty information is valid and if so, stores the royalty info for the token
@@ -5299,29 +9242,18 @@ fn mint_list<S: Storage, A: Api, Q: Querier>(
 /// * `default` - an optional reference to the default StoredRoyaltyInfo to use if royalty_info is
 ///               not provided
 /// * `key` - the storage key (either token key or default key)
+/// * `max_bps` - the maximum sum of the royalty rates, in basis points
 fn store_royalties<S: Storage, A: Api>(
     storage: &mut S,
     api: &A,
     royalty_info: Option<&RoyaltyInfo>,
     default: Option<&StoredRoyaltyInfo>,
     key: &[u8],
+    max_bps: u16,
 ) -> StdResult<()> {
     // if RoyaltyInfo is provided, check and save it
     if let Some(royal_inf) = royalty_info {
-        // the allowed message length won't let enough u16 rates to overflow u128
-        let total_rates: u128 = royal_inf.royalties.iter().map(|r| r.rate as u128).sum();
-        let (royalty_den, overflow) =
-            U256::from(10).overflowing_pow(U256::from(royal_inf.decimal_places_in_rates));
-        if overflow {
-            return Err(StdError::generic_err(
-                "The number of decimal places used in the royalty rates is larger than supported",
-            ));
-        }
-        if U256::from(total_rates) > royalty_den {
-            return Err(StdError::generic_err(
-                "The sum of royalty rates must not exceed 100%",
-            ));
-        }
+        check_royalty_rates(royal_inf, max_bps)?;
         let stored = royal_inf.to_stored(api)?;
         save(storage, key, &stored)
     } else if let Some(def) = default {
@@ -5334,17 +9266,181 @@ fn store_royalties<S: Storage, A: Api>(
 
 /// Returns StdResult<()>
 ///
-/// makes sure that Metadata does not have both `token_uri` and `extension`
+/// verifies that the sum of the royalty rates does not exceed 100% or the maximum allowed
+///
+/// # Arguments
+///
+/// * `royalty_info` - a reference to the RoyaltyInfo to check
+/// * `max_bps` - maximum sum of royalty rates in basis points
+fn check_royalty_rates(royalty_info: &RoyaltyInfo, max_bps: u16) -> StdResult<()> {
+    // the allowed message length won't let enough u16 rates to overflow u128
+    let total_rates: u128 = royalty_info.royalties.iter().map(|r| r.rate as u128).sum();
+    let (royalty_den, overflow) =
+        U256::from(10).overflowing_pow(U256::from(royalty_info.decimal_places_in_rates));
+    if overflow {
+        return Err(StdError::generic_err(
+            "The number of decimal places used in the royalty rates is larger than supported",
+        ));
+    }
+    if U256::from(total_rates) > royalty_den {
+        return Err(StdError::generic_err(
+            "The sum of royalty rates must not exceed 100%",
+        ));
+    }
+    if U256::from(total_rates) * U256::from(MAX_BPS) > royalty_den * U256::from(max_bps) {
+        return Err(StdError::generic_err(format!(
+            "The sum of royalty rates must not exceed {} basis points",
+            max_bps
+        )));
+    }
+    Ok(())
+}
+
+/// Returns StdResult<Option<StoredRoyaltyInfo>> of the royalties that a sale of the token would
+/// pay.  The default royalties are copied to the token when it is minted or its royalties are
+/// reset, so a token without its own royalties pays none.  This way a sold token never depends
+/// on the default, which any minter can change
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `token_key` - the token's storage key
+fn effective_royalties<S: ReadonlyStorage>(
+    storage: &S,
+    token_key: &[u8],
+) -> StdResult<Option<StoredRoyaltyInfo>> {
+    let roy_store = ReadonlyPrefixedStorage::new(PREFIX_ROYALTY_INFO, storage);
+    may_load(&roy_store, token_key)
+}
+
+/// Returns Vec<CanonicalAddr> of the addresses that receive royalties
+///
+/// # Arguments
+///
+/// * `royalties` - optional reference to the StoredRoyaltyInfo, where None means no royalties
+fn royalty_recipients(royalties: Option<&StoredRoyaltyInfo>) -> Vec<CanonicalAddr> {
+    royalties.map_or_else(Vec::new, |r| {
+        r.royalties
+            .iter()
+            .map(|roy| roy.recipient.clone())
+            .collect()
+    })
+}
+
+/// Returns (U256, U256) of the numerator and denominator of the sum of the royalty rates
+///
+/// # Arguments
+///
+/// * `royalties` - optional reference to the StoredRoyaltyInfo, where None means no royalties
+fn royalty_total(royalties: Option<&StoredRoyaltyInfo>) -> (U256, U256) {
+    royalties.map_or((U256::zero(), U256::one()), |r| {
+        let total: u128 = r.royalties.iter().map(|roy| roy.rate as u128).sum();
+        (
+            U256::from(total),
+            U256::from(10).pow(U256::from(r.decimal_places_in_rates)),
+        )
+    })
+}
+
+/// Returns StdResult<()>
+///
+/// makes sure that Metadata does not have both `token_uri` and `extension`, that its urls use
+/// an accepted scheme, that its background color is six hexadecimal characters, that its
+/// attributes are within the size limits, and that its media files have a known file type
 ///
 /// # Arguments
 ///
 /// * `metadata` - a reference to Metadata
-fn enforce_metadata_field_exclusion(metadata: &Metadata) -> StdResult<()> {
+/// * `field` - name of the message field the metadata was given in, used in error messages
+fn validate_metadata(metadata: &Metadata, field: &str) -> StdResult<()> {
     if metadata.token_uri.is_some() && metadata.extension.is_some() {
         return Err(StdError::generic_err(
             "Metadata can not have BOTH token_uri AND extension",
         ));
     }
+    if let Some(uri) = metadata.token_uri.as_ref() {
+        validate_uri(uri, &format!("{}.token_uri", field))?;
+    }
+    if let Some(ext) = metadata.extension.as_ref() {
+        let urls = [
+            (&ext.image, "image"),
+            (&ext.external_url, "external_url"),
+            (&ext.animation_url, "animation_url"),
+            (&ext.youtube_url, "youtube_url"),
+        ];
+        for (url, name) in urls.iter() {
+            if let Some(url) = url.as_ref() {
+                validate_uri(url, &format!("{}.extension.{}", field, name))?;
+            }
+        }
+        if let Some(color) = ext.background_color.as_ref() {
+            if color.len() != 6 || !color.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(StdError::generic_err(format!(
+                    "{}.extension.background_color must be six hexadecimal characters without a #",
+                    field
+                )));
+            }
+        }
+        if let Some(attributes) = ext.attributes.as_ref() {
+            if attributes.len() > MAX_ATTRIBUTES {
+                return Err(StdError::generic_err(format!(
+                    "{}.extension.attributes can not have more than {} traits",
+                    field, MAX_ATTRIBUTES
+                )));
+            }
+            for (i, attr) in attributes.iter().enumerate() {
+                let trait_field = format!("{}.extension.attributes[{}]", field, i);
+                let lengths = [
+                    (attr.display_type.as_deref(), "display_type"),
+                    (attr.trait_type.as_deref(), "trait_type"),
+                    (Some(attr.value.as_str()), "value"),
+                    (attr.max_value.as_deref(), "max_value"),
+                ];
+                for (text, name) in lengths.iter() {
+                    if text.is_some_and(|t| t.chars().count() > MAX_TRAIT_LENGTH) {
+                        return Err(StdError::generic_err(format!(
+                            "{}.{} can not be longer than {} characters",
+                            trait_field, name, MAX_TRAIT_LENGTH
+                        )));
+                    }
+                }
+            }
+        }
+        if let Some(media) = ext.media.as_ref() {
+            for (i, file) in media.iter().enumerate() {
+                let media_field = format!("{}.extension.media[{}]", field, i);
+                validate_uri(&file.url, &format!("{}.url", media_field))?;
+                if let Some(file_type) = file.file_type.as_ref() {
+                    if !MEDIA_FILE_TYPES.contains(&file_type.as_str()) {
+                        return Err(StdError::generic_err(format!(
+                            "{}.file_type must be one of {}",
+                            media_field,
+                            MEDIA_FILE_TYPES.join(", ")
+                        )));
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns StdResult<()>
+///
+/// makes sure that a uri is prefixed with one of the accepted schemes
+///
+/// # Arguments
+///
+/// * `uri` - the uri to check
+/// * `field` - name of the metadata field holding the uri, used in error messages
+fn validate_uri(uri: &str, field: &str) -> StdResult<()> {
+    if !URI_SCHEMES.iter().any(|scheme| uri.starts_with(scheme)) {
+        return Err(StdError::generic_err(format!(
+            "{} must start with {}",
+            field,
+            URI_SCHEMES.join(", ")
+        )));
+    }
     Ok(())
 }
 
@@ -5435,7 +9531,6 @@ pub fn dossier_list<S: Storage, A: Api, Q: Querier>(
     let mut dossiers: Vec<BatchNftDossierElement> = Vec::new();
     // set up all the immutable storage references
     let own_priv_store = ReadonlyPrefixedStorage::new(PREFIX_OWNER_PRIV, &deps.storage);
-    let pub_store = ReadonlyPrefixedStorage::new(PREFIX_PUB_META, &deps.storage);
     let priv_store = ReadonlyPrefixedStorage::new(PREFIX_PRIV_META, &deps.storage);
     let roy_store = ReadonlyPrefixedStorage::new(PREFIX_ROYALTY_INFO, &deps.storage);
     let run_store = ReadonlyPrefixedStorage::new(PREFIX_MINT_RUN, &deps.storage);
@@ -5498,7 +9593,7 @@ pub fn dossier_list<S: Storage, A: Api, Q: Querier>(
         };
         // get the public metadata
         let token_key = idx.to_le_bytes();
-        let public_metadata: Option<Metadata> = may_load(&pub_store, &token_key)?;
+        let public_metadata: Option<Metadata> = load_public_metadata(&deps.storage, &token_key)?;
         // get the private metadata if it is not sealed and if the viewer is permitted
         let mut display_private_metadata_error = None;
         let private_metadata = if let Err(err) = check_perm_core(
@@ -5602,6 +9697,7 @@ pub fn dossier_list<S: Storage, A: Api, Q: Querier>(
             private_metadata_is_public_expiration,
             token_approvals,
             inventory_approvals,
+            frozen: is_metadata_frozen(&deps.storage, &token_key)?,
         });
     }
     Ok(dossiers)
@@ -0,0 +1,372 @@
+use cosmwasm_std::{
+    Api, Binary, BlockInfo, CanonicalAddr, HumanAddr, StdError, StdResult, Uint128,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::expiration::Expiration;
+use crate::rand::sha_256;
+use crate::royalties::RoyaltyInfo;
+use crate::token::Metadata;
+
+/// metadata given to a token minted through the public mint
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct MintTemplate {
+    /// optional public metadata that can be seen by everyone
+    pub public_metadata: Option<Metadata>,
+    /// optional private metadata that can only be seen by owner and whitelist
+    pub private_metadata: Option<Metadata>,
+}
+
+/// public mint settings
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PublicMintInfo {
+    /// true if the public mint is open
+    pub enabled: bool,
+    /// price of each token in uscrt
+    pub price: Uint128,
+    /// maximum number of tokens that can be minted through the public mint
+    pub max_supply: u32,
+    /// optional maximum number of tokens a single address can mint
+    pub per_wallet_limit: Option<u32>,
+    /// number of tokens minted through the public mint so far
+    pub minted: u32,
+    /// number of uploaded templates that have not been used yet
+    pub templates_remaining: u32,
+    /// true if each mint draws a random unused template instead of the next one uploaded
+    pub random_assignment: bool,
+    /// the mint phases.  If there are any, the price and per wallet limit of the active phase
+    /// are used instead of the ones above
+    pub phases: Vec<MintPhase>,
+    /// name of the phase that was active as of the last handle executed on the contract
+    pub active_phase: Option<String>,
+}
+
+/// stored public mint settings
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct StoredPublicMint {
+    /// true if the public mint is open
+    pub enabled: bool,
+    /// price of each token in uscrt
+    pub price: u128,
+    /// maximum number of tokens that can be minted through the public mint
+    pub max_supply: u32,
+    /// optional maximum number of tokens a single address can mint
+    pub per_wallet_limit: Option<u32>,
+    /// number of tokens minted through the public mint so far
+    pub minted: u32,
+    /// number of templates uploaded so far
+    pub templates: u32,
+    /// true if each mint draws a random unused template
+    pub random_assignment: bool,
+    /// the mint phases
+    pub phases: Vec<StoredMintPhase>,
+}
+
+impl StoredPublicMint {
+    /// Returns PublicMintInfo from creating a displayable PublicMintInfo from a StoredPublicMint
+    ///
+    /// # Arguments
+    ///
+    /// * `block` - a reference to the BlockInfo used to determine the active phase
+    pub fn to_display(&self, block: &BlockInfo) -> PublicMintInfo {
+        PublicMintInfo {
+            enabled: self.enabled,
+            price: Uint128(self.price),
+            max_supply: self.max_supply,
+            per_wallet_limit: self.per_wallet_limit,
+            minted: self.minted,
+            templates_remaining: self.templates.saturating_sub(self.minted),
+            random_assignment: self.random_assignment,
+            phases: self.phases.iter().map(|p| p.to_display()).collect(),
+            active_phase: self.active_phase(block).map(|p| p.name.clone()),
+        }
+    }
+
+    /// Returns Option<&StoredMintPhase> of the settings of the first phase that is active at
+    /// the given block
+    ///
+    /// # Arguments
+    ///
+    /// * `block` - a reference to the current BlockInfo
+    pub fn active_phase(&self, block: &BlockInfo) -> Option<&StoredMintPhase> {
+        self.phases.iter().find(|p| p.is_active(block))
+    }
+}
+
+/// a phase of the public mint, such as an OG, allowlist, or public phase
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct MintPhase {
+    /// name of the phase
+    pub name: String,
+    /// optional time the phase starts.  If omitted, the phase starts immediately
+    pub start: Option<Expiration>,
+    /// time the phase ends
+    pub end: Expiration,
+    /// price of each token in uscrt
+    pub price: Uint128,
+    /// optional maximum number of tokens a single address can mint during this phase
+    pub per_wallet_limit: Option<u32>,
+    /// optional root of the Merkle tree of addresses allowed to mint during this phase.  Each
+    /// leaf is the sha256 hash of an address, and each parent is the sha256 hash of its two
+    /// children concatenated in ascending order
+    pub merkle_root: Option<Binary>,
+}
+
+impl MintPhase {
+    /// Returns StdResult<StoredMintPhase> from creating a StoredMintPhase from a MintPhase
+    pub fn into_stored(self) -> StdResult<StoredMintPhase> {
+        let merkle_root = self
+            .merkle_root
+            .map(|r| {
+                if r.len() != 32 {
+                    return Err(StdError::generic_err(format!(
+                        "The Merkle root of phase {} must be a 32 byte sha256 hash",
+                        self.name
+                    )));
+                }
+                Ok(r.0)
+            })
+            .transpose()?;
+        Ok(StoredMintPhase {
+            name: self.name,
+            start: self.start,
+            end: self.end,
+            price: self.price.u128(),
+            per_wallet_limit: self.per_wallet_limit,
+            merkle_root,
+        })
+    }
+}
+
+/// stored phase of the public mint
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct StoredMintPhase {
+    /// name of the phase
+    pub name: String,
+    /// optional time the phase starts
+    pub start: Option<Expiration>,
+    /// time the phase ends
+    pub end: Expiration,
+    /// price of each token in uscrt
+    pub price: u128,
+    /// optional maximum number of tokens a single address can mint during this phase
+    pub per_wallet_limit: Option<u32>,
+    /// optional root of the Merkle tree of addresses allowed to mint during this phase
+    pub merkle_root: Option<Vec<u8>>,
+}
+
+impl StoredMintPhase {
+    /// Returns MintPhase from creating a displayable MintPhase from a StoredMintPhase
+    pub fn to_display(&self) -> MintPhase {
+        MintPhase {
+            name: self.name.clone(),
+            start: self.start,
+            end: self.end,
+            price: Uint128(self.price),
+            per_wallet_limit: self.per_wallet_limit,
+            merkle_root: self.merkle_root.clone().map(Binary),
+        }
+    }
+
+    /// Returns bool, true if the phase has started and not yet ended
+    ///
+    /// # Arguments
+    ///
+    /// * `block` - a reference to the current BlockInfo
+    pub fn is_active(&self, block: &BlockInfo) -> bool {
+        self.start.is_none_or(|s| s.is_expired(block)) && !self.end.is_expired(block)
+    }
+}
+
+/// Returns bool, true if the proof shows the address is a leaf of the Merkle tree
+///
+/// # Arguments
+///
+/// * `root` - the root of the Merkle tree
+/// * `address` - the address whose membership is being proven
+/// * `proof` - the sibling hashes on the path from the address' leaf to the root
+pub fn verify_merkle_proof(root: &[u8], address: &str, proof: &[Binary]) -> bool {
+    let mut hash = sha_256(address.as_bytes());
+    for sibling in proof.iter() {
+        let mut pair = Vec::with_capacity(64);
+        if hash.as_ref() <= sibling.as_slice() {
+            pair.extend_from_slice(&hash);
+            pair.extend_from_slice(sibling.as_slice());
+        } else {
+            pair.extend_from_slice(sibling.as_slice());
+            pair.extend_from_slice(&hash);
+        }
+        hash = sha_256(&pair);
+    }
+    hash.as_ref() == root
+}
+
+/// commitment to the metadata of a collection that is revealed by the admin
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RevealCommitment {
+    /// sha256 hash of the full metadata set, committed before any token was revealed
+    pub metadata_hash: Binary,
+    /// sha256 hash of the seed used to determine the reveal offset
+    pub seed_hash: Binary,
+    /// number of tokens in the collection
+    pub supply: u32,
+    /// the seed, once it has been published
+    pub seed: Option<Binary>,
+    /// once the reveal has started, token index i receives the sealed metadata that was minted
+    /// to token index (i + offset) % supply
+    pub offset: Option<u32>,
+    /// number of tokens revealed so far
+    pub revealed: u32,
+}
+
+/// stored reveal commitment and the progress of the collection reveal
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct StoredRevealCommitment {
+    /// sha256 hash of the full metadata set
+    pub metadata_hash: Vec<u8>,
+    /// sha256 hash of the seed
+    pub seed_hash: Vec<u8>,
+    /// number of tokens in the collection
+    pub supply: u32,
+    /// hash chain of the mints made after the commitment
+    pub mint_entropy: Vec<u8>,
+    /// the published seed
+    pub seed: Option<Vec<u8>>,
+    /// the reveal offset
+    pub offset: Option<u32>,
+    /// number of tokens revealed so far
+    pub revealed: u32,
+    /// first index of the rotation cycle being processed
+    pub cycle_start: u32,
+    /// index that receives metadata next
+    pub position: u32,
+    /// true if the metadata of the cycle start has been set aside
+    pub carrying: bool,
+    /// the metadata of the cycle start that is set aside until the cycle completes
+    pub carried: Option<Metadata>,
+}
+
+impl StoredRevealCommitment {
+    /// Returns RevealCommitment from creating a displayable RevealCommitment from a
+    /// StoredRevealCommitment
+    pub fn to_display(&self) -> RevealCommitment {
+        RevealCommitment {
+            metadata_hash: Binary(self.metadata_hash.clone()),
+            seed_hash: Binary(self.seed_hash.clone()),
+            supply: self.supply,
+            seed: self.seed.clone().map(Binary),
+            offset: self.offset,
+            revealed: self.revealed,
+        }
+    }
+}
+
+/// a listing whose editions are minted when they are bought
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct LazyListing {
+    /// id used to buy the listing's editions
+    pub listing_id: String,
+    /// address that created the listing and is paid for its sales
+    pub creator: HumanAddr,
+    /// price of each edition in uscrt
+    pub price: u32,
+    /// number of editions that can be sold
+    pub editions: u32,
+    /// number of editions sold so far
+    pub sold: u32,
+    /// optional mint run number of the editions
+    pub mint_run: Option<u32>,
+    /// optional public metadata of the editions
+    pub public_metadata: Option<Metadata>,
+}
+
+/// stored lazy listing
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct StoredLazyListing {
+    /// address that created the listing
+    pub creator: CanonicalAddr,
+    /// price of each edition in uscrt
+    pub price: u32,
+    /// number of editions that can be sold
+    pub editions: u32,
+    /// number of editions sold so far
+    pub sold: u32,
+    /// optional mint run number of the editions
+    pub mint_run: Option<u32>,
+    /// optional mint run ID of the editions
+    pub mint_run_id: Option<String>,
+    /// optional public metadata of the editions
+    pub public_metadata: Option<Metadata>,
+    /// optional private metadata of the editions
+    pub private_metadata: Option<Metadata>,
+    /// optional royalty information of the editions
+    pub royalty_info: Option<RoyaltyInfo>,
+    /// optional index of the editions' mint run in the mint run registry
+    pub registry_index: Option<u32>,
+}
+
+impl StoredLazyListing {
+    /// Returns StdResult<LazyListing> from creating a displayable LazyListing from a
+    /// StoredLazyListing
+    ///
+    /// # Arguments
+    ///
+    /// * `api` - a reference to the Api used to convert human and canonical addresses
+    /// * `listing_id` - id of the listing
+    pub fn to_display<A: Api>(&self, api: &A, listing_id: String) -> StdResult<LazyListing> {
+        Ok(LazyListing {
+            listing_id,
+            creator: api.human_address(&self.creator)?,
+            price: self.price,
+            editions: self.editions,
+            sold: self.sold,
+            mint_run: self.mint_run,
+            public_metadata: self.public_metadata.clone(),
+        })
+    }
+}
+
+/// a mint authorized off-chain by a creator's registered voucher signer
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct MintVoucher {
+    /// address of this contract, so the voucher can not be redeemed on another contract
+    pub contract: HumanAddr,
+    /// minter whose registered public key signed the voucher.  The creator is recorded as the
+    /// token's minter and is credited the price
+    pub creator: HumanAddr,
+    /// nonce that may only be redeemed once per creator
+    pub nonce: u64,
+    /// owner of the minted token
+    pub recipient: HumanAddr,
+    /// price in uscrt paid by the redeemer
+    pub price: Uint128,
+    /// optional token id.  If omitted, the token's index is used
+    pub token_id: Option<String>,
+    /// optional public metadata of the token
+    pub public_metadata: Option<Metadata>,
+    /// optional private metadata of the token
+    pub private_metadata: Option<Metadata>,
+    /// optional royalty information of the token
+    pub royalty_info: Option<RoyaltyInfo>,
+    /// optional expiration after which the voucher can no longer be redeemed
+    pub expiration: Option<Expiration>,
+}
+
+impl MintVoucher {
+    /// Returns StdResult<Vec<u8>> of the sign-doc whose sha256 hash the creator's voucher
+    /// signer signs
+    ///
+    /// The sign-doc is the voucher serialized as compact JSON with serde-json-wasm.  Fields are
+    /// written in the order they are declared here, and nested metadata, royalty and expiration
+    /// fields in the order they are declared on their own structs.  There is no whitespace,
+    /// omitted optional fields are written as `null`, the nonce and block heights and times are
+    /// written as integers, and the price as a decimal string.  For example,
+    /// `{"contract":"secret1...","creator":"secret1...","nonce":7,"recipient":"secret1...",
+    /// "price":"100","token_id":null,"public_metadata":null,"private_metadata":null,
+    /// "royalty_info":null,"expiration":{"at_height":100}}`.  A backend must produce exactly
+    /// these bytes, so generic JSON canonicalization such as RFC 8785 will not match
+    pub fn sign_doc(&self) -> StdResult<Vec<u8>> {
+        serde_json_wasm::to_vec(self).map_err(|e| StdError::generic_err(e.to_string()))
+    }
+}
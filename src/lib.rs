@@ -1,8 +1,11 @@
 //#![allow(clippy::field_reassign_with_default)]
+pub mod composable;
 pub mod contract;
 pub mod expiration;
 mod inventory;
+pub mod launchpad;
 pub mod mint_run;
+pub mod minters;
 pub mod msg;
 mod rand;
 pub mod receiver;
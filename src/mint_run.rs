@@ -2,6 +2,8 @@ use cosmwasm_std::{Api, CanonicalAddr, HumanAddr, StdResult};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::token::{Metadata, Trait};
+
 /// information about the minting of the NFT
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 pub struct MintRunInfo {
@@ -81,3 +83,129 @@ pub struct SerialNumber {
     /// represent that this token is number m of n
     pub quantity_minted_this_run: Option<u32>,
 }
+
+/// identifies a registered mint run
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct MintRunRef {
+    /// mint run ID given when minting
+    pub run_id: String,
+    /// number of the mint run for its mint run ID
+    pub run_number: u32,
+}
+
+/// a mint run in the mint run registry
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct MintRun {
+    /// position of the mint run in the registry, used to paginate
+    pub index: u32,
+    /// mint run ID given when minting
+    pub run_id: String,
+    /// number of this mint run for its mint run ID
+    pub run_number: u32,
+    /// address that minted the run
+    pub creator: HumanAddr,
+    /// number of tokens in the mint run
+    pub quantity: u32,
+    /// number of tokens of the mint run that have been minted so far
+    pub minted: u32,
+    /// id of the first token minted in the run
+    pub first_token: Option<String>,
+    /// id of the last token minted in the run so far
+    pub last_token: Option<String>,
+    /// time the mint run was started (in seconds since 01/01/1970)
+    pub time_of_minting: u64,
+}
+
+/// stored mint run registry entry
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct StoredMintRun {
+    /// mint run ID given when minting
+    pub run_id: String,
+    /// number of this mint run for its mint run ID
+    pub run_number: u32,
+    /// address that minted the run
+    pub creator: CanonicalAddr,
+    /// number of tokens in the mint run
+    pub quantity: u32,
+    /// number of tokens of the mint run that have been minted so far
+    pub minted: u32,
+    /// id of the first token minted in the run
+    pub first_token: Option<String>,
+    /// id of the last token minted in the run so far
+    pub last_token: Option<String>,
+    /// time the mint run was started (in seconds since 01/01/1970)
+    pub time_of_minting: u64,
+}
+
+impl StoredMintRun {
+    /// Returns StdResult<MintRun> from creating a MintRun from a StoredMintRun
+    ///
+    /// # Arguments
+    ///
+    /// * `api` - a reference to the Api used to convert human and canonical addresses
+    /// * `index` - position of the mint run in the registry
+    pub fn to_human<A: Api>(&self, api: &A, index: u32) -> StdResult<MintRun> {
+        Ok(MintRun {
+            index,
+            run_id: self.run_id.clone(),
+            run_number: self.run_number,
+            creator: api.human_address(&self.creator)?,
+            quantity: self.quantity,
+            minted: self.minted,
+            first_token: self.first_token.clone(),
+            last_token: self.last_token.clone(),
+            time_of_minting: self.time_of_minting,
+        })
+    }
+}
+
+/// what an edition changes in the public metadata shared by its mint run
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
+pub struct EditionOverride {
+    /// optional name that replaces the shared name
+    pub name: Option<String>,
+    /// optional traits unique to this edition, added to the shared attributes
+    pub attributes: Option<Vec<Trait>>,
+}
+
+/// stored link from a token to the shared metadata of its editions
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct StoredEdition {
+    /// index of the first token of the editions, which keys their shared metadata
+    pub shared: u32,
+    /// edition number of this token
+    pub edition: u32,
+    /// number of editions minted with the shared metadata
+    pub editions: u32,
+    /// optional name that replaces the shared name
+    pub name: Option<String>,
+    /// optional traits unique to this edition
+    pub attributes: Option<Vec<Trait>>,
+}
+
+impl StoredEdition {
+    /// Returns Metadata from applying this edition's override to the shared metadata.  Overrides
+    /// only apply to on-chain metadata, which also gets an edition number trait
+    ///
+    /// # Arguments
+    ///
+    /// * `shared` - the metadata shared by the editions
+    pub fn merge(&self, mut shared: Metadata) -> Metadata {
+        if let Some(ext) = shared.extension.as_mut() {
+            if let Some(name) = self.name.as_ref() {
+                ext.name = Some(name.clone());
+            }
+            let attributes = ext.attributes.get_or_insert_with(Vec::new);
+            attributes.push(Trait {
+                display_type: Some("number".to_string()),
+                trait_type: Some("edition".to_string()),
+                value: self.edition.to_string(),
+                max_value: Some(self.editions.to_string()),
+            });
+            if let Some(unique) = self.attributes.as_ref() {
+                attributes.extend(unique.iter().cloned());
+            }
+        }
+        shared
+    }
+}
@@ -0,0 +1,124 @@
+use cosmwasm_std::{Api, BlockInfo, CanonicalAddr, HumanAddr, StdError, StdResult};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::expiration::Expiration;
+
+/// a minter's authority and quotas
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct MinterInfo {
+    /// address of the minter
+    pub minter: HumanAddr,
+    /// true if the minter may update metadata when minters are allowed to update metadata
+    pub may_update_metadata: bool,
+    /// optional maximum number of tokens the minter may mint
+    pub max_tokens: Option<u32>,
+    /// number of tokens minted since the minter's role was set
+    pub minted: u32,
+    /// optional list of the only mint run IDs the minter may mint with
+    pub allowed_mint_run_ids: Option<Vec<String>>,
+    /// optional time the minter's authority expires
+    pub expiration: Option<Expiration>,
+}
+
+/// stored minter role
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct StoredMinterRole {
+    /// true if the minter may update metadata
+    pub may_update_metadata: bool,
+    /// optional maximum number of tokens the minter may mint
+    pub max_tokens: Option<u32>,
+    /// number of tokens minted since the role was set
+    pub minted: u32,
+    /// optional list of the only mint run IDs the minter may mint with
+    pub allowed_mint_run_ids: Option<Vec<String>>,
+    /// optional time the minter's authority expires
+    pub expiration: Option<Expiration>,
+}
+
+/// a minter without a stored role has unlimited minting authority
+impl Default for StoredMinterRole {
+    fn default() -> Self {
+        StoredMinterRole {
+            may_update_metadata: true,
+            max_tokens: None,
+            minted: 0,
+            allowed_mint_run_ids: None,
+            expiration: None,
+        }
+    }
+}
+
+impl StoredMinterRole {
+    /// Returns StdResult<MinterInfo> from creating a displayable MinterInfo from a
+    /// StoredMinterRole
+    ///
+    /// # Arguments
+    ///
+    /// * `api` - a reference to the Api used to convert human and canonical addresses
+    /// * `minter` - a reference to the minter's address
+    pub fn to_display<A: Api>(&self, api: &A, minter: &CanonicalAddr) -> StdResult<MinterInfo> {
+        Ok(MinterInfo {
+            minter: api.human_address(minter)?,
+            may_update_metadata: self.may_update_metadata,
+            max_tokens: self.max_tokens,
+            minted: self.minted,
+            allowed_mint_run_ids: self.allowed_mint_run_ids.clone(),
+            expiration: self.expiration,
+        })
+    }
+
+    /// Returns bool, true if the minter's authority has expired
+    ///
+    /// # Arguments
+    ///
+    /// * `block` - a reference to the current BlockInfo
+    pub fn is_expired(&self, block: &BlockInfo) -> bool {
+        self.expiration.is_some_and(|e| e.is_expired(block))
+    }
+
+    /// Returns StdResult<()> after recording the mint of tokens if the role permits it
+    ///
+    /// # Arguments
+    ///
+    /// * `block` - a reference to the current BlockInfo
+    /// * `quantity` - number of tokens being minted
+    /// * `mint_run_id` - optional mint run ID the tokens are minted with
+    pub fn use_quota(
+        &mut self,
+        block: &BlockInfo,
+        quantity: u32,
+        mint_run_id: Option<&String>,
+    ) -> StdResult<()> {
+        if self.is_expired(block) {
+            return Err(StdError::generic_err("The minter's authority has expired"));
+        }
+        if let Some(allowed) = self.allowed_mint_run_ids.as_ref() {
+            match mint_run_id {
+                Some(id) if allowed.contains(id) => (),
+                Some(id) => {
+                    return Err(StdError::generic_err(format!(
+                        "The minter is not allowed to mint with mint run ID {}",
+                        id
+                    )))
+                }
+                None => {
+                    return Err(StdError::generic_err(
+                        "The minter may only mint with its allowed mint run IDs",
+                    ))
+                }
+            }
+        }
+        let minted = self.minted.saturating_add(quantity);
+        if let Some(max) = self.max_tokens {
+            if minted > max {
+                return Err(StdError::generic_err(format!(
+                    "The minter may only mint {} more tokens",
+                    max.saturating_sub(self.minted)
+                )));
+            }
+        }
+        self.minted = minted;
+        Ok(())
+    }
+}
@@ -4,13 +4,18 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Binary, Coin, HumanAddr};
+use cosmwasm_std::{Binary, Coin, HumanAddr, Uint128};
 use secret_toolkit::permit::Permit;
 
+use crate::composable::ChildToken;
 use crate::expiration::Expiration;
-use crate::mint_run::{MintRunInfo, SerialNumber};
-use crate::royalties::{DisplayRoyaltyInfo, RoyaltyInfo};
-use crate::token::{Extension, Metadata};
+use crate::launchpad::{
+    LazyListing, MintPhase, MintTemplate, MintVoucher, PublicMintInfo, RevealCommitment,
+};
+use crate::mint_run::{EditionOverride, MintRun, MintRunInfo, MintRunRef, SerialNumber};
+use crate::minters::MinterInfo;
+use crate::royalties::{DisplayRoyaltyInfo, RecipientRoyalty, RoyaltyInfo};
+use crate::token::{Extension, Metadata, Trait};
 
 /// Instantiation message
 #[derive(Serialize, Deserialize, JsonSchema)]
@@ -72,6 +77,13 @@ pub struct InitConfig {
     /// Indicates whether burn functionality should be enabled
     /// default: False
     pub enable_burn: Option<bool>,
+    /// the maximum sum of a token's royalty rates, in basis points
+    /// default: 10000
+    pub max_royalty_bps: Option<u16>,
+    /// indicates whether the sale stats of the collection are public.  If they are private,
+    /// only minters can view them
+    /// default: True
+    pub public_sale_stats: Option<bool>,
 }
 
 impl Default for InitConfig {
@@ -84,6 +96,8 @@ impl Default for InitConfig {
             minter_may_update_metadata: Some(true),
             owner_may_update_metadata: Some(false),
             enable_burn: Some(false),
+            max_royalty_bps: Some(10000),
+            public_sale_stats: Some(true),
         }
     }
 }
@@ -106,7 +120,131 @@ pub struct PostInitCallback {
 pub enum HandleMsg {
     /// withdraw funds from contract
     Withdraw { receiver_addr: HumanAddr },
-    /// buy a token
+    /// claim the sale proceeds and royalties that have been credited to the message sender
+    ClaimProceeds {
+        /// denomination of the proceeds to claim
+        denom: String,
+        /// optional message length padding
+        padding: Option<String>,
+    },
+    /// configure the public mint.  Only the admin may do this
+    SetPublicMint {
+        /// true if the public mint should be open
+        enabled: bool,
+        /// price of each token in uscrt
+        price: Uint128,
+        /// maximum number of tokens that can be minted through the public mint
+        max_supply: u32,
+        /// optional maximum number of tokens a single address can mint
+        per_wallet_limit: Option<u32>,
+        /// optionally set whether each mint draws a random unused template instead of the
+        /// next one uploaded.  If omitted, the current setting is kept
+        random_assignment: Option<bool>,
+        /// optional message length padding
+        padding: Option<String>,
+    },
+    /// upload metadata for tokens minted through the public mint.  Unless random assignment is
+    /// enabled, templates are used in the order they were uploaded.  Only the admin may do this
+    AddMintTemplates {
+        /// the metadata templates to add
+        templates: Vec<MintTemplate>,
+        /// optional message length padding
+        padding: Option<String>,
+    },
+    /// replace the phases of the public mint.  Minting is only allowed while a phase is active,
+    /// and the first active phase in the list sets the price, per wallet limit, and allowlist.
+    /// Phase names must be unique, and each address's mint count for a phase is kept by name, so
+    /// it carries over when the phases are reordered or replaced.  An empty list removes all
+    /// phases.  Only the admin may do this
+    SetMintPhases {
+        /// the mint phases
+        phases: Vec<MintPhase>,
+        /// optional message length padding
+        padding: Option<String>,
+    },
+    /// commit to the metadata of the collection before it is minted, so that it can later be
+    /// revealed with RevealCollection.  Once committed, tokens can not be revealed individually.
+    /// Only the admin may do this, only once, and only before the first token is minted
+    CommitReveal {
+        /// sha256 hash of the full metadata set
+        metadata_hash: Binary,
+        /// sha256 hash of the seed that will be published when the collection is revealed
+        seed_hash: Binary,
+        /// number of tokens in the collection
+        supply: u32,
+        /// optional message length padding
+        padding: Option<String>,
+    },
+    /// reveal the collection in batches.  The first call publishes the seed committed to in
+    /// CommitReveal, and can only be made once the whole collection has been minted.  Each call
+    /// reveals up to `limit` more tokens.  Only the admin may do this
+    RevealCollection {
+        /// the seed.  Required on the first call, and ignored afterwards
+        seed: Option<Binary>,
+        /// optional max number of tokens to reveal in this batch.  Defaults to 100
+        limit: Option<u32>,
+        /// optional message length padding
+        padding: Option<String>,
+    },
+    /// mint tokens through the public mint by sending their price
+    PublicMint {
+        /// number of tokens to mint
+        quantity: u32,
+        /// proof that the sender is on the allowlist of the active phase.  These are the sibling
+        /// hashes on the path from the sender's leaf to the Merkle root
+        merkle_proof: Option<Vec<Binary>>,
+        /// optional entropy String used when templates are randomly assigned
+        entropy: Option<String>,
+        /// optional message length padding
+        padding: Option<String>,
+    },
+    /// list editions that are only minted when they are bought.  Buying a lazy listing is done
+    /// with BuyToken using the listing id.  Only minters may do this
+    CreateLazyListing {
+        /// id used to buy the listing's editions.  It can not be the same as any token id
+        listing_id: String,
+        /// optional mint run ID
+        mint_run_id: Option<String>,
+        /// price of each edition in uscrt
+        price: u32,
+        /// number of editions that can be sold
+        editions: u32,
+        /// optional public metadata of the editions
+        public_metadata: Option<Metadata>,
+        /// optional private metadata of the editions
+        private_metadata: Option<Metadata>,
+        /// optional royalty information of the editions
+        royalty_info: Option<RoyaltyInfo>,
+        /// optional message length padding
+        padding: Option<String>,
+    },
+    /// stop selling the remaining editions of a lazy listing.  Only the listing's creator or
+    /// the admin may do this
+    CancelLazyListing {
+        /// id of the lazy listing
+        listing_id: String,
+        /// optional message length padding
+        padding: Option<String>,
+    },
+    /// register the secp256k1 public key that signs the sender's mint vouchers, or remove it
+    /// if no key is given.  Only minters may do this
+    SetVoucherSigner {
+        /// optional 33 byte compressed secp256k1 public key
+        pubkey: Option<Binary>,
+        /// optional message length padding
+        padding: Option<String>,
+    },
+    /// mint the token described by a voucher signed by its creator's registered key, paying
+    /// the voucher's price
+    RedeemMintVoucher {
+        /// the signed voucher
+        voucher: MintVoucher,
+        /// 64 byte secp256k1 signature of the sha256 hash of the voucher's sign-doc
+        signature: Binary,
+        /// optional message length padding
+        padding: Option<String>,
+    },
+    /// buy a token, or the next edition of a lazy listing
     BuyToken {
         /// token id of the token
         token_id: String,
@@ -127,6 +265,29 @@ pub enum HandleMsg {
         /// price of token
         price: Option<u32>,
     },
+    /// list several tokens to be sold together for a single price.  The tokens can not be
+    /// transferred or sold individually until the bundle is bought or cancelled.  Each token's
+    /// royalties are paid on its share of the price, which is its entry in `token_prices`, or
+    /// an even split of the price if no token prices are given
+    ListBundle {
+        /// ids of the tokens in the bundle
+        token_ids: Vec<String>,
+        /// price of the whole bundle in uscrt
+        price: u32,
+        /// optional price of each token in uscrt, in the order of `token_ids`.  They must add
+        /// up to the price of the bundle
+        token_prices: Option<Vec<u32>>,
+    },
+    /// buy all the tokens of a bundle
+    BuyBundle {
+        /// id of the bundle
+        bundle_id: u32,
+    },
+    /// take a bundle off the market and unlock its tokens
+    CancelBundle {
+        /// id of the bundle
+        bundle_id: u32,
+    },
     /// mint new token
     MintNft {
         /// optional token id. if omitted, use current token index
@@ -180,6 +341,12 @@ pub enum HandleMsg {
         royalty_info: Option<RoyaltyInfo>,
         /// optional memo for the mint txs
         memo: Option<String>,
+        /// optional listing that puts every clone up for sale as soon as it is minted
+        listing: Option<MintListing>,
+        /// optional edition overrides.  If given, the public metadata is stored once for all the
+        /// clones, and each clone only stores its edition number and override.  Either give
+        /// one override per clone, or an empty list if the clones have no overrides
+        editions: Option<Vec<EditionOverride>>,
         /// optional message length padding
         padding: Option<String>,
     },
@@ -195,6 +362,55 @@ pub enum HandleMsg {
         /// optional message length padding
         padding: Option<String>,
     },
+    /// permanently prevent the metadata of a token from being updated.  This can be called by
+    /// anyone who may update the token's metadata
+    FreezeMetadata {
+        /// id of the token whose metadata should be frozen
+        token_id: String,
+        /// optional message length padding
+        padding: Option<String>,
+    },
+    /// set or remove a token's unlockable content, which is only viewable by the token's owner
+    /// through the PrivateMetadata query.  Only the owner may do this, and not while the token
+    /// is listed for sale
+    SetUnlockable {
+        /// id of the token whose unlockable content should be set
+        token_id: String,
+        /// the optional unlockable content.  If not specified, the content is removed
+        unlockable: Option<String>,
+        /// optional message length padding
+        padding: Option<String>,
+    },
+    /// permanently prevent the metadata of every token from being updated.  Only the admin may
+    /// do this
+    FreezeAllMetadata {
+        /// optional message length padding
+        padding: Option<String>,
+    },
+    /// allow an address to update the given trait types of every token with UpdateAttributes,
+    /// without granting it any other authority over metadata.  An empty list of trait types
+    /// removes the updater.  Only the admin may do this
+    SetAttributeUpdater {
+        /// address of the updater
+        updater: HumanAddr,
+        /// the trait types it may update
+        trait_types: Vec<String>,
+        /// optional message length padding
+        padding: Option<String>,
+    },
+    /// add or replace attributes of a token.  Each attribute replaces the existing attribute
+    /// with the same trait_type, or is appended if the token does not have one.  Only attribute
+    /// updaters may do this, and only for the trait types they were allowed
+    UpdateAttributes {
+        /// id of the token whose attributes should be updated
+        token_id: String,
+        /// optional attributes to update in the public metadata
+        public_attributes: Option<Vec<Trait>>,
+        /// optional attributes to update in the private metadata
+        private_attributes: Option<Vec<Trait>>,
+        /// optional message length padding
+        padding: Option<String>,
+    },
     /// set royalty information.  If no token ID is provided, this royalty info will become the default
     /// RoyaltyInfo for any new tokens minted on the contract.  If a token ID is provided, this can only
     /// be called by the token creator and only when the creator is the current owner.  Royalties can not
@@ -359,6 +575,81 @@ pub enum HandleMsg {
         /// optional message length padding
         padding: Option<String>,
     },
+    /// revoke a non-transferable token by burning it.  Only the token's original minter may do
+    /// this, regardless of whether burn has been enabled on the contract
+    RevokeNonTransferable {
+        /// token to revoke
+        token_id: String,
+        /// optional memo for the tx
+        memo: Option<String>,
+        /// optional message length padding
+        padding: Option<String>,
+    },
+    /// move a non-transferable token to a new owner, such as when its owner has lost access to
+    /// their wallet.  Only the token's original minter may do this
+    ReissueNonTransferable {
+        /// token to reissue
+        token_id: String,
+        /// the token's new owner
+        new_owner: HumanAddr,
+        /// optional memo for the tx
+        memo: Option<String>,
+        /// optional message length padding
+        padding: Option<String>,
+    },
+    /// register a SNIP-721 contract whose tokens can be attached to this contract's tokens,
+    /// and register this contract's BatchReceiveNft with it.  Only the admin may do this
+    RegisterChildContract {
+        /// address of the child contract
+        contract: HumanAddr,
+        /// code hash of the child contract
+        code_hash: String,
+        /// optional message length padding
+        padding: Option<String>,
+    },
+    /// attach a token of this contract to a parent token, escrowing the child in this
+    /// contract.  The sender must own both tokens.  Tokens of registered child contracts are
+    /// attached by sending them to this contract with an AttachChildMsg naming the parent
+    AttachChild {
+        /// id of the parent token
+        parent_token_id: String,
+        /// id of the child token
+        child_token_id: String,
+        /// optional message length padding
+        padding: Option<String>,
+    },
+    /// detach a child from a parent token and give it to the parent's owner.  Only the
+    /// parent's owner may do this
+    DetachChild {
+        /// id of the parent token
+        parent_token_id: String,
+        /// the child to detach
+        child: ChildToken,
+        /// optional message length padding
+        padding: Option<String>,
+    },
+    /// called by a registered child contract when its tokens are sent to this contract.  The
+    /// msg must be an AttachChildMsg naming a parent token owned by the tokens' previous owner
+    BatchReceiveNft {
+        /// address that sent the tokens
+        sender: HumanAddr,
+        /// previous owner of the sent tokens
+        from: HumanAddr,
+        /// tokens that were sent
+        token_ids: Vec<String>,
+        /// AttachChildMsg naming the parent token
+        msg: Option<Binary>,
+    },
+    /// called by a registered child contract that does not implement BatchReceiveNft when
+    /// one of its tokens is sent to this contract
+    ReceiveNft {
+        /// previous owner of the sent token
+        sender: HumanAddr,
+        /// token that was sent
+        token_id: String,
+        /// AttachChildMsg naming the parent token
+        msg: Option<Binary>,
+    },
     /// register that the message sending contract implements ReceiveNft and possibly
     /// BatchReceiveNft.  If a contract implements BatchReceiveNft, SendNft will always
     /// call BatchReceiveNft even if there is only one token transferred (the token_ids
@@ -407,6 +698,23 @@ pub enum HandleMsg {
         /// optional message length padding
         padding: Option<String>,
     },
+    /// set the authority and quotas of a minter.  A minter without a role may mint without
+    /// limits and may update metadata if minters are allowed to
+    SetMinterRole {
+        /// address of the minter
+        minter: HumanAddr,
+        /// optionally true if the minter may update metadata when minters are allowed to.
+        /// Defaults to true
+        may_update_metadata: Option<bool>,
+        /// optional maximum number of tokens the minter may mint from now on
+        max_tokens: Option<u32>,
+        /// optional list of the only mint run IDs the minter may mint with
+        allowed_mint_run_ids: Option<Vec<String>>,
+        /// optional time the minter's authority expires
+        expiration: Option<Expiration>,
+        /// optional message length padding
+        padding: Option<String>,
+    },
     /// change address with administrative power
     ChangeAdmin {
         /// address with admin authority
@@ -422,6 +730,14 @@ pub enum HandleMsg {
         /// optional message length padding
         padding: Option<String>,
     },
+    /// set the maximum sum of royalty rates allowed on any royalty info set from now on.  Only
+    /// the admin may change this
+    SetMaxRoyalty {
+        /// the new maximum, in basis points
+        max_royalty_bps: u16,
+        /// optional message length padding
+        padding: Option<String>,
+    },
     /// disallow the use of a permit
     RevokePermit {
         /// name of the permit that is no longer valid
@@ -465,6 +781,19 @@ pub struct Mint {
     pub transferable: Option<bool>,
     /// optional memo for the tx
     pub memo: Option<String>,
+    /// optional listing that puts the token up for sale as soon as it is minted
+    pub listing: Option<MintListing>,
+}
+
+/// sale listing created when a token is minted
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub struct MintListing {
+    /// price of the token
+    pub price: u32,
+    /// optional denomination of the price.  Defaults to uscrt
+    pub denom: Option<String>,
+    /// optional expiration of the listing
+    pub expiration: Option<Expiration>,
 }
 
 /// token burn info used when doing a BatchBurnNft
@@ -511,6 +840,23 @@ pub struct TokenSaleInfo {
     pub sale_status: SaleStatus,
     /// price of token
     pub token_price: Option<u32>,
+    /// optional denomination of the price.  Defaults to uscrt
+    pub denom: Option<String>,
+    /// optional expiration of the listing
+    pub expiration: Option<Expiration>,
+}
+
+/// several tokens listed to be sold together
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Bundle {
+    /// id used to buy the bundle
+    pub bundle_id: u32,
+    /// ids of the tokens in the bundle
+    pub token_ids: Vec<String>,
+    /// price of the whole bundle in uscrt
+    pub price: u32,
+    /// optional price of each token in uscrt, in the order of `token_ids`
+    pub token_prices: Option<Vec<u32>>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Copy, PartialEq, JsonSchema, Debug)]
@@ -536,6 +882,45 @@ pub enum HandleAnswer {
     WithdrawFunds {
         status: ResponseStatus,
     },
+    /// the proceeds sent to the claimer
+    ClaimProceeds {
+        amount: Coin,
+    },
+    SetPublicMint {
+        status: ResponseStatus,
+    },
+    AddMintTemplates {
+        /// number of uploaded templates that have not been used yet
+        templates_remaining: u32,
+    },
+    SetMintPhases {
+        status: ResponseStatus,
+    },
+    CommitReveal {
+        status: ResponseStatus,
+    },
+    RevealCollection {
+        /// number of tokens revealed so far
+        revealed: u32,
+        /// number of tokens that have not been revealed yet
+        remaining: u32,
+    },
+    /// the ids of the tokens minted
+    PublicMint {
+        token_ids: Vec<String>,
+    },
+    CreateLazyListing {
+        listing_id: String,
+    },
+    CancelLazyListing {
+        listing_id: String,
+    },
+    SetVoucherSigner {
+        status: ResponseStatus,
+    },
+    RedeemMintVoucher {
+        token_id: String,
+    },
     /// buy a token that is for sale
     BuyToken {
         token_id: String,
@@ -569,6 +954,21 @@ pub enum HandleAnswer {
     SetMetadata {
         status: ResponseStatus,
     },
+    FreezeMetadata {
+        status: ResponseStatus,
+    },
+    FreezeAllMetadata {
+        status: ResponseStatus,
+    },
+    SetUnlockable {
+        status: ResponseStatus,
+    },
+    SetAttributeUpdater {
+        status: ResponseStatus,
+    },
+    UpdateAttributes {
+        status: ResponseStatus,
+    },
     SetRoyaltyInfo {
         status: ResponseStatus,
     },
@@ -614,6 +1014,27 @@ pub enum HandleAnswer {
     BatchBurnNft {
         status: ResponseStatus,
     },
+    RevokeNonTransferable {
+        status: ResponseStatus,
+    },
+    ReissueNonTransferable {
+        status: ResponseStatus,
+    },
+    RegisterChildContract {
+        status: ResponseStatus,
+    },
+    AttachChild {
+        status: ResponseStatus,
+    },
+    DetachChild {
+        status: ResponseStatus,
+    },
+    BatchReceiveNft {
+        status: ResponseStatus,
+    },
+    ReceiveNft {
+        status: ResponseStatus,
+    },
     RegisterReceiveNft {
         status: ResponseStatus,
     },
@@ -630,12 +1051,18 @@ pub enum HandleAnswer {
     SetMinters {
         status: ResponseStatus,
     },
+    SetMinterRole {
+        status: ResponseStatus,
+    },
     ChangeAdmin {
         status: ResponseStatus,
     },
     SetContractStatus {
         status: ResponseStatus,
     },
+    SetMaxRoyalty {
+        status: ResponseStatus,
+    },
     RevokePermit {
         status: ResponseStatus,
     },
@@ -643,6 +1070,17 @@ pub enum HandleAnswer {
         token_id: String,
         sale_status: SaleStatus,
     },
+    ListBundle {
+        bundle_id: u32,
+    },
+    /// buy all the tokens of a bundle
+    BuyBundle {
+        bundle_id: u32,
+        price: Vec<Coin>,
+    },
+    CancelBundle {
+        bundle_id: u32,
+    },
 }
 
 /// the address and viewing key making an authenticated query request
@@ -691,6 +1129,27 @@ pub enum TxAction {
         /// burner's address if not owner
         burner: Option<HumanAddr>,
     },
+    /// changed a token's royalties
+    SetRoyalties {
+        /// address that changed the royalties
+        changer: HumanAddr,
+    },
+    /// revoked a non-transferable token
+    Revoke {
+        /// previous owner
+        owner: HumanAddr,
+        /// the token's original minter who revoked it
+        issuer: HumanAddr,
+    },
+    /// reissued a non-transferable token to a new owner
+    Reissue {
+        /// previous owner
+        from: HumanAddr,
+        /// the token's original minter who reissued it
+        issuer: HumanAddr,
+        /// new owner
+        recipient: HumanAddr,
+    },
 }
 
 /// tx for display
@@ -711,6 +1170,43 @@ pub struct Tx {
     pub memo: Option<String>,
 }
 
+/// an address allowed to update specific attributes of every token
+#[derive(Serialize, Deserialize, JsonSchema, Clone, PartialEq, Debug)]
+pub struct AttributeUpdater {
+    /// address of the updater, typically a game contract
+    pub updater: HumanAddr,
+    /// the trait types it may update
+    pub trait_types: Vec<String>,
+}
+
+/// number of tokens with a trait value
+#[derive(Serialize, Deserialize, JsonSchema, Clone, PartialEq, Debug)]
+pub struct TraitCount {
+    /// trait type
+    pub trait_type: String,
+    /// trait value
+    pub value: String,
+    /// number of tokens with this trait value
+    pub count: u32,
+}
+
+/// revision of a token's metadata for display
+#[derive(Serialize, Deserialize, JsonSchema, Clone, PartialEq, Debug)]
+pub struct MetadataRevision {
+    /// address that changed the metadata
+    pub changer: HumanAddr,
+    /// the block containing the change
+    pub block_height: u64,
+    /// the time (in seconds since 01/01/1970) of the block containing the change
+    pub block_time: u64,
+    /// true if the private metadata was changed
+    pub private: bool,
+    /// sha256 hash of the JSON of the metadata before the change, if it had any
+    pub old_hash: Option<Binary>,
+    /// sha256 hash of the JSON of the new metadata
+    pub new_hash: Binary,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
@@ -718,18 +1214,131 @@ pub enum QueryMsg {
     TokensForSale {},
     /// displays the entire sale info of the specified token
     SaleInfo { token_id: String },
+    /// displays the sale proceeds and royalties that the specified address has not claimed yet
+    PendingProceeds {
+        address: HumanAddr,
+        /// viewing key
+        viewing_key: String,
+    },
+    /// reconciles the contract's balances against the funds it owes.  Only the admin may
+    /// perform this query
+    Accounting {
+        address: HumanAddr,
+        /// viewing key
+        viewing_key: String,
+    },
+    /// displays the public mint settings
+    PublicMintInfo {},
+    /// displays the commitment made for revealing the collection, if any
+    RevealCommitment {},
+    /// displays the lazy listings that have editions left
+    LazyListings {},
+    /// displays the registered mint runs, optionally only those of one creator.  The token
+    /// supply must be public, or the querier must be an authenticated minter
+    MintRuns {
+        /// optional creator whose mint runs should be displayed
+        creator: Option<HumanAddr>,
+        /// optional address and key requesting to view the mint runs
+        viewer: Option<ViewerInfo>,
+        /// paginate by providing the index of the last mint run received in the previous query
+        start_after: Option<u32>,
+        /// optional number of mint runs to display
+        limit: Option<u32>,
+    },
+    /// displays the token ids of a registered mint run.  The token supply must be public, or
+    /// the querier must be an authenticated minter
+    TokensInMintRun {
+        /// mint run ID given when minting
+        run_id: String,
+        /// number of the mint run for its mint run ID
+        run_number: u32,
+        /// optional address and key requesting to view the tokens
+        viewer: Option<ViewerInfo>,
+        /// paginate by providing the last_position received in the previous query
+        start_after: Option<u32>,
+        /// optional number of token ids to display
+        limit: Option<u32>,
+    },
+    /// displays the revisions of a token's metadata, newest first.  Revisions of the private
+    /// metadata are only displayed to those permitted to view the private metadata
+    MetadataHistory {
+        token_id: String,
+        /// optional address and key requesting to view the private metadata revisions
+        viewer: Option<ViewerInfo>,
+        /// optional page to display
+        page: Option<u32>,
+        /// optional number of revisions per page
+        page_size: Option<u32>,
+    },
+    /// displays the tokens held by a parent token.  The querier must be permitted to view the
+    /// parent's owner
+    Children {
+        token_id: String,
+        /// optional address and key requesting to view the children
+        viewer: Option<ViewerInfo>,
+    },
+    /// displays the sale stats of the collection, and optionally of one of its mint runs.  The
+    /// sale stats must either be public, or the querier must be an authenticated minter
+    CollectionStats {
+        /// optional registered mint run
+        mint_run: Option<MintRunRef>,
+        /// optional address and key requesting to view the sale stats
+        viewer: Option<ViewerInfo>,
+    },
+    /// displays the tokens that pay royalties to the specified address, along with its rate
+    /// and the royalties it has received from each of them
+    MyRoyalties {
+        address: HumanAddr,
+        /// viewing key
+        viewing_key: String,
+        /// optional page to display
+        page: Option<u32>,
+        /// optional number of tokens per page
+        page_size: Option<u32>,
+    },
     /// display the contract's name and symbol
     ContractInfo {},
     /// display the contract's configuration
     ContractConfig {},
     /// display the list of authorized minters
     Minters {},
+    /// display the addresses allowed to update attributes and their trait types
+    AttributeUpdaters {},
     /// display the number of tokens controlled by the contract.  The token supply must
     /// either be public, or the querier must be an authenticated minter
     NumTokens {
         /// optional address and key requesting to view the number of tokens
         viewer: Option<ViewerInfo>,
     },
+    /// display the number of tokens with each public trait value.  The token supply must
+    /// either be public, or the querier must be an authenticated minter
+    TraitCounts {
+        /// optional address and key requesting to view the trait counts
+        viewer: Option<ViewerInfo>,
+    },
+    /// display the number of tokens sharing each of a token's public trait values, and its
+    /// rarity score.  The token supply must either be public, or the querier must be an
+    /// authenticated minter
+    TokenRarity {
+        token_id: String,
+        /// optional address and key requesting to view the token's rarity
+        viewer: Option<ViewerInfo>,
+    },
+    /// display an optionally paginated list of the tokens with a public trait value, in mint
+    /// order.  The token supply must either be public, or the querier must be an
+    /// authenticated minter
+    TokensByTrait {
+        /// trait type to search
+        trait_type: String,
+        /// trait value to search for
+        value: String,
+        /// optional address and key requesting to view the list of tokens
+        viewer: Option<ViewerInfo>,
+        /// paginate by providing the last token_id received in the previous query
+        start_after: Option<String>,
+        /// optional number of token ids to display
+        limit: Option<u32>,
+    },
     /// display an optionally paginated list of all the tokens controlled by the contract.
     /// The token supply must either be public, or the querier must be an authenticated
     /// minter
@@ -936,6 +1545,37 @@ pub struct Cw721OwnerOfResponse {
     pub approvals: Vec<Cw721Approval>,
 }
 
+/// sales made through the marketplace.  All amounts are in uscrt
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SaleStats {
+    /// total amount of all sales
+    pub volume: Uint128,
+    /// number of sales
+    pub sale_count: u64,
+    /// price of the most recent sale
+    pub last_sale_price: Option<Uint128>,
+    /// price of the highest sale
+    pub highest_sale: Option<Uint128>,
+    /// lowest price among the tokens currently for sale
+    pub floor_price: Option<Uint128>,
+}
+
+/// the contract's balance of a single denomination reconciled against its liabilities
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DenomAccounting {
+    pub denom: String,
+    /// the contract's current balance
+    pub balance: Uint128,
+    /// funds held on behalf of others, such as unclaimed proceeds
+    pub escrowed: Uint128,
+    /// marketplace fees that may be withdrawn by the admin
+    pub earned_fees: Uint128,
+    /// amount of the balance that is not accounted for by escrow or earned fees
+    pub surplus: Uint128,
+    /// amount by which escrow and earned fees exceed the balance
+    pub shortfall: Uint128,
+}
+
 /// the token id and nft dossier info of a single token response in a batch query
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct BatchNftDossierElement {
@@ -956,6 +1596,8 @@ pub struct BatchNftDossierElement {
     pub private_metadata_is_public_expiration: Option<Expiration>,
     pub token_approvals: Option<Vec<Snip721Approval>>,
     pub inventory_approvals: Option<Vec<Snip721Approval>>,
+    /// true if the token's metadata can no longer be updated
+    pub frozen: bool,
 }
 
 #[derive(Serialize, Deserialize, JsonSchema, Debug)]
@@ -963,9 +1605,55 @@ pub struct BatchNftDossierElement {
 pub enum QueryAnswer {
     TokensForSale {
         for_sale: Vec<String>,
+        /// bundles of tokens for sale at a single price
+        bundles: Vec<Bundle>,
     },
     SaleInfo {
         sale_store: TokenSaleInfo,
+        /// true if the token has unlockable content that is revealed to its buyer
+        has_unlockable: bool,
+    },
+    PendingProceeds {
+        balances: Vec<Coin>,
+    },
+    Accounting {
+        accounts: Vec<DenomAccounting>,
+    },
+    PublicMintInfo {
+        info: PublicMintInfo,
+    },
+    RevealCommitment {
+        commitment: Option<RevealCommitment>,
+    },
+    LazyListings {
+        listings: Vec<LazyListing>,
+    },
+    MintRuns {
+        mint_runs: Vec<MintRun>,
+    },
+    TokensInMintRun {
+        token_ids: Vec<String>,
+        /// position in the mint run of the last token displayed, if any
+        last_position: Option<u32>,
+    },
+    MetadataHistory {
+        /// total number of revisions the querier may view
+        total: u64,
+        revisions: Vec<MetadataRevision>,
+    },
+    Children {
+        children: Vec<ChildToken>,
+    },
+    CollectionStats {
+        collection: SaleStats,
+        mint_run: Option<SaleStats>,
+    },
+    MyRoyalties {
+        /// total number of tokens that pay royalties to the address
+        count: u32,
+        royalties: Vec<RecipientRoyalty>,
+        /// lifetime royalties received from all tokens, including ones no longer listed
+        total_received: Vec<Coin>,
     },
     ContractInfo {
         name: String,
@@ -979,13 +1667,30 @@ pub enum QueryAnswer {
         minter_may_update_metadata: bool,
         owner_may_update_metadata: bool,
         burn_is_enabled: bool,
+        max_royalty_bps: u16,
+        sale_stats_are_public: bool,
     },
     Minters {
         minters: Vec<HumanAddr>,
+        roles: Vec<MinterInfo>,
+    },
+    AttributeUpdaters {
+        updaters: Vec<AttributeUpdater>,
     },
     NumTokens {
         count: u32,
     },
+    TraitCounts {
+        /// number of tokens in the collection
+        total_tokens: u32,
+        traits: Vec<TraitCount>,
+    },
+    TokenRarity {
+        token_id: String,
+        /// sum of total_tokens / count for each of the token's traits, in thousandths
+        rarity_score: u64,
+        traits: Vec<TraitCount>,
+    },
     TokenList {
         tokens: Vec<String>,
     },
@@ -1010,10 +1715,14 @@ pub enum QueryAnswer {
     NftInfo {
         token_uri: Option<String>,
         extension: Option<Extension>,
+        /// true if the token's metadata can no longer be updated
+        frozen: bool,
     },
     PrivateMetadata {
         token_uri: Option<String>,
         extension: Option<Extension>,
+        /// the token's unlockable content, only displayed to the owner
+        unlockable: Option<String>,
     },
     AllNftInfo {
         access: Cw721OwnerOfResponse,
@@ -1034,6 +1743,8 @@ pub enum QueryAnswer {
         private_metadata_is_public_expiration: Option<Expiration>,
         token_approvals: Option<Vec<Snip721Approval>>,
         inventory_approvals: Option<Vec<Snip721Approval>>,
+        /// true if the token's metadata can no longer be updated
+        frozen: bool,
     },
     BatchNftDossier {
         nft_dossiers: Vec<BatchNftDossierElement>,
@@ -1175,6 +1886,26 @@ pub enum QueryWithPermit {
     /// display the number of tokens controlled by the contract.  The token supply must
     /// either be public, or the querier must be an authenticated minter
     NumTokens {},
+    /// display the number of tokens with each public trait value.  The token supply must
+    /// either be public, or the querier must be an authenticated minter
+    TraitCounts {},
+    /// display the number of tokens sharing each of a token's public trait values, and its
+    /// rarity score.  The token supply must either be public, or the querier must be an
+    /// authenticated minter
+    TokenRarity { token_id: String },
+    /// display an optionally paginated list of the tokens with a public trait value, in mint
+    /// order.  The token supply must either be public, or the querier must be an
+    /// authenticated minter
+    TokensByTrait {
+        /// trait type to search
+        trait_type: String,
+        /// trait value to search for
+        value: String,
+        /// paginate by providing the last token_id received in the previous query
+        start_after: Option<String>,
+        /// optional number of token ids to display
+        limit: Option<u32>,
+    },
     /// display an optionally paginated list of all the tokens controlled by the contract.
     /// The token supply must either be public, or the querier must be an authenticated
     /// minter
@@ -1211,4 +1942,53 @@ pub enum QueryWithPermit {
     /// displays the number of tokens that the querier has permission to see the owner and that
     /// belong to the specified address
     NumTokensOfOwner { owner: HumanAddr },
+    /// displays the sale proceeds and royalties that the permit creator has not claimed yet
+    PendingProceeds {},
+    /// reconciles the contract's balances against the funds it owes.  Only the admin may
+    /// perform this query
+    Accounting {},
+    /// displays the sale stats of the collection, and optionally of one of its mint runs.  The
+    /// sale stats must either be public, or the permit creator must be a minter
+    CollectionStats {
+        /// optional registered mint run
+        mint_run: Option<MintRunRef>,
+    },
+    /// displays the tokens that pay royalties to the permit creator, along with its rate
+    /// and the royalties it has received from each of them
+    MyRoyalties {
+        /// optional page to display
+        page: Option<u32>,
+        /// optional number of tokens per page
+        page_size: Option<u32>,
+    },
+    /// displays the registered mint runs, optionally only those of one creator
+    MintRuns {
+        /// optional creator whose mint runs should be displayed
+        creator: Option<HumanAddr>,
+        /// paginate by providing the index of the last mint run received in the previous query
+        start_after: Option<u32>,
+        /// optional number of mint runs to display
+        limit: Option<u32>,
+    },
+    /// displays the token ids of a registered mint run
+    TokensInMintRun {
+        /// mint run ID given when minting
+        run_id: String,
+        /// number of the mint run for its mint run ID
+        run_number: u32,
+        /// paginate by providing the last_position received in the previous query
+        start_after: Option<u32>,
+        /// optional number of token ids to display
+        limit: Option<u32>,
+    },
+    /// displays the revisions of a token's metadata, newest first
+    MetadataHistory {
+        token_id: String,
+        /// optional page to display
+        page: Option<u32>,
+        /// optional number of revisions per page
+        page_size: Option<u32>,
+    },
+    /// displays the tokens held by a parent token
+    Children { token_id: String },
 }
@@ -1,4 +1,4 @@
-use cosmwasm_std::{Api, CanonicalAddr, HumanAddr, StdResult};
+use cosmwasm_std::{Api, CanonicalAddr, Coin, HumanAddr, StdResult};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -70,6 +70,19 @@ pub struct DisplayRoyaltyInfo {
     pub royalties: Vec<DisplayRoyalty>,
 }
 
+/// display of a token that pays royalties to the querier
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RecipientRoyalty {
+    /// id of the token
+    pub token_id: String,
+    /// decimal places in the royalty rate
+    pub decimal_places_in_rates: u8,
+    /// the querier's royalty rate for this token
+    pub rate: u16,
+    /// royalties the querier has received from marketplace sales of this token
+    pub received: Vec<Coin>,
+}
+
 /// data for storing a single royalty
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 pub struct StoredRoyalty {
@@ -1,6 +1,9 @@
-use std::any::type_name;
+use std::{any::type_name, collections::BTreeMap};
 
-use cosmwasm_std::{Api, BlockInfo, CanonicalAddr, ReadonlyStorage, StdError, StdResult, Storage};
+use cosmwasm_std::{
+    Api, Binary, BlockInfo, CanonicalAddr, Coin, ReadonlyStorage, StdError, StdResult, Storage,
+    Uint128,
+};
 use cosmwasm_storage::{PrefixedStorage, ReadonlyPrefixedStorage};
 
 use secret_toolkit::{
@@ -11,7 +14,8 @@ use secret_toolkit::{
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use crate::expiration::Expiration;
-use crate::msg::{Tx, TxAction};
+use crate::msg::{AttributeUpdater, Bundle, MetadataRevision, SaleStats, TraitCount, Tx, TxAction};
+use crate::token::Metadata;
 
 /// storage key for config
 pub const CONFIG_KEY: &[u8] = b"config";
@@ -67,6 +71,103 @@ pub const PREFIX_TOKEN_SALE_INFO: &[u8] = b"tokensaleinfo";
 pub const PREFIX_SALE_NUM: &[u8] = b"tokensale";
 /// prefix for the storage of nfts received
 pub const RECEIVED_NFT_KEY: &[u8] = b"received";
+/// prefix for the storage of an address' unclaimed sale proceeds
+pub const PREFIX_PROCEEDS: &[u8] = b"proceeds";
+/// storage key for the marketplace fees earned from sales
+pub const EARNED_FEES_KEY: &[u8] = b"earnedfees";
+/// storage key for the total funds held in escrow on behalf of others
+pub const ESCROW_KEY: &[u8] = b"escrow";
+/// prefix for the storage of the buckets of indices of the tokens that pay royalties to an
+/// address
+pub const PREFIX_ROYALTY_TOKENS: &[u8] = b"roytokens";
+/// prefix for the storage of the non-empty token buckets of each royalty recipient and their
+/// sizes
+pub const PREFIX_ROYALTY_BUCKETS: &[u8] = b"roybuckets";
+/// number of consecutive token indexes that share a royalty recipient's token bucket
+pub const ROYALTY_BUCKET_SIZE: u32 = 128;
+/// prefix for the storage of the royalties an address has received from each token
+pub const PREFIX_ROYALTY_EARNINGS: &[u8] = b"royearned";
+/// prefix for the storage of the total royalties an address has received
+pub const PREFIX_ROYALTY_TOTALS: &[u8] = b"roytotals";
+/// storage key for the sale stats of the whole collection
+pub const COLLECTION_STATS_KEY: &[u8] = b"colstats";
+/// prefix for the storage of the sale stats of each mint run
+pub const PREFIX_MINT_RUN_STATS: &[u8] = b"runstats";
+/// storage key for the public mint settings
+pub const PUBLIC_MINT_KEY: &[u8] = b"publicmint";
+/// prefix for the storage of the public mint metadata templates
+pub const PREFIX_MINT_TEMPLATES: &[u8] = b"minttemplates";
+/// prefix for the storage of the number of tokens each address minted through the public mint
+pub const PREFIX_PUBLIC_MINTED: &[u8] = b"publicminted";
+/// prefix for the storage of the number of tokens each address minted during each mint phase,
+/// keyed by phase name
+pub const PREFIX_PHASE_MINTED: &[u8] = b"phaseminted";
+/// storage key for the collection reveal commitment
+pub const REVEAL_COMMITMENT_KEY: &[u8] = b"revealcommit";
+/// prefix for the storage of lazy listings
+pub const PREFIX_LAZY_LISTINGS: &[u8] = b"lazylistings";
+/// storage key for the ids of the lazy listings that have editions left
+pub const LAZY_LISTINGS_KEY: &[u8] = b"lazyforsale";
+/// storage key for the number of mint runs in the registry
+pub const MINT_RUN_COUNT_KEY: &[u8] = b"runcount";
+/// prefix for the storage of the mint run registry
+pub const PREFIX_MINT_RUN_REGISTRY: &[u8] = b"runregistry";
+/// prefix for the storage of the registry index of each mint run ID and number
+pub const PREFIX_MINT_RUN_LOOKUP: &[u8] = b"runlookup";
+/// prefix for the storage of the token indices of each registered mint run
+pub const PREFIX_MINT_RUN_TOKENS: &[u8] = b"runtokens";
+/// prefix for the storage of the registry indices of each creator's mint runs
+pub const PREFIX_CREATOR_RUNS: &[u8] = b"creatorruns";
+/// prefix for the storage of minter roles
+pub const PREFIX_MINTER_ROLES: &[u8] = b"minterroles";
+/// prefix for the storage of the public key that signs each creator's mint vouchers
+pub const PREFIX_VOUCHER_SIGNERS: &[u8] = b"vouchersigners";
+/// prefix for the storage of the mint voucher nonces each creator has had redeemed
+pub const PREFIX_VOUCHER_NONCES: &[u8] = b"vouchernonces";
+/// prefix for the storage of the public metadata shared by editions
+pub const PREFIX_EDITION_META: &[u8] = b"editionmeta";
+/// prefix for the storage of each edition's link to its shared metadata
+pub const PREFIX_EDITIONS: &[u8] = b"editions";
+/// storage key for whether the metadata of every token is frozen
+pub const METADATA_FROZEN_KEY: &[u8] = b"metafrozen";
+/// prefix for the storage of the tokens whose metadata is frozen
+pub const PREFIX_FROZEN_METADATA: &[u8] = b"frozenmeta";
+/// prefix for the storage of each token's metadata revisions
+pub const PREFIX_META_HISTORY: &[u8] = b"metahistory";
+/// prefix for the storage of the number of public revisions in each token's metadata history
+pub const PREFIX_META_HISTORY_PUBLIC: &[u8] = b"metahistpublic";
+/// prefix for the storage of each token's unlockable content
+pub const PREFIX_UNLOCKABLE: &[u8] = b"unlockable";
+/// storage key for the contracts allowed to update specific attributes
+pub const ATTRIBUTE_UPDATERS_KEY: &[u8] = b"attrupdaters";
+/// storage key for the trait types in the rarity index
+pub const TRAIT_TYPES_KEY: &[u8] = b"traittypes";
+/// prefix for the storage of the number of tokens with each value of a trait type
+pub const PREFIX_TRAIT_COUNTS: &[u8] = b"traitcounts";
+/// prefix for the storage of the buckets of tokens with each value of a trait type
+pub const PREFIX_TRAIT_TOKENS: &[u8] = b"traittokens";
+/// prefix for the storage of the non-empty token buckets of each value of a trait type
+pub const PREFIX_TRAIT_BUCKETS: &[u8] = b"traitbuckets";
+/// number of consecutive token indexes that share a trait value's token bucket
+pub const TRAIT_BUCKET_SIZE: u32 = 128;
+/// prefix for the storage of the code hashes of registered child contracts
+pub const PREFIX_CHILD_CONTRACTS: &[u8] = b"childcontracts";
+/// prefix for the storage of the tokens held by each parent token
+pub const PREFIX_CHILDREN: &[u8] = b"children";
+/// prefix for the storage of the registry index of the mint run each token belongs to
+pub const PREFIX_TOKEN_MINT_RUN: &[u8] = b"tokenmintrun";
+/// storage key for the number of uscrt listings at each price for the whole collection
+pub const COLLECTION_FLOOR_KEY: &[u8] = b"colfloor";
+/// prefix for the storage of the number of uscrt listings at each price for each mint run
+pub const PREFIX_MINT_RUN_FLOOR: &[u8] = b"runfloor";
+/// storage key for the number of bundles that have been listed
+pub const BUNDLE_COUNT_KEY: &[u8] = b"bundlecount";
+/// storage key for the ids of the bundles currently for sale
+pub const BUNDLES_FOR_SALE_KEY: &[u8] = b"bundlesforsale";
+/// prefix for the storage of bundle listings
+pub const PREFIX_BUNDLES: &[u8] = b"bundles";
+/// prefix for the storage of the id of the bundle each locked token is listed in
+pub const PREFIX_BUNDLED_TOKENS: &[u8] = b"bundledtokens";
 
 /// Token contract config
 #[derive(Serialize, Debug, Deserialize, Clone, PartialEq)]
@@ -99,6 +200,10 @@ pub struct Config {
     pub owner_may_update_metadata: bool,
     /// is burn enabled
     pub burn_is_enabled: bool,
+    /// maximum sum of a token's royalty rates, in basis points
+    pub max_royalty_bps: u16,
+    /// are the collection's sale stats public
+    pub sale_stats_are_public: bool,
 }
 
 /// tx type and specifics
@@ -128,6 +233,27 @@ pub enum StoredTxAction {
         /// burner's address if not owner
         burner: Option<CanonicalAddr>,
     },
+    /// changed a token's royalties
+    SetRoyalties {
+        /// address that changed the royalties
+        changer: CanonicalAddr,
+    },
+    /// revoked a non-transferable token
+    Revoke {
+        /// previous owner
+        owner: CanonicalAddr,
+        /// the token's original minter who revoked it
+        issuer: CanonicalAddr,
+    },
+    /// reissued a non-transferable token to a new owner
+    Reissue {
+        /// previous owner
+        from: CanonicalAddr,
+        /// the token's original minter who reissued it
+        issuer: CanonicalAddr,
+        /// new owner
+        recipient: CanonicalAddr,
+    },
 }
 
 /// tx in storage
@@ -187,6 +313,22 @@ impl StoredTx {
                     burner: bnr,
                 }
             }
+            StoredTxAction::SetRoyalties { changer } => TxAction::SetRoyalties {
+                changer: api.human_address(&changer)?,
+            },
+            StoredTxAction::Revoke { owner, issuer } => TxAction::Revoke {
+                owner: api.human_address(&owner)?,
+                issuer: api.human_address(&issuer)?,
+            },
+            StoredTxAction::Reissue {
+                from,
+                issuer,
+                recipient,
+            } => TxAction::Reissue {
+                from: api.human_address(&from)?,
+                issuer: api.human_address(&issuer)?,
+                recipient: api.human_address(&recipient)?,
+            },
         };
         let tx = Tx {
             tx_id: self.tx_id,
@@ -339,6 +481,140 @@ pub fn store_burn<S: Storage>(
     Ok(())
 }
 
+/// Returns StdResult<()> after storing tx
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the storage this item should go to
+/// * `config` - a mutable reference to the contract Config
+/// * `block` - a reference to the current BlockInfo
+/// * `token_id` - id of the token whose royalties changed
+/// * `changer` - the address that changed the royalties
+/// * `owner` - the token owner's address
+pub fn store_royalty_change<S: Storage>(
+    storage: &mut S,
+    config: &mut Config,
+    block: &BlockInfo,
+    token_id: String,
+    changer: CanonicalAddr,
+    owner: &CanonicalAddr,
+) -> StdResult<()> {
+    let action = StoredTxAction::SetRoyalties { changer };
+    let tx = StoredTx {
+        tx_id: config.tx_cnt,
+        block_height: block.height,
+        block_time: block.time,
+        token_id,
+        action,
+        memo: None,
+    };
+    let mut tx_store = PrefixedStorage::new(PREFIX_TXS, storage);
+    json_save(&mut tx_store, &config.tx_cnt.to_le_bytes(), &tx)?;
+    if let StoredTxAction::SetRoyalties { changer } = tx.action {
+        append_tx_for_addr(storage, config.tx_cnt, owner)?;
+        if *owner != changer {
+            append_tx_for_addr(storage, config.tx_cnt, &changer)?;
+        }
+    }
+    config.tx_cnt += 1;
+    Ok(())
+}
+
+/// Returns StdResult<()> after storing tx
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the storage this item should go to
+/// * `config` - a mutable reference to the contract Config
+/// * `block` - a reference to the current BlockInfo
+/// * `token_id` - id of the token being revoked
+/// * `owner` - the previous owner's address
+/// * `issuer` - the original minter's address
+/// * `memo` - optional memo for the tx
+pub fn store_revoke<S: Storage>(
+    storage: &mut S,
+    config: &mut Config,
+    block: &BlockInfo,
+    token_id: String,
+    owner: CanonicalAddr,
+    issuer: CanonicalAddr,
+    memo: Option<String>,
+) -> StdResult<()> {
+    let action = StoredTxAction::Revoke { owner, issuer };
+    let tx = StoredTx {
+        tx_id: config.tx_cnt,
+        block_height: block.height,
+        block_time: block.time,
+        token_id,
+        action,
+        memo,
+    };
+    let mut tx_store = PrefixedStorage::new(PREFIX_TXS, storage);
+    json_save(&mut tx_store, &config.tx_cnt.to_le_bytes(), &tx)?;
+    if let StoredTxAction::Revoke { owner, issuer } = tx.action {
+        append_tx_for_addr(storage, config.tx_cnt, &owner)?;
+        if owner != issuer {
+            append_tx_for_addr(storage, config.tx_cnt, &issuer)?;
+        }
+    }
+    config.tx_cnt += 1;
+    Ok(())
+}
+
+/// Returns StdResult<()> after storing tx
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the storage this item should go to
+/// * `config` - a mutable reference to the contract Config
+/// * `block` - a reference to the current BlockInfo
+/// * `token_id` - id of the token being reissued
+/// * `from` - the previous owner's address
+/// * `issuer` - the original minter's address
+/// * `recipient` - the new owner's address
+/// * `memo` - optional memo for the tx
+#[allow(clippy::too_many_arguments)]
+pub fn store_reissue<S: Storage>(
+    storage: &mut S,
+    config: &mut Config,
+    block: &BlockInfo,
+    token_id: String,
+    from: CanonicalAddr,
+    issuer: CanonicalAddr,
+    recipient: CanonicalAddr,
+    memo: Option<String>,
+) -> StdResult<()> {
+    let action = StoredTxAction::Reissue {
+        from,
+        issuer,
+        recipient,
+    };
+    let tx = StoredTx {
+        tx_id: config.tx_cnt,
+        block_height: block.height,
+        block_time: block.time,
+        token_id,
+        action,
+        memo,
+    };
+    let mut tx_store = PrefixedStorage::new(PREFIX_TXS, storage);
+    json_save(&mut tx_store, &config.tx_cnt.to_le_bytes(), &tx)?;
+    if let StoredTxAction::Reissue {
+        from,
+        issuer,
+        recipient,
+    } = tx.action
+    {
+        append_tx_for_addr(storage, config.tx_cnt, &from)?;
+        append_tx_for_addr(storage, config.tx_cnt, &recipient)?;
+        if issuer != from && issuer != recipient {
+            append_tx_for_addr(storage, config.tx_cnt, &issuer)?;
+        }
+    }
+    config.tx_cnt += 1;
+    Ok(())
+}
+
 /// Returns StdResult<()> after saving tx id
 ///
 /// # Arguments
@@ -404,6 +680,144 @@ pub fn get_txs<A: Api, S: ReadonlyStorage>(
     txs.map(|t| (t, count))
 }
 
+/// attribute updater in storage
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct StoredAttributeUpdater {
+    /// address allowed to update attributes
+    pub updater: CanonicalAddr,
+    /// the trait types it may update
+    pub trait_types: Vec<String>,
+}
+
+impl StoredAttributeUpdater {
+    /// Returns StdResult<AttributeUpdater> from converting a stored attribute updater to a
+    /// displayable attribute updater
+    ///
+    /// # Arguments
+    ///
+    /// * `api` - a reference to the Api used to convert human and canonical addresses
+    pub fn to_display<A: Api>(&self, api: &A) -> StdResult<AttributeUpdater> {
+        Ok(AttributeUpdater {
+            updater: api.human_address(&self.updater)?,
+            trait_types: self.trait_types.clone(),
+        })
+    }
+}
+
+/// metadata revision in storage
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct StoredMetadataRevision {
+    /// address that changed the metadata
+    pub changer: CanonicalAddr,
+    /// the block containing the change
+    pub block_height: u64,
+    /// the time (in seconds since 01/01/1970) of the block containing the change
+    pub block_time: u64,
+    /// true if the private metadata was changed
+    pub private: bool,
+    /// sha256 hash of the metadata before the change
+    pub old_hash: Option<Vec<u8>>,
+    /// sha256 hash of the new metadata
+    pub new_hash: Vec<u8>,
+}
+
+impl StoredMetadataRevision {
+    /// Returns StdResult<MetadataRevision> from converting a stored revision to a displayable
+    /// revision
+    ///
+    /// # Arguments
+    ///
+    /// * `api` - a reference to the Api used to convert human and canonical addresses
+    pub fn into_humanized<A: Api>(self, api: &A) -> StdResult<MetadataRevision> {
+        Ok(MetadataRevision {
+            changer: api.human_address(&self.changer)?,
+            block_height: self.block_height,
+            block_time: self.block_time,
+            private: self.private,
+            old_hash: self.old_hash.map(Binary),
+            new_hash: Binary(self.new_hash),
+        })
+    }
+}
+
+/// Returns StdResult<()> after appending a revision to a token's metadata history
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `token_key` - the token's index as a byte slice
+/// * `revision` - a reference to the revision to append
+pub fn store_metadata_revision<S: Storage>(
+    storage: &mut S,
+    token_key: &[u8],
+    revision: &StoredMetadataRevision,
+) -> StdResult<()> {
+    // count the public revisions so the history query does not need to read them all
+    if !revision.private {
+        let mut count_store = PrefixedStorage::new(PREFIX_META_HISTORY_PUBLIC, storage);
+        let count: u64 = may_load(&count_store, token_key)?.unwrap_or(0);
+        save(&mut count_store, token_key, &(count + 1))?;
+    }
+    let mut store = PrefixedStorage::multilevel(&[PREFIX_META_HISTORY, token_key], storage);
+    let mut store = AppendStoreMut::attach_or_create(&mut store)?;
+    store.push(revision)
+}
+
+/// Returns StdResult<(Vec<MetadataRevision>, u64)> of the revisions to display and the total
+/// count of revisions that may be displayed
+///
+/// # Arguments
+///
+/// * `api` - a reference to the Api used to convert human and canonical addresses
+/// * `storage` - a reference to the contract's storage
+/// * `token_key` - the token's index as a byte slice
+/// * `include_private` - true if private metadata revisions may be displayed
+/// * `page` - page to start displaying
+/// * `page_size` - number of revisions per page
+pub fn get_metadata_history<A: Api, S: ReadonlyStorage>(
+    api: &A,
+    storage: &S,
+    token_key: &[u8],
+    include_private: bool,
+    page: u32,
+    page_size: u32,
+) -> StdResult<(Vec<MetadataRevision>, u64)> {
+    let store = ReadonlyPrefixedStorage::multilevel(&[PREFIX_META_HISTORY, token_key], storage);
+    // if the token's metadata was never changed, there are no revisions
+    let store = if let Some(result) = AppendStore::<StoredMetadataRevision, _>::attach(&store) {
+        result?
+    } else {
+        return Ok((vec![], 0));
+    };
+    let skip = page.saturating_mul(page_size) as usize;
+    let (page_revs, count) = if include_private {
+        let page_revs = store
+            .iter()
+            .rev()
+            .skip(skip)
+            .take(page_size as usize)
+            .collect::<StdResult<Vec<StoredMetadataRevision>>>()?;
+        (page_revs, store.len() as u64)
+    } else {
+        // private revisions are skipped as the history is read, stopping once the page is full
+        let page_revs = store
+            .iter()
+            .rev()
+            .filter(|rev| rev.as_ref().map_or(true, |r| !r.private))
+            .skip(skip)
+            .take(page_size as usize)
+            .collect::<StdResult<Vec<StoredMetadataRevision>>>()?;
+        let count_store = ReadonlyPrefixedStorage::new(PREFIX_META_HISTORY_PUBLIC, storage);
+        let count: u64 = may_load(&count_store, token_key)?.unwrap_or(0);
+        (page_revs, count)
+    };
+    let revisions = page_revs
+        .into_iter()
+        .map(|r| r.into_humanized(api))
+        .collect::<StdResult<Vec<MetadataRevision>>>()?;
+    Ok((revisions, count))
+}
+
 /// permission to view token info/transfer tokens
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 pub struct Permission {
@@ -455,6 +869,811 @@ pub struct ReceiveRegistration {
     pub impl_batch: bool,
 }
 
+/// an amount of a single denomination held by the contract on someone's behalf
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StoredBalance {
+    /// denomination of the coins
+    pub denom: String,
+    /// amount of coins
+    pub amount: u128,
+}
+
+impl StoredBalance {
+    /// Returns Coin from converting a stored balance to a displayable Coin
+    pub fn into_coin(self) -> Coin {
+        Coin {
+            denom: self.denom,
+            amount: Uint128(self.amount),
+        }
+    }
+}
+
+/// sale stats in storage
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct StoredSaleStats {
+    /// total amount of all sales
+    pub volume: u128,
+    /// number of sales
+    pub sale_count: u64,
+    /// price of the most recent sale
+    pub last_sale_price: Option<u128>,
+    /// price of the highest sale
+    pub highest_sale: Option<u128>,
+}
+
+impl StoredSaleStats {
+    /// Returns StdResult<()> after adding a sale to the stats
+    ///
+    /// # Arguments
+    ///
+    /// * `price` - the sale price
+    pub fn record_sale(&mut self, price: u128) -> StdResult<()> {
+        self.volume = self
+            .volume
+            .checked_add(price)
+            .ok_or_else(|| StdError::generic_err("Sale volume would overflow"))?;
+        self.sale_count += 1;
+        self.last_sale_price = Some(price);
+        if self.highest_sale.is_none_or(|h| price > h) {
+            self.highest_sale = Some(price);
+        }
+        Ok(())
+    }
+
+    /// Returns SaleStats from converting stored sale stats to displayable sale stats
+    ///
+    /// # Arguments
+    ///
+    /// * `floor_price` - lowest price among the tokens currently for sale
+    pub fn into_display(self, floor_price: Option<u128>) -> SaleStats {
+        SaleStats {
+            volume: Uint128(self.volume),
+            sale_count: self.sale_count,
+            last_sale_price: self.last_sale_price.map(Uint128),
+            highest_sale: self.highest_sale.map(Uint128),
+            floor_price: floor_price.map(Uint128),
+        }
+    }
+}
+
+/// bundle listing in storage
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StoredBundle {
+    /// address of the seller
+    pub seller: CanonicalAddr,
+    /// ids of the tokens in the bundle
+    pub token_ids: Vec<String>,
+    /// price of the whole bundle in uscrt
+    pub price: u32,
+    /// optional price of each token in uscrt, in the order of `token_ids`
+    pub token_prices: Option<Vec<u32>>,
+}
+
+impl StoredBundle {
+    /// Returns Bundle from converting a stored bundle to a displayable bundle
+    ///
+    /// # Arguments
+    ///
+    /// * `bundle_id` - the id of the bundle
+    pub fn into_display(self, bundle_id: u32) -> Bundle {
+        Bundle {
+            bundle_id,
+            token_ids: self.token_ids,
+            price: self.price,
+            token_prices: self.token_prices,
+        }
+    }
+}
+
+/// Returns StdResult<()> after adding an amount to the matching denomination of a balance list
+///
+/// # Arguments
+///
+/// * `balances` - a mutable reference to the list of balances
+/// * `denom` - denomination of the coins being added
+/// * `amount` - amount of coins being added
+pub fn add_balance(balances: &mut Vec<StoredBalance>, denom: &str, amount: u128) -> StdResult<()> {
+    if let Some(bal) = balances.iter_mut().find(|b| b.denom == denom) {
+        bal.amount = bal
+            .amount
+            .checked_add(amount)
+            .ok_or_else(|| StdError::generic_err("Balance would overflow"))?;
+    } else {
+        balances.push(StoredBalance {
+            denom: denom.to_string(),
+            amount,
+        });
+    }
+    Ok(())
+}
+
+/// Returns StdResult<()> after subtracting an amount from the matching denomination of a
+/// balance list
+///
+/// # Arguments
+///
+/// * `balances` - a mutable reference to the list of balances
+/// * `denom` - denomination of the coins being subtracted
+/// * `amount` - amount of coins being subtracted
+pub fn sub_balance(balances: &mut Vec<StoredBalance>, denom: &str, amount: u128) -> StdResult<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    let bal = balances
+        .iter_mut()
+        .find(|b| b.denom == denom)
+        .ok_or_else(|| StdError::generic_err(format!("No {} balance to subtract from", denom)))?;
+    bal.amount = bal
+        .amount
+        .checked_sub(amount)
+        .ok_or_else(|| StdError::generic_err("Balance would underflow"))?;
+    balances.retain(|b| b.amount != 0);
+    Ok(())
+}
+
+/// Returns StdResult<()> after adding to the total funds held in escrow
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `denom` - denomination of the escrowed funds
+/// * `amount` - amount being escrowed
+pub fn add_escrow<S: Storage>(storage: &mut S, denom: &str, amount: u128) -> StdResult<()> {
+    let mut escrow: Vec<StoredBalance> = may_load(storage, ESCROW_KEY)?.unwrap_or_default();
+    add_balance(&mut escrow, denom, amount)?;
+    save(storage, ESCROW_KEY, &escrow)
+}
+
+/// Returns StdResult<()> after releasing funds from escrow
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `denom` - denomination of the released funds
+/// * `amount` - amount being released
+pub fn release_escrow<S: Storage>(storage: &mut S, denom: &str, amount: u128) -> StdResult<()> {
+    let mut escrow: Vec<StoredBalance> = may_load(storage, ESCROW_KEY)?.unwrap_or_default();
+    sub_balance(&mut escrow, denom, amount)?;
+    save(storage, ESCROW_KEY, &escrow)
+}
+
+/// Returns StdResult<()> after crediting an address' unclaimed sale proceeds
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `address` - a reference to the address being credited
+/// * `denom` - denomination of the proceeds
+/// * `amount` - amount of the proceeds
+pub fn credit_proceeds<S: Storage>(
+    storage: &mut S,
+    address: &CanonicalAddr,
+    denom: &str,
+    amount: u128,
+) -> StdResult<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    add_escrow(storage, denom, amount)?;
+    let mut store = PrefixedStorage::new(PREFIX_PROCEEDS, storage);
+    let mut balances: Vec<StoredBalance> =
+        may_load(&store, address.as_slice())?.unwrap_or_default();
+    add_balance(&mut balances, denom, amount)?;
+    save(&mut store, address.as_slice(), &balances)
+}
+
+/// Returns StdResult<u128> of the amount of unclaimed proceeds of the specified denomination
+/// after removing them from the address' ledger
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `address` - a reference to the address claiming its proceeds
+/// * `denom` - denomination of the proceeds being claimed
+pub fn take_proceeds<S: Storage>(
+    storage: &mut S,
+    address: &CanonicalAddr,
+    denom: &str,
+) -> StdResult<u128> {
+    let mut store = PrefixedStorage::new(PREFIX_PROCEEDS, storage);
+    let mut balances: Vec<StoredBalance> =
+        may_load(&store, address.as_slice())?.unwrap_or_default();
+    let amount = balances
+        .iter()
+        .find(|b| b.denom == denom)
+        .map(|b| b.amount)
+        .unwrap_or(0);
+    balances.retain(|b| b.denom != denom);
+    if balances.is_empty() {
+        remove(&mut store, address.as_slice());
+    } else {
+        save(&mut store, address.as_slice(), &balances)?;
+    }
+    release_escrow(storage, denom, amount)?;
+    Ok(amount)
+}
+
+/// Returns StdResult<Vec<StoredBalance>> of an address' unclaimed proceeds
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `address` - a reference to the address whose proceeds should be displayed
+pub fn get_proceeds<S: ReadonlyStorage>(
+    storage: &S,
+    address: &CanonicalAddr,
+) -> StdResult<Vec<StoredBalance>> {
+    let store = ReadonlyPrefixedStorage::new(PREFIX_PROCEEDS, storage);
+    Ok(may_load(&store, address.as_slice())?.unwrap_or_default())
+}
+
+/// Returns StdResult<()> after recording royalties received from the sale of a token
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `recipient` - a reference to the royalty recipient's address
+/// * `token_key` - the storage key of the token that was sold
+/// * `denom` - denomination of the royalties
+/// * `amount` - amount of the royalties
+pub fn record_royalty_earnings<S: Storage>(
+    storage: &mut S,
+    recipient: &CanonicalAddr,
+    token_key: &[u8],
+    denom: &str,
+    amount: u128,
+) -> StdResult<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    let mut earn_store =
+        PrefixedStorage::multilevel(&[PREFIX_ROYALTY_EARNINGS, recipient.as_slice()], storage);
+    let mut earned: Vec<StoredBalance> = may_load(&earn_store, token_key)?.unwrap_or_default();
+    add_balance(&mut earned, denom, amount)?;
+    save(&mut earn_store, token_key, &earned)?;
+    let mut total_store = PrefixedStorage::new(PREFIX_ROYALTY_TOTALS, storage);
+    let mut totals: Vec<StoredBalance> =
+        may_load(&total_store, recipient.as_slice())?.unwrap_or_default();
+    add_balance(&mut totals, denom, amount)?;
+    save(&mut total_store, recipient.as_slice(), &totals)
+}
+
+/// Returns StdResult<()> after updating which recipients are listed as receiving royalties
+/// from a token
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `token_idx` - the index of the token whose royalties changed
+/// * `old` - the token's previous royalty recipients
+/// * `new` - the token's new royalty recipients
+pub fn update_royalty_index<S: Storage>(
+    storage: &mut S,
+    token_idx: u32,
+    old: &[CanonicalAddr],
+    new: &[CanonicalAddr],
+) -> StdResult<()> {
+    for removed in old.iter().filter(|a| !new.contains(a)) {
+        update_royalty_bucket(storage, removed, token_idx, false)?;
+    }
+    for added in new.iter().filter(|a| !old.contains(a)) {
+        update_royalty_bucket(storage, added, token_idx, true)?;
+    }
+    Ok(())
+}
+
+/// Returns StdResult<()> after adding or removing a token in the bucket of a royalty
+/// recipient's index that holds it.  Only that bucket and the recipient's bucket sizes are
+/// rewritten, so updates do not grow with the number of tokens paying the recipient
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `recipient` - a reference to the royalty recipient
+/// * `token_idx` - the index of the token
+/// * `add` - true if the token should be added, false if it should be removed
+fn update_royalty_bucket<S: Storage>(
+    storage: &mut S,
+    recipient: &CanonicalAddr,
+    token_idx: u32,
+    add: bool,
+) -> StdResult<()> {
+    let bucket = token_idx / ROYALTY_BUCKET_SIZE;
+    let bucket_key = bucket.to_le_bytes();
+    let mut token_store =
+        PrefixedStorage::multilevel(&[PREFIX_ROYALTY_TOKENS, recipient.as_slice()], storage);
+    let mut tokens: Vec<u32> = may_load(&token_store, &bucket_key)?.unwrap_or_default();
+    // keep the buckets in mint order so they can be paginated
+    match (tokens.binary_search(&token_idx), add) {
+        (Err(pos), true) => tokens.insert(pos, token_idx),
+        (Ok(pos), false) => {
+            tokens.remove(pos);
+        }
+        _ => return Ok(()),
+    }
+    if tokens.is_empty() {
+        remove(&mut token_store, &bucket_key);
+    } else {
+        save(&mut token_store, &bucket_key, &tokens)?;
+    }
+    let mut bucket_store = PrefixedStorage::new(PREFIX_ROYALTY_BUCKETS, storage);
+    let mut buckets: Vec<(u32, u32)> =
+        may_load(&bucket_store, recipient.as_slice())?.unwrap_or_default();
+    match buckets.binary_search_by_key(&bucket, |(b, _)| *b) {
+        Ok(pos) if tokens.is_empty() => {
+            buckets.remove(pos);
+        }
+        Ok(pos) => buckets[pos].1 = tokens.len() as u32,
+        Err(pos) => buckets.insert(pos, (bucket, tokens.len() as u32)),
+    }
+    if buckets.is_empty() {
+        remove(&mut bucket_store, recipient.as_slice());
+        Ok(())
+    } else {
+        save(&mut bucket_store, recipient.as_slice(), &buckets)
+    }
+}
+
+/// Returns StdResult<(Vec<u32>, u32)> of a page of the indexes of the tokens that pay royalties
+/// to an address, in mint order, and the number of those tokens.  Only the token buckets that
+/// hold the page are read
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `recipient` - a reference to the royalty recipient
+/// * `skip` - number of indexes to skip
+/// * `limit` - max number of indexes to return
+pub fn get_royalty_tokens<S: ReadonlyStorage>(
+    storage: &S,
+    recipient: &CanonicalAddr,
+    skip: usize,
+    limit: usize,
+) -> StdResult<(Vec<u32>, u32)> {
+    let bucket_store = ReadonlyPrefixedStorage::new(PREFIX_ROYALTY_BUCKETS, storage);
+    let buckets: Vec<(u32, u32)> =
+        may_load(&bucket_store, recipient.as_slice())?.unwrap_or_default();
+    let count = buckets.iter().map(|(_, len)| *len).sum();
+    let token_store = ReadonlyPrefixedStorage::multilevel(
+        &[PREFIX_ROYALTY_TOKENS, recipient.as_slice()],
+        storage,
+    );
+    let mut to_skip = skip;
+    let mut page = Vec::new();
+    for (bucket, len) in buckets.into_iter() {
+        if page.len() >= limit {
+            break;
+        }
+        // whole buckets before the page are skipped without being read
+        if to_skip >= len as usize {
+            to_skip -= len as usize;
+            continue;
+        }
+        let tokens: Vec<u32> = may_load(&token_store, &bucket.to_le_bytes())?.unwrap_or_default();
+        page.extend(tokens.into_iter().skip(to_skip).take(limit - page.len()));
+        to_skip = 0;
+    }
+    Ok((page, count))
+}
+
+/// Returns StdResult<()> after recording the registry index of the mint run a token belongs to
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `token_idx` - the index of the token
+/// * `reg_idx` - the registry index of the token's mint run
+/// * `listed_price` - the token's uscrt listing price if it is already listed for sale
+pub fn set_token_mint_run<S: Storage>(
+    storage: &mut S,
+    token_idx: u32,
+    reg_idx: u32,
+    listed_price: Option<u128>,
+) -> StdResult<()> {
+    let mut run_store = PrefixedStorage::new(PREFIX_TOKEN_MINT_RUN, storage);
+    save(&mut run_store, &token_idx.to_le_bytes(), &reg_idx)?;
+    let mut floor_store = PrefixedStorage::new(PREFIX_MINT_RUN_FLOOR, storage);
+    update_price_counts(&mut floor_store, &reg_idx.to_le_bytes(), None, listed_price)
+}
+
+/// Returns StdResult<Option<u32>> of the registry index of the mint run a token belongs to
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `token_idx` - the index of the token
+pub fn get_token_mint_run<S: ReadonlyStorage>(
+    storage: &S,
+    token_idx: u32,
+) -> StdResult<Option<u32>> {
+    let run_store = ReadonlyPrefixedStorage::new(PREFIX_TOKEN_MINT_RUN, storage);
+    may_load(&run_store, &token_idx.to_le_bytes())
+}
+
+/// Returns StdResult<()> after updating the floor price index when a token's uscrt listing
+/// price changes
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `token_idx` - the index of the token whose listing changed
+/// * `old` - the token's previous uscrt listing price, if it was listed
+/// * `new` - the token's new uscrt listing price, if it is listed
+pub fn update_floor_index<S: Storage>(
+    storage: &mut S,
+    token_idx: u32,
+    old: Option<u128>,
+    new: Option<u128>,
+) -> StdResult<()> {
+    if old == new {
+        return Ok(());
+    }
+    update_price_counts(storage, COLLECTION_FLOOR_KEY, old, new)?;
+    if let Some(reg_idx) = get_token_mint_run(storage, token_idx)? {
+        let mut floor_store = PrefixedStorage::new(PREFIX_MINT_RUN_FLOOR, storage);
+        update_price_counts(&mut floor_store, &reg_idx.to_le_bytes(), old, new)?;
+    }
+    Ok(())
+}
+
+/// Returns StdResult<Option<u128>> of the lowest uscrt listing price of the collection, or of
+/// one of its mint runs
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `reg_idx` - optional registry index of the mint run
+pub fn get_floor_price<S: ReadonlyStorage>(
+    storage: &S,
+    reg_idx: Option<u32>,
+) -> StdResult<Option<u128>> {
+    let counts: Option<BTreeMap<u128, u32>> = if let Some(idx) = reg_idx {
+        let floor_store = ReadonlyPrefixedStorage::new(PREFIX_MINT_RUN_FLOOR, storage);
+        may_load(&floor_store, &idx.to_le_bytes())?
+    } else {
+        may_load(storage, COLLECTION_FLOOR_KEY)?
+    };
+    Ok(counts.and_then(|c| c.keys().next().copied()))
+}
+
+/// Returns StdResult<()> after moving a listing between prices in a count of listings at
+/// each price
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the storage holding the counts
+/// * `key` - storage key of the counts
+/// * `old` - the previous price of the listing, if it was listed
+/// * `new` - the new price of the listing, if it is listed
+fn update_price_counts<S: Storage>(
+    storage: &mut S,
+    key: &[u8],
+    old: Option<u128>,
+    new: Option<u128>,
+) -> StdResult<()> {
+    if old == new {
+        return Ok(());
+    }
+    let mut counts: BTreeMap<u128, u32> = may_load(storage, key)?.unwrap_or_default();
+    if let Some(price) = old {
+        if let Some(cnt) = counts.get_mut(&price) {
+            *cnt -= 1;
+            if *cnt == 0 {
+                counts.remove(&price);
+            }
+        }
+    }
+    if let Some(price) = new {
+        *counts.entry(price).or_insert(0) += 1;
+    }
+    if counts.is_empty() {
+        remove(storage, key);
+        Ok(())
+    } else {
+        save(storage, key, &counts)
+    }
+}
+
+/// number of tokens with a trait value in storage
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct StoredTraitCount {
+    /// trait value
+    pub value: String,
+    /// number of tokens with this value
+    pub count: u32,
+}
+
+/// Returns Vec<(String, String)> of the trait types and values of the metadata that count
+/// toward rarity.  Only public attributes with a trait type and without a display type
+/// (numbers, dates and boosts are not rare in themselves) are counted
+///
+/// # Arguments
+///
+/// * `metadata` - optional reference to the token's public metadata
+pub fn rarity_traits(metadata: Option<&Metadata>) -> Vec<(String, String)> {
+    metadata
+        .and_then(|m| m.extension.as_ref())
+        .and_then(|x| x.attributes.as_ref())
+        .map(|attrs| {
+            attrs
+                .iter()
+                .filter(|a| a.display_type.is_none())
+                .filter_map(|a| {
+                    a.trait_type
+                        .as_ref()
+                        .map(|tt| (tt.clone(), a.value.clone()))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Returns Vec<(String, String)> of the trait types and values of the metadata that can be
+/// searched.  All public attributes with a trait type are searchable
+///
+/// # Arguments
+///
+/// * `metadata` - optional reference to the token's public metadata
+fn searchable_traits(metadata: Option<&Metadata>) -> Vec<(String, String)> {
+    metadata
+        .and_then(|m| m.extension.as_ref())
+        .and_then(|x| x.attributes.as_ref())
+        .map(|attrs| {
+            attrs
+                .iter()
+                .filter_map(|a| {
+                    a.trait_type
+                        .as_ref()
+                        .map(|tt| (tt.clone(), a.value.clone()))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Returns StdResult<()> after updating the trait-frequency and attribute-value indexes when
+/// a token's public metadata changes
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `token_idx` - the index of the token whose public metadata changed
+/// * `old` - optional reference to the token's previous public metadata
+/// * `new` - optional reference to the token's new public metadata
+pub fn update_trait_index<S: Storage>(
+    storage: &mut S,
+    token_idx: u32,
+    old: Option<&Metadata>,
+    new: Option<&Metadata>,
+) -> StdResult<()> {
+    update_trait_counts(storage, old, new)?;
+    let removed = searchable_traits(old);
+    let added = searchable_traits(new);
+    // only the bucket holding the token is rewritten, so updates do not grow with the number
+    // of tokens sharing the trait value
+    let bucket = token_idx / TRAIT_BUCKET_SIZE;
+    let bucket_key = bucket.to_le_bytes();
+    for (trait_type, value) in removed.iter().filter(|t| !added.contains(t)) {
+        let mut token_store = PrefixedStorage::multilevel(
+            &[PREFIX_TRAIT_TOKENS, trait_type.as_bytes(), value.as_bytes()],
+            storage,
+        );
+        let mut tokens: Vec<u32> = may_load(&token_store, &bucket_key)?.unwrap_or_default();
+        tokens.retain(|t| *t != token_idx);
+        if tokens.is_empty() {
+            remove(&mut token_store, &bucket_key);
+            let mut bucket_store = PrefixedStorage::multilevel(
+                &[PREFIX_TRAIT_BUCKETS, trait_type.as_bytes()],
+                storage,
+            );
+            let mut buckets: Vec<u32> =
+                may_load(&bucket_store, value.as_bytes())?.unwrap_or_default();
+            buckets.retain(|b| *b != bucket);
+            if buckets.is_empty() {
+                remove(&mut bucket_store, value.as_bytes());
+            } else {
+                save(&mut bucket_store, value.as_bytes(), &buckets)?;
+            }
+        } else {
+            save(&mut token_store, &bucket_key, &tokens)?;
+        }
+    }
+    for (trait_type, value) in added.iter().filter(|t| !removed.contains(t)) {
+        let mut token_store = PrefixedStorage::multilevel(
+            &[PREFIX_TRAIT_TOKENS, trait_type.as_bytes(), value.as_bytes()],
+            storage,
+        );
+        let mut tokens: Vec<u32> = may_load(&token_store, &bucket_key)?.unwrap_or_default();
+        // keep the buckets in mint order so they can be paginated
+        if let Err(pos) = tokens.binary_search(&token_idx) {
+            tokens.insert(pos, token_idx);
+            save(&mut token_store, &bucket_key, &tokens)?;
+            if tokens.len() == 1 {
+                let mut bucket_store = PrefixedStorage::multilevel(
+                    &[PREFIX_TRAIT_BUCKETS, trait_type.as_bytes()],
+                    storage,
+                );
+                let mut buckets: Vec<u32> =
+                    may_load(&bucket_store, value.as_bytes())?.unwrap_or_default();
+                if let Err(pos) = buckets.binary_search(&bucket) {
+                    buckets.insert(pos, bucket);
+                    save(&mut bucket_store, value.as_bytes(), &buckets)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns StdResult<Vec<u32>> of a page of the indexes of the tokens with a trait value, in
+/// mint order.  Only the token buckets that can hold the page are read
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `trait_type` - the trait type
+/// * `value` - the trait value
+/// * `start_after` - optionally only return indexes greater than this one
+/// * `limit` - max number of indexes to return
+pub fn get_trait_tokens<S: ReadonlyStorage>(
+    storage: &S,
+    trait_type: &str,
+    value: &str,
+    start_after: Option<u32>,
+    limit: usize,
+) -> StdResult<Vec<u32>> {
+    let bucket_store = ReadonlyPrefixedStorage::multilevel(
+        &[PREFIX_TRAIT_BUCKETS, trait_type.as_bytes()],
+        storage,
+    );
+    let buckets: Vec<u32> = may_load(&bucket_store, value.as_bytes())?.unwrap_or_default();
+    let first = start_after.map_or(0, |s| s / TRAIT_BUCKET_SIZE);
+    let token_store = ReadonlyPrefixedStorage::multilevel(
+        &[PREFIX_TRAIT_TOKENS, trait_type.as_bytes(), value.as_bytes()],
+        storage,
+    );
+    let mut page = Vec::new();
+    for bucket in buckets.into_iter().filter(|b| *b >= first) {
+        if page.len() >= limit {
+            break;
+        }
+        let tokens: Vec<u32> = may_load(&token_store, &bucket.to_le_bytes())?.unwrap_or_default();
+        page.extend(
+            tokens
+                .into_iter()
+                .filter(|idx| start_after.is_none_or(|s| *idx > s))
+                .take(limit - page.len()),
+        );
+    }
+    Ok(page)
+}
+
+/// Returns StdResult<()> after updating the trait-frequency index when a token's public
+/// metadata changes
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `old` - optional reference to the token's previous public metadata
+/// * `new` - optional reference to the token's new public metadata
+fn update_trait_counts<S: Storage>(
+    storage: &mut S,
+    old: Option<&Metadata>,
+    new: Option<&Metadata>,
+) -> StdResult<()> {
+    let removed = rarity_traits(old);
+    let added = rarity_traits(new);
+    if removed == added {
+        return Ok(());
+    }
+    let mut trait_types: Vec<String> = may_load(storage, TRAIT_TYPES_KEY)?.unwrap_or_default();
+    let old_types = trait_types.clone();
+    let mut count_store = PrefixedStorage::new(PREFIX_TRAIT_COUNTS, storage);
+    for (trait_type, value) in removed.iter() {
+        let mut counts: Vec<StoredTraitCount> =
+            may_load(&count_store, trait_type.as_bytes())?.unwrap_or_default();
+        if let Some(pos) = counts.iter().position(|c| c.value == *value) {
+            counts[pos].count = counts[pos].count.saturating_sub(1);
+            if counts[pos].count == 0 {
+                counts.remove(pos);
+            }
+        }
+        if counts.is_empty() {
+            remove(&mut count_store, trait_type.as_bytes());
+            trait_types.retain(|t| t != trait_type);
+        } else {
+            save(&mut count_store, trait_type.as_bytes(), &counts)?;
+        }
+    }
+    for (trait_type, value) in added.into_iter() {
+        let mut counts: Vec<StoredTraitCount> =
+            may_load(&count_store, trait_type.as_bytes())?.unwrap_or_default();
+        if let Some(existing) = counts.iter_mut().find(|c| c.value == value) {
+            existing.count += 1;
+        } else {
+            counts.push(StoredTraitCount { value, count: 1 });
+        }
+        save(&mut count_store, trait_type.as_bytes(), &counts)?;
+        if !trait_types.contains(&trait_type) {
+            trait_types.push(trait_type);
+        }
+    }
+    if trait_types != old_types {
+        save(storage, TRAIT_TYPES_KEY, &trait_types)?;
+    }
+    Ok(())
+}
+
+/// Returns StdResult<u32> of the number of tokens with a trait value
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+/// * `trait_type` - the trait type
+/// * `value` - the trait value
+pub fn get_trait_count<S: ReadonlyStorage>(
+    storage: &S,
+    trait_type: &str,
+    value: &str,
+) -> StdResult<u32> {
+    let count_store = ReadonlyPrefixedStorage::new(PREFIX_TRAIT_COUNTS, storage);
+    let counts: Vec<StoredTraitCount> =
+        may_load(&count_store, trait_type.as_bytes())?.unwrap_or_default();
+    Ok(counts
+        .iter()
+        .find(|c| c.value == value)
+        .map(|c| c.count)
+        .unwrap_or(0))
+}
+
+/// Returns StdResult<Vec<TraitCount>> of the number of tokens with each trait value
+///
+/// # Arguments
+///
+/// * `storage` - a reference to the contract's storage
+pub fn get_trait_counts<S: ReadonlyStorage>(storage: &S) -> StdResult<Vec<TraitCount>> {
+    let trait_types: Vec<String> = may_load(storage, TRAIT_TYPES_KEY)?.unwrap_or_default();
+    let count_store = ReadonlyPrefixedStorage::new(PREFIX_TRAIT_COUNTS, storage);
+    let mut traits = Vec::new();
+    for trait_type in trait_types.into_iter() {
+        let counts: Vec<StoredTraitCount> =
+            may_load(&count_store, trait_type.as_bytes())?.unwrap_or_default();
+        traits.extend(counts.into_iter().map(|c| TraitCount {
+            trait_type: trait_type.clone(),
+            value: c.value,
+            count: c.count,
+        }));
+    }
+    Ok(traits)
+}
+
+/// Returns StdResult<()> after adding to the marketplace fees earned from sales
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+/// * `denom` - denomination of the fee
+/// * `amount` - amount of the fee
+pub fn credit_fees<S: Storage>(storage: &mut S, denom: &str, amount: u128) -> StdResult<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    let mut fees: Vec<StoredBalance> = may_load(storage, EARNED_FEES_KEY)?.unwrap_or_default();
+    add_balance(&mut fees, denom, amount)?;
+    save(storage, EARNED_FEES_KEY, &fees)
+}
+
+/// Returns StdResult<Vec<StoredBalance>> of all the earned marketplace fees after clearing them
+///
+/// # Arguments
+///
+/// * `storage` - a mutable reference to the contract's storage
+pub fn take_fees<S: Storage>(storage: &mut S) -> StdResult<Vec<StoredBalance>> {
+    let fees: Vec<StoredBalance> = may_load(storage, EARNED_FEES_KEY)?.unwrap_or_default();
+    remove(storage, EARNED_FEES_KEY);
+    Ok(fees)
+}
+
 /// Returns StdResult<()> resulting from saving an item to storage
 ///
 /// # Arguments
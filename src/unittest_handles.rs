@@ -3,14 +3,22 @@
 #[cfg(test)]
 
 mod tests {
+    use crate::composable::{AttachChildMsg, ChildToken, Snip721HandleMsg};
     use crate::contract::{check_permission, handle, init, query};
     use crate::expiration::Expiration;
     use crate::inventory::Inventory;
+    use crate::launchpad::{
+        LazyListing, MintPhase, MintTemplate, MintVoucher, PublicMintInfo, RevealCommitment,
+    };
+    use crate::mint_run::MintRunRef;
+    use crate::minters::MinterInfo;
     use crate::msg::{
-        AccessLevel, Burn, ContractStatus, HandleAnswer, HandleMsg, InitConfig, InitMsg, Mint,
-        PostInitCallback, QueryAnswer, QueryMsg, ReceiverInfo, SaleStatus, Send, TokenSaleInfo,
-        Transfer, Tx, TxAction,
+        AccessLevel, Bundle, Burn, ContractStatus, DenomAccounting, HandleAnswer, HandleMsg,
+        InitConfig, InitMsg, Mint, MintListing, PostInitCallback, QueryAnswer, QueryMsg,
+        ReceiverInfo, SaleStats, SaleStatus, Send, TokenSaleInfo, Transfer, Tx, TxAction,
+        ViewerInfo,
     };
+    use crate::rand::sha_256;
     use crate::receiver::Snip721ReceiveMsg;
     use crate::royalties::{Royalty, RoyaltyInfo};
     use crate::state::{
@@ -18,15 +26,15 @@ mod tests {
         PermissionType, CONFIG_KEY, FOR_SALE_KEY, MINTERS_KEY, PREFIX_ALL_PERMISSIONS,
         PREFIX_AUTHLIST, PREFIX_INFOS, PREFIX_MAP_TO_ID, PREFIX_MAP_TO_INDEX, PREFIX_OWNER_PRIV,
         PREFIX_PRIV_META, PREFIX_PUB_META, PREFIX_RECEIVERS, PREFIX_SALE_NUM,
-        PREFIX_TOKEN_SALE_INFO, PREFIX_VIEW_KEY,
+        PREFIX_TOKEN_SALE_INFO, PREFIX_VIEW_KEY, PRNG_SEED_KEY,
     };
-    use crate::token::{Extension, Metadata, Token};
+    use crate::token::{Extension, MediaFile, Metadata, Token, Trait};
     use crate::viewing_key::{ViewingKey, VIEWING_KEY_SIZE};
     use cosmwasm_std::testing::*;
     use cosmwasm_std::{
-        coins, from_binary, to_binary, Api, Binary, BlockInfo, CanonicalAddr, Coin, CosmosMsg, Env,
-        Extern, HandleResponse, HumanAddr, InitResponse, MessageInfo, StdError, StdResult, Uint128,
-        WasmMsg,
+        coins, from_binary, to_binary, Api, BankMsg, Binary, BlockInfo, CanonicalAddr, Coin,
+        CosmosMsg, Env, Extern, HandleResponse, HumanAddr, InitResponse, MessageInfo, StdError,
+        StdResult, Uint128, WasmMsg,
     };
     use cosmwasm_storage::ReadonlyPrefixedStorage;
     use secret_toolkit::utils::space_pad;
@@ -230,7 +238,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("NFT1".to_string()),
                 description: Some("pub1".to_string()),
-                image: Some("uri1".to_string()),
+                image: Some("https://uri1".to_string()),
                 ..Extension::default()
             }),
         };
@@ -239,7 +247,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("NFT2".to_string()),
                 description: Some("priv2".to_string()),
-                image: Some("uri2".to_string()),
+                image: Some("https://uri2".to_string()),
                 ..Extension::default()
             }),
         };
@@ -253,6 +261,7 @@ mod tests {
                 serial_number: None,
                 transferable: None,
                 memo: None,
+                listing: None,
             },
             Mint {
                 token_id: Some("NFT2".to_string()),
@@ -263,6 +272,7 @@ mod tests {
                 serial_number: None,
                 transferable: None,
                 memo: None,
+                listing: None,
             },
             Mint {
                 token_id: Some("NFT3".to_string()),
@@ -273,6 +283,7 @@ mod tests {
                 transferable: None,
                 serial_number: None,
                 memo: None,
+                listing: None,
             },
             Mint {
                 token_id: None,
@@ -283,6 +294,7 @@ mod tests {
                 transferable: None,
                 serial_number: None,
                 memo: Some("has id 3".to_string()),
+                listing: None,
             },
         ];
 
@@ -428,7 +440,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("MyNFT".to_string()),
                     description: None,
-                    image: Some("uri".to_string()),
+                    image: Some("https://uri".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -454,11 +466,11 @@ mod tests {
             token_id: Some("MyNFT".to_string()),
             owner: Some(HumanAddr("alice".to_string())),
             public_metadata: Some(Metadata {
-                token_uri: Some("uri".to_string()),
+                token_uri: Some("https://uri".to_string()),
                 extension: Some(Extension {
                     name: Some("MyNFT".to_string()),
                     description: None,
-                    image: Some("uri".to_string()),
+                    image: Some("https://uri".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -482,7 +494,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("MyNFT".to_string()),
                     description: None,
-                    image: Some("uri".to_string()),
+                    image: Some("https://uri".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -509,7 +521,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("MyNFT".to_string()),
                 description: None,
-                image: Some("uri".to_string()),
+                image: Some("https://uri".to_string()),
                 ..Extension::default()
             }),
         });
@@ -518,7 +530,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("MyNFTpriv".to_string()),
                 description: Some("Nifty".to_string()),
-                image: Some("privuri".to_string()),
+                image: Some("https://privuri".to_string()),
                 ..Extension::default()
             }),
         });
@@ -591,7 +603,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("MyNFT".to_string()),
                     description: None,
-                    image: Some("uri".to_string()),
+                    image: Some("https://uri".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -600,7 +612,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("MyNFTpriv".to_string()),
                     description: Some("Nifty".to_string()),
-                    image: Some("privuri".to_string()),
+                    image: Some("https://privuri".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -715,7 +727,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("New Name".to_string()),
                     description: Some("I changed the metadata".to_string()),
-                    image: Some("new uri".to_string()),
+                    image: Some("https://new uri".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -741,7 +753,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("New Name".to_string()),
                     description: Some("I changed the metadata".to_string()),
-                    image: Some("new uri".to_string()),
+                    image: Some("https://new uri".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -766,7 +778,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("MyNFT".to_string()),
                     description: None,
-                    image: Some("uri".to_string()),
+                    image: Some("https://uri".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -790,7 +802,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("MyNFT".to_string()),
                     description: None,
-                    image: Some("uri".to_string()),
+                    image: Some("https://uri".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -811,7 +823,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("New Name".to_string()),
                     description: Some("I changed the metadata".to_string()),
-                    image: Some("new uri".to_string()),
+                    image: Some("https://new uri".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -830,7 +842,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("New Name".to_string()),
                     description: Some("I changed the metadata".to_string()),
-                    image: Some("new uri".to_string()),
+                    image: Some("https://new uri".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -857,7 +869,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("MyNFT".to_string()),
                     description: None,
-                    image: Some("uri".to_string()),
+                    image: Some("https://uri".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -876,7 +888,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("New Name".to_string()),
                     description: Some("I changed the metadata".to_string()),
-                    image: Some("new uri".to_string()),
+                    image: Some("https://new uri".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -902,7 +914,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("MyNFT".to_string()),
                     description: None,
-                    image: Some("uri".to_string()),
+                    image: Some("https://uri".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -919,7 +931,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("New Name".to_string()),
                 description: Some("I changed the metadata".to_string()),
-                image: Some("new uri".to_string()),
+                image: Some("https://new uri".to_string()),
                 ..Extension::default()
             }),
         });
@@ -957,7 +969,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("New Name".to_string()),
                     description: Some("I changed the metadata".to_string()),
-                    image: Some("new uri".to_string()),
+                    image: Some("https://new uri".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -983,7 +995,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("MyNFT".to_string()),
                     description: None,
-                    image: Some("uri".to_string()),
+                    image: Some("https://uri".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -1013,7 +1025,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("MyNFT".to_string()),
                     description: None,
-                    image: Some("uri".to_string()),
+                    image: Some("https://uri".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -1033,7 +1045,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("New Name".to_string()),
                     description: Some("I changed the metadata".to_string()),
-                    image: Some("new uri".to_string()),
+                    image: Some("https://new uri".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -1052,7 +1064,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("New Name".to_string()),
                     description: Some("I changed the metadata".to_string()),
-                    image: Some("new uri".to_string()),
+                    image: Some("https://new uri".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -1081,11 +1093,11 @@ mod tests {
         let handle_msg = HandleMsg::SetMetadata {
             token_id: "MyNFT".to_string(),
             private_metadata: Some(Metadata {
-                token_uri: Some("uri".to_string()),
+                token_uri: Some("https://uri".to_string()),
                 extension: Some(Extension {
                     name: Some("MyNFT".to_string()),
                     description: None,
-                    image: Some("uri".to_string()),
+                    image: Some("https://uri".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -1102,7 +1114,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("New Name Pub".to_string()),
                 description: Some("Minter changed the public metadata".to_string()),
-                image: Some("new uri pub".to_string()),
+                image: Some("https://new uri pub".to_string()),
                 ..Extension::default()
             }),
         });
@@ -1111,7 +1123,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("New Name Priv".to_string()),
                 description: Some("Minter changed the private metadata".to_string()),
-                image: Some("new uri priv".to_string()),
+                image: Some("https://new uri priv".to_string()),
                 ..Extension::default()
             }),
         });
@@ -1144,7 +1156,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("MyNFT".to_string()),
                     description: None,
-                    image: Some("uri".to_string()),
+                    image: Some("https://uri".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -1169,7 +1181,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("New Name".to_string()),
                     description: Some("I changed the metadata".to_string()),
-                    image: Some("new uri".to_string()),
+                    image: Some("https://new uri".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -1192,7 +1204,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("MyNFT".to_string()),
                 description: None,
-                image: Some("uri".to_string()),
+                image: Some("https://uri".to_string()),
                 ..Extension::default()
             }),
         });
@@ -1213,7 +1225,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("New Name".to_string()),
                 description: Some("Owner changed the metadata".to_string()),
-                image: Some("new uri".to_string()),
+                image: Some("https://new uri".to_string()),
                 ..Extension::default()
             }),
         });
@@ -1291,7 +1303,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("MySealedNFT".to_string()),
                     description: Some("Sealed metadata test".to_string()),
-                    image: Some("sealed_uri".to_string()),
+                    image: Some("https://sealed_uri".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -1341,7 +1353,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("MySealedNFT".to_string()),
                 description: Some("Sealed metadata test".to_string()),
-                image: Some("sealed_uri".to_string()),
+                image: Some("https://sealed_uri".to_string()),
                 ..Extension::default()
             }),
         });
@@ -1478,7 +1490,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("My1".to_string()),
                 description: Some("Public 1".to_string()),
-                image: Some("URI 1".to_string()),
+                image: Some("https://URI 1".to_string()),
                 ..Extension::default()
             }),
         });
@@ -1499,7 +1511,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("My2".to_string()),
                 description: Some("Public 2".to_string()),
-                image: Some("URI 2".to_string()),
+                image: Some("https://URI 2".to_string()),
                 ..Extension::default()
             }),
         });
@@ -1520,7 +1532,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("My3".to_string()),
                 description: Some("Public 3".to_string()),
-                image: Some("URI 3".to_string()),
+                image: Some("https://URI 3".to_string()),
                 ..Extension::default()
             }),
         });
@@ -1541,7 +1553,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("My4".to_string()),
                 description: Some("Public 4".to_string()),
-                image: Some("URI 4".to_string()),
+                image: Some("https://URI 4".to_string()),
                 ..Extension::default()
             }),
         });
@@ -3528,7 +3540,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("MyNFT".to_string()),
                 description: Some("metadata".to_string()),
-                image: Some("uri".to_string()),
+                image: Some("https://uri".to_string()),
                 ..Extension::default()
             }),
         });
@@ -3839,7 +3851,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("MyNFT2".to_string()),
                 description: Some("metadata2".to_string()),
-                image: Some("uri2".to_string()),
+                image: Some("https://uri2".to_string()),
                 ..Extension::default()
             }),
         });
@@ -3863,7 +3875,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("MyNFT3".to_string()),
                     description: Some("metadata3".to_string()),
-                    image: Some("uri3".to_string()),
+                    image: Some("https://uri3".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -4037,7 +4049,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("MyNFT".to_string()),
                 description: Some("metadata".to_string()),
-                image: Some("uri".to_string()),
+                image: Some("https://uri".to_string()),
                 ..Extension::default()
             }),
         });
@@ -4415,7 +4427,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("MyNFT2".to_string()),
                 description: Some("metadata2".to_string()),
-                image: Some("uri2".to_string()),
+                image: Some("https://uri2".to_string()),
                 ..Extension::default()
             }),
         });
@@ -4439,7 +4451,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("MyNFT3".to_string()),
                     description: Some("metadata3".to_string()),
-                    image: Some("uri3".to_string()),
+                    image: Some("https://uri3".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -4575,7 +4587,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("MyNFT".to_string()),
                     description: Some("metadata".to_string()),
-                    image: Some("uri".to_string()),
+                    image: Some("https://uri".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -4663,7 +4675,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("MyNFT".to_string()),
                     description: Some("privmetadata".to_string()),
-                    image: Some("privuri".to_string()),
+                    image: Some("https://privuri".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -4672,7 +4684,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("MyNFT".to_string()),
                     description: Some("pubmetadata".to_string()),
-                    image: Some("puburi".to_string()),
+                    image: Some("https://puburi".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -4862,7 +4874,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("MyNFT2".to_string()),
                 description: Some("privmetadata2".to_string()),
-                image: Some("privuri2".to_string()),
+                image: Some("https://privuri2".to_string()),
                 ..Extension::default()
             }),
         });
@@ -4871,7 +4883,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("MyNFT2".to_string()),
                 description: Some("pubmetadata2".to_string()),
-                image: Some("puburi2".to_string()),
+                image: Some("https://puburi2".to_string()),
                 ..Extension::default()
             }),
         });
@@ -4893,7 +4905,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("MyNFT3".to_string()),
                 description: Some("privmetadata3".to_string()),
-                image: Some("privuri3".to_string()),
+                image: Some("https://privuri3".to_string()),
                 ..Extension::default()
             }),
         });
@@ -4902,7 +4914,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("MyNFT3".to_string()),
                 description: Some("pubmetadata3".to_string()),
-                image: Some("puburi3".to_string()),
+                image: Some("https://puburi3".to_string()),
                 ..Extension::default()
             }),
         });
@@ -5154,7 +5166,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("MyNFT3".to_string()),
                 description: Some("privmetadata3".to_string()),
-                image: Some("privuri3".to_string()),
+                image: Some("https://privuri3".to_string()),
                 ..Extension::default()
             }),
         });
@@ -5163,7 +5175,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("MyNFT3".to_string()),
                 description: Some("pubmetadata3".to_string()),
-                image: Some("puburi3".to_string()),
+                image: Some("https://puburi3".to_string()),
                 ..Extension::default()
             }),
         });
@@ -6011,7 +6023,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("MyNFT".to_string()),
                     description: Some("metadata".to_string()),
-                    image: Some("uri".to_string()),
+                    image: Some("https://uri".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -6089,7 +6101,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("MyNFT".to_string()),
                 description: Some("privmetadata".to_string()),
-                image: Some("privuri".to_string()),
+                image: Some("https://privuri".to_string()),
                 ..Extension::default()
             }),
         });
@@ -6098,7 +6110,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("MyNFT".to_string()),
                 description: Some("pubmetadata".to_string()),
-                image: Some("puburi".to_string()),
+                image: Some("https://puburi".to_string()),
                 ..Extension::default()
             }),
         });
@@ -6568,7 +6580,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("MyNFT".to_string()),
                     description: Some("metadata".to_string()),
-                    image: Some("uri".to_string()),
+                    image: Some("https://uri".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -6673,6 +6685,7 @@ mod tests {
                     serial_number: None,
                     transferable: None,
                     memo: None,
+                    listing: None,
                 },
                 Mint {
                     token_id: Some("NFT2".to_string()),
@@ -6683,6 +6696,7 @@ mod tests {
                     serial_number: None,
                     transferable: None,
                     memo: None,
+                    listing: None,
                 },
                 Mint {
                     token_id: Some("NFT3".to_string()),
@@ -6693,6 +6707,7 @@ mod tests {
                     serial_number: None,
                     transferable: None,
                     memo: None,
+                    listing: None,
                 },
                 Mint {
                     token_id: Some("NFT4".to_string()),
@@ -6703,6 +6718,7 @@ mod tests {
                     serial_number: None,
                     transferable: None,
                     memo: None,
+                    listing: None,
                 },
                 Mint {
                     token_id: Some("NFT5".to_string()),
@@ -6713,6 +6729,7 @@ mod tests {
                     serial_number: None,
                     transferable: None,
                     memo: None,
+                    listing: None,
                 },
                 Mint {
                     token_id: Some("NFT6".to_string()),
@@ -6723,6 +6740,7 @@ mod tests {
                     serial_number: None,
                     transferable: None,
                     memo: None,
+                    listing: None,
                 },
             ],
             padding: None,
@@ -7274,6 +7292,7 @@ mod tests {
                     serial_number: None,
                     transferable: None,
                     memo: None,
+                    listing: None,
                 },
                 Mint {
                     token_id: Some("NFT2".to_string()),
@@ -7284,6 +7303,7 @@ mod tests {
                     serial_number: None,
                     transferable: None,
                     memo: None,
+                    listing: None,
                 },
                 Mint {
                     token_id: Some("NFT3".to_string()),
@@ -7294,6 +7314,7 @@ mod tests {
                     serial_number: None,
                     transferable: None,
                     memo: None,
+                    listing: None,
                 },
                 Mint {
                     token_id: Some("NFT4".to_string()),
@@ -7304,6 +7325,7 @@ mod tests {
                     serial_number: None,
                     transferable: None,
                     memo: None,
+                    listing: None,
                 },
                 Mint {
                     token_id: Some("NFT5".to_string()),
@@ -7314,6 +7336,7 @@ mod tests {
                     serial_number: None,
                     transferable: None,
                     memo: None,
+                    listing: None,
                 },
                 Mint {
                     token_id: Some("NFT6".to_string()),
@@ -7324,6 +7347,7 @@ mod tests {
                     serial_number: None,
                     transferable: None,
                     memo: None,
+                    listing: None,
                 },
             ],
             padding: None,
@@ -7476,7 +7500,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("MyNFT".to_string()),
                     description: Some("metadata".to_string()),
-                    image: Some("uri".to_string()),
+                    image: Some("https://uri".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -7560,7 +7584,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("MyNFT".to_string()),
                 description: Some("privmetadata".to_string()),
-                image: Some("privuri".to_string()),
+                image: Some("https://privuri".to_string()),
                 ..Extension::default()
             }),
         });
@@ -7569,7 +7593,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("MyNFT".to_string()),
                 description: Some("pubmetadata".to_string()),
-                image: Some("puburi".to_string()),
+                image: Some("https://puburi".to_string()),
                 ..Extension::default()
             }),
         });
@@ -8061,7 +8085,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("MyNFT".to_string()),
                     description: Some("metadata".to_string()),
-                    image: Some("uri".to_string()),
+                    image: Some("https://uri".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -8647,6 +8671,7 @@ mod tests {
                     serial_number: None,
                     transferable: None,
                     memo: None,
+                    listing: None,
                 },
                 Mint {
                     token_id: Some("NFT2".to_string()),
@@ -8657,6 +8682,7 @@ mod tests {
                     serial_number: None,
                     transferable: None,
                     memo: None,
+                    listing: None,
                 },
                 Mint {
                     token_id: Some("NFT3".to_string()),
@@ -8667,6 +8693,7 @@ mod tests {
                     serial_number: None,
                     transferable: None,
                     memo: None,
+                    listing: None,
                 },
                 Mint {
                     token_id: Some("NFT4".to_string()),
@@ -8677,6 +8704,7 @@ mod tests {
                     serial_number: None,
                     transferable: None,
                     memo: None,
+                    listing: None,
                 },
                 Mint {
                     token_id: Some("NFT5".to_string()),
@@ -8687,6 +8715,7 @@ mod tests {
                     serial_number: None,
                     transferable: None,
                     memo: None,
+                    listing: None,
                 },
                 Mint {
                     token_id: Some("NFT6".to_string()),
@@ -8697,6 +8726,7 @@ mod tests {
                     serial_number: None,
                     transferable: None,
                     memo: None,
+                    listing: None,
                 },
             ],
             padding: None,
@@ -9377,6 +9407,189 @@ mod tests {
         assert!(cur_minter.is_none());
     }
 
+    // test minter roles
+    #[test]
+    fn test_set_minter_role() {
+        let (init_result, mut deps) =
+            init_helper_with_config(true, true, false, false, true, false, false);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let handle_msg = HandleMsg::AddMinters {
+            minters: vec![HumanAddr("alice".to_string())],
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+
+        // test non admin trying to set a role
+        let handle_msg = HandleMsg::SetMinterRole {
+            minter: HumanAddr("alice".to_string()),
+            may_update_metadata: Some(false),
+            max_tokens: Some(3),
+            allowed_mint_run_ids: Some(vec!["run".to_string()]),
+            expiration: Some(Expiration::AtHeight(20000)),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(
+            error.contains("This is an admin command and can only be run from the admin address")
+        );
+
+        // test setting a role of an address that is not a minter
+        let handle_msg = HandleMsg::SetMinterRole {
+            minter: HumanAddr("bob".to_string()),
+            may_update_metadata: None,
+            max_tokens: None,
+            allowed_mint_run_ids: None,
+            expiration: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("bob is not a minter"));
+
+        // mint a token alice will try to update before she gets a role
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some("NFT1".to_string()),
+            owner: None,
+            public_metadata: None,
+            private_metadata: None,
+            royalty_info: None,
+            serial_number: None,
+            transferable: None,
+            memo: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+
+        let handle_msg = HandleMsg::SetMinterRole {
+            minter: HumanAddr("alice".to_string()),
+            may_update_metadata: Some(false),
+            max_tokens: Some(3),
+            allowed_mint_run_ids: Some(vec!["run".to_string()]),
+            expiration: Some(Expiration::AtHeight(20000)),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+
+        // test a mint-only minter can not update metadata
+        let handle_msg = HandleMsg::SetMetadata {
+            token_id: "NFT1".to_string(),
+            public_metadata: Some(Metadata {
+                token_uri: Some("https://uri".to_string()),
+                extension: None,
+            }),
+            private_metadata: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Not authorized to update metadata of token NFT1"));
+
+        // test minting without a mint run id
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some("NFT2".to_string()),
+            owner: None,
+            public_metadata: None,
+            private_metadata: None,
+            royalty_info: None,
+            serial_number: None,
+            transferable: None,
+            memo: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("The minter may only mint with its allowed mint run IDs"));
+
+        // test minting with a mint run id that is not allowed
+        let clones = |run: &str, quantity: u32| HandleMsg::MintNftClones {
+            mint_run_id: Some(run.to_string()),
+            quantity,
+            owner: None,
+            public_metadata: None,
+            private_metadata: None,
+            royalty_info: None,
+            memo: None,
+            editions: None,
+            padding: None,
+            listing: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), clones("other", 1));
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("The minter is not allowed to mint with mint run ID other"));
+
+        // test the quota
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), clones("run", 4));
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("The minter may only mint 3 more tokens"));
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), clones("run", 2));
+        assert!(handle_result.is_ok());
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), clones("run", 2));
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("The minter may only mint 1 more tokens"));
+
+        // test the role expiration
+        let mut env = mock_env("alice", &[]);
+        env.block.height = 20000;
+        let handle_result = handle(&mut deps, env, clones("run", 1));
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("The minter's authority has expired"));
+
+        // test the admin still mints without limits
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), clones("other", 5));
+        assert!(handle_result.is_ok());
+
+        let query_result = query(&deps, QueryMsg::Minters {});
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::Minters { minters, roles } => {
+                assert_eq!(minters.len(), 2);
+                let admin = roles
+                    .iter()
+                    .find(|r| r.minter == HumanAddr("admin".to_string()))
+                    .unwrap();
+                assert!(admin.may_update_metadata);
+                assert_eq!(admin.max_tokens, None);
+                let alice = roles
+                    .iter()
+                    .find(|r| r.minter == HumanAddr("alice".to_string()))
+                    .unwrap();
+                assert_eq!(
+                    alice,
+                    &MinterInfo {
+                        minter: HumanAddr("alice".to_string()),
+                        may_update_metadata: false,
+                        max_tokens: Some(3),
+                        minted: 2,
+                        allowed_mint_run_ids: Some(vec!["run".to_string()]),
+                        expiration: Some(Expiration::AtHeight(20000)),
+                    }
+                );
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // test removing a minter drops its role
+        let handle_msg = HandleMsg::RemoveMinters {
+            minters: vec![HumanAddr("alice".to_string())],
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let handle_msg = HandleMsg::AddMinters {
+            minters: vec![HumanAddr("alice".to_string())],
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), clones("other", 5));
+        assert!(handle_result.is_ok());
+    }
+
     // test change admin
     #[test]
     fn test_change_admin() {
@@ -10051,7 +10264,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("My1".to_string()),
                 description: Some("Pub 1".to_string()),
-                image: Some("URI 1".to_string()),
+                image: Some("https://URI 1".to_string()),
                 ..Extension::default()
             }),
         });
@@ -10408,7 +10621,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("My1".to_string()),
                 description: Some("Pub 1".to_string()),
-                image: Some("URI 1".to_string()),
+                image: Some("https://URI 1".to_string()),
                 ..Extension::default()
             }),
         });
@@ -10429,7 +10642,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("My2".to_string()),
                 description: Some("Pub 2".to_string()),
-                image: Some("URI 2".to_string()),
+                image: Some("https://URI 2".to_string()),
                 ..Extension::default()
             }),
         });
@@ -11168,7 +11381,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("My3".to_string()),
                 description: Some("Pub 3".to_string()),
-                image: Some("URI 3".to_string()),
+                image: Some("https://URI 3".to_string()),
                 ..Extension::default()
             }),
         });
@@ -11222,7 +11435,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("My1".to_string()),
                 description: Some("Pub 1".to_string()),
-                image: Some("URI 1".to_string()),
+                image: Some("https://URI 1".to_string()),
                 ..Extension::default()
             }),
         });
@@ -11257,7 +11470,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("My2".to_string()),
                 description: Some("Pub 2".to_string()),
-                image: Some("URI 2".to_string()),
+                image: Some("https://URI 2".to_string()),
                 ..Extension::default()
             }),
         });
@@ -11355,7 +11568,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("My3".to_string()),
                 description: Some("Pub 3".to_string()),
-                image: Some("URI 3".to_string()),
+                image: Some("https://URI 3".to_string()),
                 ..Extension::default()
             }),
         });
@@ -11432,7 +11645,7 @@ mod tests {
         let query_result = query(&deps, query_msg);
         let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
         match query_answer {
-            QueryAnswer::SaleInfo { sale_store } => {
+            QueryAnswer::SaleInfo { sale_store, .. } => {
                 assert_eq!(sale_store.token_price.unwrap(), 5);
             }
             _ => panic!("Updated token price not saved to storage"),
@@ -11455,7 +11668,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("BuyMe".to_string()),
                 description: Some("Pub 3".to_string()),
-                image: Some("URI 3".to_string()),
+                image: Some("https://URI 3".to_string()),
                 ..Extension::default()
             }),
         });
@@ -11533,7 +11746,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("2".to_string()),
                 description: Some("Pub 2".to_string()),
-                image: Some("URI 2".to_string()),
+                image: Some("https://URI 2".to_string()),
                 ..Extension::default()
             }),
         });
@@ -11644,4 +11857,3143 @@ mod tests {
         let value_ = "2".to_string();
         assert_eq!(sale_store_.contains(&value_), false);
     }
+
+    #[test]
+    fn test_claim_proceeds() {
+        let (init_result, mut deps) =
+            init_helper_with_config(true, true, false, false, false, false, false);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some("Proceeds".to_string()),
+            owner: Some(HumanAddr("alice".to_string())),
+            public_metadata: None,
+            private_metadata: None,
+            royalty_info: Some(RoyaltyInfo {
+                decimal_places_in_rates: 2,
+                royalties: vec![Royalty {
+                    recipient: HumanAddr("charlie".to_string()),
+                    rate: 10,
+                }],
+            }),
+            serial_number: None,
+            transferable: Some(true),
+            memo: None,
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        for (name, key) in [("alice", "akey"), ("bob", "bkey"), ("charlie", "ckey")].iter() {
+            let handle_msg = HandleMsg::SetViewingKey {
+                key: key.to_string(),
+                padding: None,
+            };
+            let _handle_result = handle(&mut deps, mock_env(*name, &[]), handle_msg);
+        }
+
+        // test nothing to claim before a sale
+        let handle_msg = HandleMsg::ClaimProceeds {
+            denom: "uscrt".to_string(),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("You do not have any uscrt proceeds to claim"));
+
+        // test primary sale does not pay royalties and no funds are sent at sale time
+        let handle_msg = HandleMsg::SetSaleStatus {
+            token_id: "Proceeds".to_string(),
+            sale_status: SaleStatus::ForSale,
+            price: Some(100),
+        };
+        let _handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let handle_msg = HandleMsg::BuyToken {
+            token_id: "Proceeds".to_string(),
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &coins(100, "uscrt")), handle_msg);
+        assert!(handle_result.unwrap().messages.is_empty());
+        let query_msg = QueryMsg::PendingProceeds {
+            address: HumanAddr("alice".to_string()),
+            viewing_key: "akey".to_string(),
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::PendingProceeds { balances } => {
+                assert_eq!(balances, coins(98, "uscrt"));
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // test secondary sale pays royalties before the fee
+        let handle_msg = HandleMsg::SetSaleStatus {
+            token_id: "Proceeds".to_string(),
+            sale_status: SaleStatus::ForSale,
+            price: Some(1000),
+        };
+        let _handle_result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
+        let handle_msg = HandleMsg::BuyToken {
+            token_id: "Proceeds".to_string(),
+        };
+        let _handle_result = handle(
+            &mut deps,
+            mock_env("alice", &coins(1000, "uscrt")),
+            handle_msg,
+        );
+        let query_msg = QueryMsg::PendingProceeds {
+            address: HumanAddr("charlie".to_string()),
+            viewing_key: "ckey".to_string(),
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::PendingProceeds { balances } => {
+                assert_eq!(balances, coins(100, "uscrt"));
+            }
+            _ => panic!("unexpected"),
+        }
+        let query_msg = QueryMsg::PendingProceeds {
+            address: HumanAddr("bob".to_string()),
+            viewing_key: "bkey".to_string(),
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::PendingProceeds { balances } => {
+                assert_eq!(balances, coins(882, "uscrt"));
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // test wrong viewing key
+        let query_msg = QueryMsg::PendingProceeds {
+            address: HumanAddr("bob".to_string()),
+            viewing_key: "akey".to_string(),
+        };
+        let query_result = query(&deps, query_msg);
+        let error = extract_error_msg(query_result);
+        assert!(error.contains("Wrong viewing key"));
+
+        // test claiming sends the balance and clears it
+        let handle_msg = HandleMsg::ClaimProceeds {
+            denom: "uscrt".to_string(),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
+        let messages = handle_result.unwrap().messages;
+        assert_eq!(
+            messages,
+            vec![CosmosMsg::Bank(BankMsg::Send {
+                from_address: HumanAddr(MOCK_CONTRACT_ADDR.to_string()),
+                to_address: HumanAddr("bob".to_string()),
+                amount: coins(882, "uscrt"),
+            })]
+        );
+        let query_msg = QueryMsg::PendingProceeds {
+            address: HumanAddr("bob".to_string()),
+            viewing_key: "bkey".to_string(),
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::PendingProceeds { balances } => {
+                assert!(balances.is_empty());
+            }
+            _ => panic!("unexpected"),
+        }
+        let handle_msg = HandleMsg::ClaimProceeds {
+            denom: "uscrt".to_string(),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("You do not have any uscrt proceeds to claim"));
+    }
+
+    #[test]
+    fn test_withdraw_earned_fees() {
+        let (init_result, mut deps) =
+            init_helper_with_config(true, true, false, false, false, false, false);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some("Fees".to_string()),
+            owner: Some(HumanAddr("alice".to_string())),
+            public_metadata: None,
+            private_metadata: None,
+            royalty_info: None,
+            serial_number: None,
+            transferable: Some(true),
+            memo: None,
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        for (name, key) in [("admin", "key"), ("alice", "akey")].iter() {
+            let handle_msg = HandleMsg::SetViewingKey {
+                key: key.to_string(),
+                padding: None,
+            };
+            let _handle_result = handle(&mut deps, mock_env(*name, &[]), handle_msg);
+        }
+
+        // test nothing to withdraw before any sale
+        let handle_msg = HandleMsg::Withdraw {
+            receiver_addr: HumanAddr("treasury".to_string()),
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("There are no earned fees to withdraw"));
+
+        let handle_msg = HandleMsg::SetSaleStatus {
+            token_id: "Fees".to_string(),
+            sale_status: SaleStatus::ForSale,
+            price: Some(100),
+        };
+        let _handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let handle_msg = HandleMsg::BuyToken {
+            token_id: "Fees".to_string(),
+        };
+        let _handle_result = handle(&mut deps, mock_env("bob", &coins(100, "uscrt")), handle_msg);
+        deps.querier
+            .update_balance(MOCK_CONTRACT_ADDR, coins(100, "uscrt"));
+
+        // test only the admin can query the accounting
+        let query_msg = QueryMsg::Accounting {
+            address: HumanAddr("alice".to_string()),
+            viewing_key: "akey".to_string(),
+        };
+        let query_result = query(&deps, query_msg);
+        let error = extract_error_msg(query_result);
+        assert!(error.contains("This is an admin command"));
+
+        let query_msg = QueryMsg::Accounting {
+            address: HumanAddr("admin".to_string()),
+            viewing_key: "key".to_string(),
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::Accounting { accounts } => {
+                assert_eq!(
+                    accounts,
+                    vec![DenomAccounting {
+                        denom: "uscrt".to_string(),
+                        balance: Uint128(100),
+                        escrowed: Uint128(98),
+                        earned_fees: Uint128(2),
+                        surplus: Uint128(0),
+                        shortfall: Uint128(0),
+                    }]
+                );
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // test only the admin can withdraw
+        let handle_msg = HandleMsg::Withdraw {
+            receiver_addr: HumanAddr("alice".to_string()),
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("This is an admin command"));
+
+        // test withdraw only sends the earned fees, not the escrowed proceeds
+        let handle_msg = HandleMsg::Withdraw {
+            receiver_addr: HumanAddr("treasury".to_string()),
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let messages = handle_result.unwrap().messages;
+        assert_eq!(
+            messages,
+            vec![CosmosMsg::Bank(BankMsg::Send {
+                from_address: HumanAddr(MOCK_CONTRACT_ADDR.to_string()),
+                to_address: HumanAddr("treasury".to_string()),
+                amount: coins(2, "uscrt"),
+            })]
+        );
+        deps.querier
+            .update_balance(MOCK_CONTRACT_ADDR, coins(98, "uscrt"));
+        let handle_msg = HandleMsg::Withdraw {
+            receiver_addr: HumanAddr("treasury".to_string()),
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("There are no earned fees to withdraw"));
+
+        // test claiming proceeds releases them from escrow
+        let handle_msg = HandleMsg::ClaimProceeds {
+            denom: "uscrt".to_string(),
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        deps.querier.update_balance(MOCK_CONTRACT_ADDR, vec![]);
+        let query_msg = QueryMsg::Accounting {
+            address: HumanAddr("admin".to_string()),
+            viewing_key: "key".to_string(),
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::Accounting { accounts } => {
+                assert!(accounts.is_empty());
+            }
+            _ => panic!("unexpected"),
+        }
+    }
+
+    #[test]
+    fn test_collection_stats() {
+        let mut deps = mock_dependencies(20, &[]);
+        let init_msg = InitMsg {
+            name: "sec721".to_string(),
+            symbol: "S721".to_string(),
+            admin: Some(HumanAddr("admin".to_string())),
+            entropy: "We're going to need a bigger boat".to_string(),
+            royalty_info: None,
+            config: Some(InitConfig {
+                public_sale_stats: Some(false),
+                ..InitConfig::default()
+            }),
+            post_init_callback: None,
+        };
+        let init_result = init(&mut deps, mock_env("instantiator", &[]), init_msg);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let handle_msg = HandleMsg::SetViewingKey {
+            key: "akey".to_string(),
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+
+        let handle_msg = HandleMsg::MintNftClones {
+            mint_run_id: Some("run".to_string()),
+            quantity: 2,
+            owner: Some(HumanAddr("alice".to_string())),
+            public_metadata: None,
+            private_metadata: None,
+            royalty_info: None,
+            memo: None,
+            editions: None,
+            padding: None,
+            listing: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let handle_msg = HandleMsg::MintNftClones {
+            mint_run_id: Some("other".to_string()),
+            quantity: 1,
+            owner: Some(HumanAddr("alice".to_string())),
+            public_metadata: None,
+            private_metadata: None,
+            royalty_info: None,
+            memo: None,
+            editions: None,
+            padding: None,
+            listing: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some("solo".to_string()),
+            owner: Some(HumanAddr("alice".to_string())),
+            public_metadata: None,
+            private_metadata: None,
+            royalty_info: None,
+            serial_number: None,
+            transferable: Some(true),
+            memo: None,
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        for (id, price) in [("0", 50u32), ("1", 30), ("2", 70), ("solo", 10)].iter() {
+            let handle_msg = HandleMsg::SetSaleStatus {
+                token_id: id.to_string(),
+                sale_status: SaleStatus::ForSale,
+                price: Some(*price),
+            };
+            let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+            assert!(handle_result.is_ok());
+        }
+        let handle_msg = HandleMsg::BuyToken {
+            token_id: "0".to_string(),
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &coins(50, "uscrt")), handle_msg);
+        assert!(handle_result.is_ok());
+
+        // test private stats can only be viewed by minters
+        let query_msg = QueryMsg::CollectionStats {
+            mint_run: None,
+            viewer: None,
+        };
+        let query_result = query(&deps, query_msg);
+        let error = extract_error_msg(query_result);
+        assert!(error.contains("The sale stats of this contract are private"));
+
+        let query_msg = QueryMsg::CollectionStats {
+            mint_run: Some(MintRunRef {
+                run_id: "run".to_string(),
+                run_number: 1,
+            }),
+            viewer: Some(ViewerInfo {
+                address: HumanAddr("admin".to_string()),
+                viewing_key: "akey".to_string(),
+            }),
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::CollectionStats {
+                collection,
+                mint_run,
+            } => {
+                assert_eq!(
+                    collection,
+                    SaleStats {
+                        volume: Uint128(50),
+                        sale_count: 1,
+                        last_sale_price: Some(Uint128(50)),
+                        highest_sale: Some(Uint128(50)),
+                        floor_price: Some(Uint128(10)),
+                    }
+                );
+                assert_eq!(
+                    mint_run,
+                    Some(SaleStats {
+                        volume: Uint128(50),
+                        sale_count: 1,
+                        last_sale_price: Some(Uint128(50)),
+                        highest_sale: Some(Uint128(50)),
+                        floor_price: Some(Uint128(30)),
+                    })
+                );
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // test a sale outside of a mint run only updates the collection stats
+        let handle_msg = HandleMsg::BuyToken {
+            token_id: "solo".to_string(),
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &coins(10, "uscrt")), handle_msg);
+        assert!(handle_result.is_ok());
+        let query_msg = QueryMsg::CollectionStats {
+            mint_run: Some(MintRunRef {
+                run_id: "run".to_string(),
+                run_number: 1,
+            }),
+            viewer: Some(ViewerInfo {
+                address: HumanAddr("admin".to_string()),
+                viewing_key: "akey".to_string(),
+            }),
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::CollectionStats {
+                collection,
+                mint_run,
+            } => {
+                assert_eq!(
+                    collection,
+                    SaleStats {
+                        volume: Uint128(60),
+                        sale_count: 2,
+                        last_sale_price: Some(Uint128(10)),
+                        highest_sale: Some(Uint128(50)),
+                        floor_price: Some(Uint128(30)),
+                    }
+                );
+                assert_eq!(mint_run.unwrap().sale_count, 1);
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // test the first run of a different mint run ID keeps separate stats
+        let handle_msg = HandleMsg::BuyToken {
+            token_id: "2".to_string(),
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &coins(70, "uscrt")), handle_msg);
+        assert!(handle_result.is_ok());
+        let handle_msg = HandleMsg::SetSaleStatus {
+            token_id: "1".to_string(),
+            sale_status: SaleStatus::NotForSale,
+            price: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        for (run_id, volume) in [("run", 50u128), ("other", 70)].iter() {
+            let query_msg = QueryMsg::CollectionStats {
+                mint_run: Some(MintRunRef {
+                    run_id: run_id.to_string(),
+                    run_number: 1,
+                }),
+                viewer: Some(ViewerInfo {
+                    address: HumanAddr("admin".to_string()),
+                    viewing_key: "akey".to_string(),
+                }),
+            };
+            let query_result = query(&deps, query_msg);
+            let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+            match query_answer {
+                QueryAnswer::CollectionStats {
+                    collection,
+                    mint_run,
+                } => {
+                    // no uscrt listings remain after the last one is delisted
+                    assert_eq!(collection.floor_price, None);
+                    let mint_run = mint_run.unwrap();
+                    assert_eq!(mint_run.sale_count, 1);
+                    assert_eq!(mint_run.volume, Uint128(*volume));
+                    assert_eq!(mint_run.floor_price, None);
+                }
+                _ => panic!("unexpected"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_public_mint() {
+        let (init_result, mut deps) =
+            init_helper_with_config(true, true, false, false, false, false, false);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+
+        // test minting before the public mint is open
+        let handle_msg = HandleMsg::PublicMint {
+            quantity: 1,
+            merkle_proof: None,
+            entropy: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &coins(10, "uscrt")), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("The public mint is not open"));
+
+        // test only the admin can configure the public mint
+        let handle_msg = HandleMsg::SetPublicMint {
+            enabled: true,
+            price: Uint128(10),
+            max_supply: 3,
+            per_wallet_limit: Some(2),
+            random_assignment: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("This is an admin command"));
+        // test random assignment needs sealed metadata to keep the drawn templates hidden
+        let handle_msg = HandleMsg::SetPublicMint {
+            enabled: true,
+            price: Uint128(10),
+            max_supply: 3,
+            per_wallet_limit: Some(2),
+            random_assignment: Some(true),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Random assignment requires sealed metadata to be enabled"));
+        let handle_msg = HandleMsg::SetPublicMint {
+            enabled: true,
+            price: Uint128(10),
+            max_supply: 3,
+            per_wallet_limit: Some(2),
+            random_assignment: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+
+        let template = |name: &str| MintTemplate {
+            public_metadata: Some(Metadata {
+                token_uri: None,
+                extension: Some(Extension {
+                    name: Some(name.to_string()),
+                    ..Extension::default()
+                }),
+            }),
+            private_metadata: None,
+        };
+        let handle_msg = HandleMsg::AddMintTemplates {
+            templates: vec![template("A"), template("B")],
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+        // the default royalties are copied to each minted token
+        let handle_msg = HandleMsg::SetRoyaltyInfo {
+            token_id: None,
+            royalty_info: Some(RoyaltyInfo {
+                decimal_places_in_rates: 2,
+                royalties: vec![Royalty {
+                    recipient: HumanAddr("charlie".to_string()),
+                    rate: 10,
+                }],
+            }),
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+
+        // test the price must be paid
+        let handle_msg = HandleMsg::PublicMint {
+            quantity: 2,
+            merkle_proof: None,
+            entropy: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &coins(10, "uscrt")), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Insufficient funds provided"));
+
+        // test the minter's entropy advances the private seed without random assignment
+        let seed_before: Vec<u8> = load(&deps.storage, PRNG_SEED_KEY).unwrap();
+        let handle_msg = HandleMsg::PublicMint {
+            quantity: 2,
+            merkle_proof: None,
+            entropy: Some("secret".to_string()),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &coins(20, "uscrt")), handle_msg);
+        let handle_answer: HandleAnswer =
+            from_binary(&handle_result.unwrap().data.unwrap()).unwrap();
+        match handle_answer {
+            HandleAnswer::PublicMint { token_ids } => {
+                assert_eq!(token_ids, vec!["0".to_string(), "1".to_string()]);
+            }
+            _ => panic!("unexpected"),
+        }
+        let seed_after: Vec<u8> = load(&deps.storage, PRNG_SEED_KEY).unwrap();
+        assert_ne!(seed_before, seed_after);
+        let bob_raw = deps
+            .api
+            .canonical_address(&HumanAddr("bob".to_string()))
+            .unwrap();
+        let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
+        let token: Token = json_load(&info_store, &1u32.to_le_bytes()).unwrap();
+        assert_eq!(token.owner, bob_raw);
+        let query_result = query(
+            &deps,
+            QueryMsg::NftInfo {
+                token_id: "1".to_string(),
+            },
+        );
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::NftInfo { extension, .. } => {
+                assert_eq!(extension.unwrap().name, Some("B".to_string()));
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // test the proceeds are split like a primary sale
+        let handle_msg = HandleMsg::SetViewingKey {
+            key: "key".to_string(),
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let query_msg = QueryMsg::PendingProceeds {
+            address: HumanAddr("admin".to_string()),
+            viewing_key: "key".to_string(),
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::PendingProceeds { balances } => {
+                assert_eq!(balances, coins(19, "uscrt"));
+            }
+            _ => panic!("unexpected"),
+        }
+        let query_msg = QueryMsg::CollectionStats {
+            mint_run: None,
+            viewer: Some(ViewerInfo {
+                address: HumanAddr("admin".to_string()),
+                viewing_key: "key".to_string(),
+            }),
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::CollectionStats { collection, .. } => {
+                assert_eq!(collection.sale_count, 2);
+                assert_eq!(collection.volume, Uint128(20));
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // test the first resale after a public mint pays royalties
+        let handle_msg = HandleMsg::SetSaleStatus {
+            token_id: "0".to_string(),
+            sale_status: SaleStatus::ForSale,
+            price: Some(100),
+        };
+        let _handle_result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
+        let handle_msg = HandleMsg::BuyToken {
+            token_id: "0".to_string(),
+        };
+        let handle_result = handle(
+            &mut deps,
+            mock_env("david", &coins(100, "uscrt")),
+            handle_msg,
+        );
+        assert!(handle_result.is_ok());
+        let handle_msg = HandleMsg::SetViewingKey {
+            key: "ckey".to_string(),
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("charlie", &[]), handle_msg);
+        let query_msg = QueryMsg::PendingProceeds {
+            address: HumanAddr("charlie".to_string()),
+            viewing_key: "ckey".to_string(),
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::PendingProceeds { balances } => {
+                assert_eq!(balances, coins(10, "uscrt"));
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // test raising the default after the sale does not change the sold token's royalties
+        let handle_msg = HandleMsg::SetRoyaltyInfo {
+            token_id: None,
+            royalty_info: Some(RoyaltyInfo {
+                decimal_places_in_rates: 2,
+                royalties: vec![Royalty {
+                    recipient: HumanAddr("charlie".to_string()),
+                    rate: 20,
+                }],
+            }),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+        let handle_msg = HandleMsg::SetSaleStatus {
+            token_id: "0".to_string(),
+            sale_status: SaleStatus::ForSale,
+            price: Some(100),
+        };
+        let _handle_result = handle(&mut deps, mock_env("david", &[]), handle_msg);
+        let handle_msg = HandleMsg::BuyToken {
+            token_id: "0".to_string(),
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &coins(100, "uscrt")), handle_msg);
+        assert!(handle_result.is_ok());
+        let query_msg = QueryMsg::PendingProceeds {
+            address: HumanAddr("charlie".to_string()),
+            viewing_key: "ckey".to_string(),
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::PendingProceeds { balances } => {
+                assert_eq!(balances, coins(20, "uscrt"));
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // test the per wallet limit
+        let handle_msg = HandleMsg::PublicMint {
+            quantity: 1,
+            merkle_proof: None,
+            entropy: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &coins(10, "uscrt")), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("An address may only mint 2 tokens through the public mint"));
+
+        // test running out of templates
+        let handle_msg = HandleMsg::PublicMint {
+            quantity: 1,
+            merkle_proof: None,
+            entropy: None,
+            padding: None,
+        };
+        let handle_result = handle(
+            &mut deps,
+            mock_env("alice", &coins(10, "uscrt")),
+            handle_msg,
+        );
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Not enough metadata templates have been uploaded for this mint"));
+
+        // test the max supply
+        let handle_msg = HandleMsg::AddMintTemplates {
+            templates: vec![template("C"), template("D")],
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let handle_msg = HandleMsg::PublicMint {
+            quantity: 2,
+            merkle_proof: None,
+            entropy: None,
+            padding: None,
+        };
+        let handle_result = handle(
+            &mut deps,
+            mock_env("alice", &coins(20, "uscrt")),
+            handle_msg,
+        );
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Only 1 tokens remain in the public mint"));
+
+        let query_result = query(&deps, QueryMsg::PublicMintInfo {});
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::PublicMintInfo { info } => {
+                assert_eq!(
+                    info,
+                    PublicMintInfo {
+                        enabled: true,
+                        price: Uint128(10),
+                        max_supply: 3,
+                        per_wallet_limit: Some(2),
+                        minted: 2,
+                        templates_remaining: 2,
+                        random_assignment: false,
+                        phases: vec![],
+                        active_phase: None,
+                    }
+                );
+            }
+            _ => panic!("unexpected"),
+        }
+    }
+
+    #[test]
+    fn test_mint_phases() {
+        let (init_result, mut deps) =
+            init_helper_with_config(true, true, false, false, false, false, false);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let handle_msg = HandleMsg::SetPublicMint {
+            enabled: true,
+            price: Uint128(10),
+            max_supply: 10,
+            per_wallet_limit: None,
+            random_assignment: None,
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let templates = (0..4)
+            .map(|_| MintTemplate {
+                public_metadata: None,
+                private_metadata: None,
+            })
+            .collect();
+        let handle_msg = HandleMsg::AddMintTemplates {
+            templates,
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+
+        // allowlist of bob and charlie
+        let bob_leaf = sha_256(b"bob");
+        let charlie_leaf = sha_256(b"charlie");
+        let mut pair = Vec::new();
+        if bob_leaf <= charlie_leaf {
+            pair.extend_from_slice(&bob_leaf);
+            pair.extend_from_slice(&charlie_leaf);
+        } else {
+            pair.extend_from_slice(&charlie_leaf);
+            pair.extend_from_slice(&bob_leaf);
+        }
+        let root = Binary::from(sha_256(&pair).to_vec());
+
+        // test a bad merkle root
+        let handle_msg = HandleMsg::SetMintPhases {
+            phases: vec![MintPhase {
+                name: "og".to_string(),
+                start: None,
+                end: Expiration::Never,
+                price: Uint128(5),
+                per_wallet_limit: None,
+                merkle_root: Some(Binary::from(b"short".to_vec())),
+            }],
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("The Merkle root of phase og must be a 32 byte sha256 hash"));
+
+        let phases = vec![
+            MintPhase {
+                name: "og".to_string(),
+                start: None,
+                end: Expiration::AtHeight(20000),
+                price: Uint128(5),
+                per_wallet_limit: Some(1),
+                merkle_root: Some(root),
+            },
+            MintPhase {
+                name: "public".to_string(),
+                start: Some(Expiration::AtHeight(20000)),
+                end: Expiration::AtHeight(30000),
+                price: Uint128(10),
+                per_wallet_limit: None,
+                merkle_root: None,
+            },
+        ];
+        let handle_msg = HandleMsg::SetMintPhases {
+            phases: phases.clone(),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("This is an admin command"));
+        let handle_msg = HandleMsg::SetMintPhases {
+            phases: phases.clone(),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+
+        let query_result = query(&deps, QueryMsg::PublicMintInfo {});
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::PublicMintInfo { info } => {
+                assert_eq!(info.phases, phases);
+                assert_eq!(info.active_phase, Some("og".to_string()));
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // test an address not on the allowlist
+        let handle_msg = HandleMsg::PublicMint {
+            quantity: 1,
+            merkle_proof: Some(vec![Binary::from(bob_leaf.to_vec())]),
+            entropy: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &coins(5, "uscrt")), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("You are not on the allowlist for the og phase"));
+
+        // test the phase price and allowlist
+        let handle_msg = HandleMsg::PublicMint {
+            quantity: 1,
+            merkle_proof: Some(vec![Binary::from(charlie_leaf.to_vec())]),
+            entropy: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &coins(10, "uscrt")), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Funds sent exceeds funds needed"));
+        let handle_msg = HandleMsg::PublicMint {
+            quantity: 1,
+            merkle_proof: Some(vec![Binary::from(charlie_leaf.to_vec())]),
+            entropy: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &coins(5, "uscrt")), handle_msg);
+        assert!(handle_result.is_ok());
+
+        // test the phase's per wallet limit
+        let handle_msg = HandleMsg::PublicMint {
+            quantity: 1,
+            merkle_proof: Some(vec![Binary::from(charlie_leaf.to_vec())]),
+            entropy: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &coins(5, "uscrt")), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("An address may only mint 1 tokens during the og phase"));
+
+        // test phase names must be unique
+        let handle_msg = HandleMsg::SetMintPhases {
+            phases: vec![phases[0].clone(), phases[0].clone()],
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Mint phase name og is used more than once"));
+
+        // test reordering the phases keeps the mint counts of each phase
+        let handle_msg = HandleMsg::SetMintPhases {
+            phases: vec![phases[1].clone(), phases[0].clone()],
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+        let handle_msg = HandleMsg::PublicMint {
+            quantity: 1,
+            merkle_proof: Some(vec![Binary::from(charlie_leaf.to_vec())]),
+            entropy: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &coins(5, "uscrt")), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("An address may only mint 1 tokens during the og phase"));
+
+        // test the public phase does not need a proof and has its own price and limit
+        let mut env = mock_env("bob", &coins(20, "uscrt"));
+        env.block.height = 20000;
+        let handle_msg = HandleMsg::PublicMint {
+            quantity: 2,
+            merkle_proof: None,
+            entropy: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, env, handle_msg);
+        assert!(handle_result.is_ok());
+
+        // test minting after all phases have ended
+        let mut env = mock_env("alice", &coins(10, "uscrt"));
+        env.block.height = 30000;
+        let handle_msg = HandleMsg::PublicMint {
+            quantity: 1,
+            merkle_proof: None,
+            entropy: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, env, handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("No mint phase is currently active"));
+    }
+
+    #[test]
+    fn test_random_public_mint() {
+        let (init_result, mut deps) =
+            init_helper_with_config(true, true, true, false, false, false, false);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let handle_msg = HandleMsg::SetPublicMint {
+            enabled: true,
+            price: Uint128(0),
+            max_supply: 4,
+            per_wallet_limit: None,
+            random_assignment: Some(true),
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let templates = ["A", "B", "C", "D"]
+            .iter()
+            .map(|name| MintTemplate {
+                public_metadata: None,
+                private_metadata: Some(Metadata {
+                    token_uri: None,
+                    extension: Some(Extension {
+                        name: Some(name.to_string()),
+                        ..Extension::default()
+                    }),
+                }),
+            })
+            .collect();
+        let handle_msg = HandleMsg::AddMintTemplates {
+            templates,
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+
+        let handle_msg = HandleMsg::PublicMint {
+            quantity: 1,
+            merkle_proof: None,
+            entropy: Some("some entropy".to_string()),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+        let handle_msg = HandleMsg::PublicMint {
+            quantity: 3,
+            merkle_proof: None,
+            entropy: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+
+        // test the private metadata is sealed until revealed
+        let handle_msg = HandleMsg::SetViewingKey {
+            key: "akey".to_string(),
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let query_msg = QueryMsg::PrivateMetadata {
+            token_id: "0".to_string(),
+            viewer: Some(ViewerInfo {
+                address: HumanAddr("alice".to_string()),
+                viewing_key: "akey".to_string(),
+            }),
+        };
+        let query_result = query(&deps, query_msg);
+        let error = extract_error_msg(query_result);
+        assert!(error.contains("Sealed metadata must be unwrapped by calling Reveal"));
+
+        // test every template was used exactly once
+        let mut names = Vec::new();
+        for id in ["0", "1", "2", "3"].iter() {
+            let handle_msg = HandleMsg::Reveal {
+                token_id: id.to_string(),
+                padding: None,
+            };
+            let _handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+            let query_result = query(
+                &deps,
+                QueryMsg::NftInfo {
+                    token_id: id.to_string(),
+                },
+            );
+            let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+            match query_answer {
+                QueryAnswer::NftInfo { extension, .. } => {
+                    names.push(extension.unwrap().name.unwrap());
+                }
+                _ => panic!("unexpected"),
+            }
+        }
+        names.sort();
+        assert_eq!(names, vec!["A", "B", "C", "D"]);
+    }
+
+    #[test]
+    fn test_reveal_collection() {
+        let (init_result, mut deps) =
+            init_helper_with_config(true, true, true, false, false, false, false);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let metadata_hash = Binary::from(sha_256(b"metadata").to_vec());
+        let seed_hash = Binary::from(sha_256(b"reveal").to_vec());
+
+        // test only the admin can commit
+        let handle_msg = HandleMsg::CommitReveal {
+            metadata_hash: metadata_hash.clone(),
+            seed_hash: seed_hash.clone(),
+            supply: 5,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("This is an admin command"));
+        let handle_msg = HandleMsg::CommitReveal {
+            metadata_hash: metadata_hash.clone(),
+            seed_hash: seed_hash.clone(),
+            supply: 5,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+        let handle_msg = HandleMsg::CommitReveal {
+            metadata_hash: metadata_hash.clone(),
+            seed_hash: seed_hash.clone(),
+            supply: 5,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("A reveal commitment has already been made"));
+
+        for i in 0..4 {
+            let handle_msg = HandleMsg::MintNft {
+                token_id: Some(format!("{}", i)),
+                owner: Some(HumanAddr("alice".to_string())),
+                public_metadata: None,
+                private_metadata: Some(Metadata {
+                    token_uri: None,
+                    extension: Some(Extension {
+                        name: Some(format!("T{}", i)),
+                        ..Extension::default()
+                    }),
+                }),
+                royalty_info: None,
+                serial_number: None,
+                transferable: None,
+                memo: None,
+                padding: None,
+            };
+            let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        }
+
+        // test owners can not reveal individually
+        let handle_msg = HandleMsg::Reveal {
+            token_id: "0".to_string(),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("This collection is revealed by the admin with RevealCollection"));
+
+        // test the seed must match and the collection must be minted
+        let handle_msg = HandleMsg::RevealCollection {
+            seed: Some(Binary::from(b"wrong".to_vec())),
+            limit: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("The seed does not match the one committed to"));
+        let handle_msg = HandleMsg::RevealCollection {
+            seed: Some(Binary::from(b"reveal".to_vec())),
+            limit: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("All 5 tokens of the collection must be minted"));
+
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some("4".to_string()),
+            owner: Some(HumanAddr("alice".to_string())),
+            public_metadata: None,
+            private_metadata: Some(Metadata {
+                token_uri: None,
+                extension: Some(Extension {
+                    name: Some("T4".to_string()),
+                    ..Extension::default()
+                }),
+            }),
+            royalty_info: None,
+            serial_number: None,
+            transferable: None,
+            memo: None,
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+
+        // test revealing in batches
+        let handle_msg = HandleMsg::RevealCollection {
+            seed: Some(Binary::from(b"reveal".to_vec())),
+            limit: Some(2),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let handle_answer: HandleAnswer =
+            from_binary(&handle_result.unwrap().data.unwrap()).unwrap();
+        match handle_answer {
+            HandleAnswer::RevealCollection {
+                revealed,
+                remaining,
+            } => {
+                assert_eq!(revealed, 2);
+                assert_eq!(remaining, 3);
+            }
+            _ => panic!("unexpected"),
+        }
+        let handle_msg = HandleMsg::RevealCollection {
+            seed: None,
+            limit: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let handle_answer: HandleAnswer =
+            from_binary(&handle_result.unwrap().data.unwrap()).unwrap();
+        match handle_answer {
+            HandleAnswer::RevealCollection {
+                revealed,
+                remaining,
+            } => {
+                assert_eq!(revealed, 5);
+                assert_eq!(remaining, 0);
+            }
+            _ => panic!("unexpected"),
+        }
+
+        let query_result = query(&deps, QueryMsg::RevealCommitment {});
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        let offset = match query_answer {
+            QueryAnswer::RevealCommitment { commitment } => {
+                let commitment: RevealCommitment = commitment.unwrap();
+                assert_eq!(commitment.metadata_hash, metadata_hash);
+                assert_eq!(commitment.seed, Some(Binary::from(b"reveal".to_vec())));
+                assert_eq!(commitment.revealed, 5);
+                assert_eq!(commitment.offset, Some(1));
+                commitment.offset.unwrap()
+            }
+            _ => panic!("unexpected"),
+        };
+
+        // test every token received the metadata of the token offset from it
+        for i in 0..5u32 {
+            let query_result = query(
+                &deps,
+                QueryMsg::NftInfo {
+                    token_id: format!("{}", i),
+                },
+            );
+            let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+            match query_answer {
+                QueryAnswer::NftInfo { extension, .. } => {
+                    assert_eq!(
+                        extension.unwrap().name,
+                        Some(format!("T{}", (i + offset) % 5))
+                    );
+                }
+                _ => panic!("unexpected"),
+            }
+        }
+
+        // test the commitment must be made before the first mint
+        let (init_result, mut deps) =
+            init_helper_with_config(true, true, true, false, false, false, false);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some("0".to_string()),
+            owner: Some(HumanAddr("alice".to_string())),
+            public_metadata: None,
+            private_metadata: None,
+            royalty_info: None,
+            serial_number: None,
+            transferable: None,
+            memo: None,
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let handle_msg = HandleMsg::CommitReveal {
+            metadata_hash: metadata_hash.clone(),
+            seed_hash: seed_hash.clone(),
+            supply: 5,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("The reveal commitment must be made before any tokens are minted"));
+
+        // test the admin can not change the offset by picking the block of the reveal
+        let (init_result, mut deps) =
+            init_helper_with_config(true, true, true, false, false, false, false);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let handle_msg = HandleMsg::CommitReveal {
+            metadata_hash: metadata_hash.clone(),
+            seed_hash,
+            supply: 5,
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        for i in 0..5 {
+            let handle_msg = HandleMsg::MintNft {
+                token_id: Some(format!("{}", i)),
+                owner: Some(HumanAddr("alice".to_string())),
+                public_metadata: None,
+                private_metadata: Some(Metadata {
+                    token_uri: None,
+                    extension: Some(Extension {
+                        name: Some(format!("T{}", i)),
+                        ..Extension::default()
+                    }),
+                }),
+                royalty_info: None,
+                serial_number: None,
+                transferable: None,
+                memo: None,
+                padding: None,
+            };
+            let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        }
+        let handle_msg = HandleMsg::RevealCollection {
+            seed: Some(Binary::from(b"reveal".to_vec())),
+            limit: None,
+            padding: None,
+        };
+        let mut env = mock_env("admin", &[]);
+        env.block.height += 1000;
+        env.block.time += 6000;
+        let _handle_result = handle(&mut deps, env, handle_msg);
+        let query_result = query(&deps, QueryMsg::RevealCommitment {});
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::RevealCommitment { commitment } => {
+                assert_eq!(commitment.unwrap().offset, Some(offset));
+            }
+            _ => panic!("unexpected"),
+        }
+    }
+
+    #[test]
+    fn test_mint_and_list() {
+        let (init_result, mut deps) =
+            init_helper_with_config(true, true, false, false, false, false, false);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let mint = |id: &str, listing: Option<MintListing>, transferable: bool| Mint {
+            token_id: Some(id.to_string()),
+            owner: Some(HumanAddr("alice".to_string())),
+            public_metadata: None,
+            private_metadata: None,
+            serial_number: None,
+            royalty_info: None,
+            transferable: Some(transferable),
+            memo: None,
+            listing,
+        };
+        let handle_msg = HandleMsg::AddMinters {
+            minters: vec![HumanAddr("alice".to_string())],
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+
+        // test a token can only be listed at mint when it is minted to the minter
+        let handle_msg = HandleMsg::BatchMintNft {
+            mints: vec![mint(
+                "Gift",
+                Some(MintListing {
+                    price: 10,
+                    denom: None,
+                    expiration: None,
+                }),
+                true,
+            )],
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Only tokens minted to the minter can be listed for sale at mint"));
+
+        // test non-transferable tokens can not be listed
+        let handle_msg = HandleMsg::BatchMintNft {
+            mints: vec![mint(
+                "NT",
+                Some(MintListing {
+                    price: 10,
+                    denom: None,
+                    expiration: None,
+                }),
+                false,
+            )],
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Non-transferable tokens can not be listed for sale"));
+
+        // test a zero price
+        let handle_msg = HandleMsg::BatchMintNft {
+            mints: vec![mint(
+                "Zero",
+                Some(MintListing {
+                    price: 0,
+                    denom: None,
+                    expiration: None,
+                }),
+                true,
+            )],
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Invalid input! Price cannot be set to 0."));
+
+        let handle_msg = HandleMsg::BatchMintNft {
+            mints: vec![
+                mint(
+                    "Listed",
+                    Some(MintListing {
+                        price: 10,
+                        denom: None,
+                        expiration: None,
+                    }),
+                    true,
+                ),
+                mint(
+                    "Atom",
+                    Some(MintListing {
+                        price: 5,
+                        denom: Some("uatom".to_string()),
+                        expiration: Some(Expiration::AtHeight(13000)),
+                    }),
+                    true,
+                ),
+                mint("Unlisted", None, true),
+            ],
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+        let query_result = query(&deps, QueryMsg::TokensForSale {});
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::TokensForSale { for_sale, .. } => {
+                assert_eq!(for_sale, vec!["Listed".to_string(), "Atom".to_string()]);
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // test buying a token listed at mint
+        let handle_msg = HandleMsg::BuyToken {
+            token_id: "Listed".to_string(),
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &coins(10, "uscrt")), handle_msg);
+        assert!(handle_result.is_ok());
+
+        // test the listing denom is enforced
+        let handle_msg = HandleMsg::BuyToken {
+            token_id: "Atom".to_string(),
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &coins(5, "uscrt")), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Insufficient funds provided"));
+
+        // test an expired listing can not be bought
+        let mut env = mock_env("bob", &coins(5, "uatom"));
+        env.block.height = 13000;
+        let handle_msg = HandleMsg::BuyToken {
+            token_id: "Atom".to_string(),
+        };
+        let handle_result = handle(&mut deps, env, handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("This listing has expired"));
+
+        let handle_msg = HandleMsg::BuyToken {
+            token_id: "Atom".to_string(),
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &coins(5, "uatom")), handle_msg);
+        assert!(handle_result.is_ok());
+
+        // test the proceeds are credited in the listing's denom, less the marketplace fee
+        let handle_msg = HandleMsg::SetViewingKey {
+            key: "akey".to_string(),
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let query_msg = QueryMsg::PendingProceeds {
+            address: HumanAddr("alice".to_string()),
+            viewing_key: "akey".to_string(),
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::PendingProceeds { mut balances } => {
+                balances.sort_by(|a, b| a.denom.cmp(&b.denom));
+                assert_eq!(
+                    balances,
+                    vec![
+                        Coin {
+                            denom: "uatom".to_string(),
+                            amount: Uint128(4),
+                        },
+                        Coin {
+                            denom: "uscrt".to_string(),
+                            amount: Uint128(9),
+                        },
+                    ]
+                );
+            }
+            _ => panic!("unexpected"),
+        }
+        let query_result = query(&deps, QueryMsg::TokensForSale {});
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::TokensForSale { for_sale, .. } => {
+                assert!(for_sale.is_empty());
+            }
+            _ => panic!("unexpected"),
+        }
+    }
+
+    #[test]
+    fn test_lazy_listing() {
+        let (init_result, mut deps) =
+            init_helper_with_config(true, true, false, false, false, false, false);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let public_metadata = Some(Metadata {
+            token_uri: Some("https://uri".to_string()),
+            extension: None,
+        });
+
+        // test only minters can create lazy listings
+        let handle_msg = HandleMsg::CreateLazyListing {
+            listing_id: "Drop".to_string(),
+            mint_run_id: Some("run".to_string()),
+            price: 10,
+            editions: 2,
+            public_metadata: public_metadata.clone(),
+            private_metadata: None,
+            royalty_info: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Only designated minters are allowed to mint"));
+
+        // test a listing id can not be a default token id
+        let handle_msg = HandleMsg::CreateLazyListing {
+            listing_id: "500".to_string(),
+            mint_run_id: None,
+            price: 10,
+            editions: 2,
+            public_metadata: public_metadata.clone(),
+            private_metadata: None,
+            royalty_info: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Lazy listing ID 500 can not be a number"));
+        let handle_msg = HandleMsg::CreateLazyListing {
+            listing_id: "Drop".to_string(),
+            mint_run_id: Some("run".to_string()),
+            price: 10,
+            editions: 2,
+            public_metadata: public_metadata.clone(),
+            private_metadata: None,
+            royalty_info: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+
+        // test the listing id can not be reused as a token id
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some("Drop".to_string()),
+            owner: None,
+            public_metadata: None,
+            private_metadata: None,
+            royalty_info: None,
+            serial_number: None,
+            transferable: None,
+            memo: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Token ID Drop is already in use"));
+
+        let query_result = query(&deps, QueryMsg::LazyListings {});
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::LazyListings { listings } => {
+                assert_eq!(
+                    listings,
+                    vec![LazyListing {
+                        listing_id: "Drop".to_string(),
+                        creator: HumanAddr("admin".to_string()),
+                        price: 10,
+                        editions: 2,
+                        sold: 0,
+                        mint_run: Some(1),
+                        public_metadata: public_metadata.clone(),
+                    }]
+                );
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // test buying mints the next edition to the buyer
+        let handle_msg = HandleMsg::BuyToken {
+            token_id: "Drop".to_string(),
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &coins(5, "uscrt")), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Insufficient funds provided"));
+        let handle_msg = HandleMsg::BuyToken {
+            token_id: "Drop".to_string(),
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &coins(10, "uscrt")), handle_msg);
+        assert!(handle_result.is_ok());
+        let handle_msg = HandleMsg::BuyToken {
+            token_id: "Drop".to_string(),
+        };
+        let handle_result = handle(
+            &mut deps,
+            mock_env("charlie", &coins(10, "uscrt")),
+            handle_msg,
+        );
+        let handle_answer: HandleAnswer =
+            from_binary(&handle_result.unwrap().data.unwrap()).unwrap();
+        let token_id = match handle_answer {
+            HandleAnswer::BuyToken { token_id, .. } => token_id,
+            _ => panic!("unexpected"),
+        };
+        let query_result = query(
+            &deps,
+            QueryMsg::NftDossier {
+                token_id,
+                viewer: None,
+                include_expired: None,
+            },
+        );
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::NftDossier {
+                owner,
+                public_metadata: meta,
+                mint_run_info,
+                ..
+            } => {
+                assert_eq!(owner, Some(HumanAddr("charlie".to_string())));
+                assert_eq!(meta, public_metadata);
+                let run_info = mint_run_info.unwrap();
+                assert_eq!(run_info.token_creator, Some(HumanAddr("admin".to_string())));
+                assert_eq!(run_info.mint_run, Some(1));
+                assert_eq!(run_info.serial_number, Some(2));
+                assert_eq!(run_info.quantity_minted_this_run, Some(2));
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // test a sold out listing
+        let handle_msg = HandleMsg::BuyToken {
+            token_id: "Drop".to_string(),
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &coins(10, "uscrt")), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Lazy listing Drop has sold out"));
+        let query_result = query(&deps, QueryMsg::LazyListings {});
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::LazyListings { listings } => {
+                assert!(listings.is_empty());
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // test editions can not be bought once the creator is no longer a minter
+        let handle_msg = HandleMsg::AddMinters {
+            minters: vec![HumanAddr("alice".to_string())],
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let handle_msg = HandleMsg::CreateLazyListing {
+            listing_id: "Encore".to_string(),
+            mint_run_id: Some("encore".to_string()),
+            price: 10,
+            editions: 3,
+            public_metadata: public_metadata.clone(),
+            private_metadata: None,
+            royalty_info: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+        let handle_msg = HandleMsg::RemoveMinters {
+            minters: vec![HumanAddr("alice".to_string())],
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let handle_msg = HandleMsg::BuyToken {
+            token_id: "Encore".to_string(),
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &coins(10, "uscrt")), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("The creator of lazy listing Encore is no longer a minter"));
+        let handle_msg = HandleMsg::AddMinters {
+            minters: vec![HumanAddr("alice".to_string())],
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let handle_msg = HandleMsg::BuyToken {
+            token_id: "Encore".to_string(),
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &coins(10, "uscrt")), handle_msg);
+        assert!(handle_result.is_ok());
+
+        // test only the creator or the admin can cancel a lazy listing
+        let handle_msg = HandleMsg::CancelLazyListing {
+            listing_id: "Encore".to_string(),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Only the creator or the admin can cancel lazy listing Encore"));
+        let handle_msg = HandleMsg::CancelLazyListing {
+            listing_id: "Encore".to_string(),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+        let handle_msg = HandleMsg::CancelLazyListing {
+            listing_id: "Encore".to_string(),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Lazy listing Encore has no editions left to sell"));
+
+        // test a cancelled listing can not be bought and its mint run ends at the editions sold
+        let handle_msg = HandleMsg::BuyToken {
+            token_id: "Encore".to_string(),
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &coins(10, "uscrt")), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Lazy listing Encore has sold out"));
+        let query_result = query(&deps, QueryMsg::LazyListings {});
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::LazyListings { listings } => {
+                assert!(listings.is_empty());
+            }
+            _ => panic!("unexpected"),
+        }
+        let query_result = query(
+            &deps,
+            QueryMsg::MintRuns {
+                creator: Some(HumanAddr("alice".to_string())),
+                viewer: None,
+                start_after: None,
+                limit: None,
+            },
+        );
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::MintRuns { mint_runs } => {
+                assert_eq!(mint_runs.len(), 1);
+                assert_eq!(mint_runs[0].quantity, 1);
+                assert_eq!(mint_runs[0].minted, 1);
+            }
+            _ => panic!("unexpected"),
+        }
+    }
+
+    // test redeeming mint vouchers
+    #[test]
+    fn test_redeem_mint_voucher() {
+        let (init_result, mut deps) =
+            init_helper_with_config(true, true, false, false, false, false, false);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let voucher = MintVoucher {
+            contract: HumanAddr(MOCK_CONTRACT_ADDR.to_string()),
+            creator: HumanAddr("admin".to_string()),
+            nonce: 7,
+            recipient: HumanAddr("alice".to_string()),
+            price: Uint128(100),
+            token_id: Some("Voucher".to_string()),
+            public_metadata: Some(Metadata {
+                token_uri: Some("https://uri".to_string()),
+                extension: None,
+            }),
+            private_metadata: None,
+            royalty_info: None,
+            expiration: Some(Expiration::AtHeight(20000)),
+        };
+        let redeem = |voucher: &MintVoucher| HandleMsg::RedeemMintVoucher {
+            voucher: voucher.clone(),
+            signature: Binary(vec![1u8; 64]),
+            padding: None,
+        };
+
+        // test only minters can register a signer
+        let handle_msg = HandleMsg::SetVoucherSigner {
+            pubkey: Some(Binary(vec![2u8; 33])),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Only designated minters are allowed to register a voucher signer"));
+        let handle_msg = HandleMsg::SetVoucherSigner {
+            pubkey: Some(Binary(vec![2u8; 20])),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("must be a 33 byte compressed secp256k1 public key"));
+
+        // test redeeming before the creator registered a signer
+        let handle_result = handle(
+            &mut deps,
+            mock_env("bob", &coins(100, "uscrt")),
+            redeem(&voucher),
+        );
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("admin has not registered a voucher signer"));
+
+        let handle_msg = HandleMsg::SetVoucherSigner {
+            pubkey: Some(Binary(vec![2u8; 33])),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+
+        // test a voucher for another contract
+        let mut other = voucher.clone();
+        other.contract = HumanAddr("other".to_string());
+        let handle_result = handle(
+            &mut deps,
+            mock_env("bob", &coins(100, "uscrt")),
+            redeem(&other),
+        );
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("This voucher was issued for a different contract"));
+
+        // test a voucher from an address that is not a minter
+        let mut other = voucher.clone();
+        other.creator = HumanAddr("charlie".to_string());
+        let handle_result = handle(
+            &mut deps,
+            mock_env("bob", &coins(100, "uscrt")),
+            redeem(&other),
+        );
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("charlie is not a minter"));
+
+        // test an expired voucher
+        let mut env = mock_env("bob", &coins(100, "uscrt"));
+        env.block.height = 20000;
+        let handle_result = handle(&mut deps, env, redeem(&voucher));
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("This voucher has expired"));
+
+        // test the sign-doc keeps the declared field order and has no whitespace
+        assert_eq!(
+            String::from_utf8(voucher.sign_doc().unwrap()).unwrap(),
+            format!(
+                "{{\"contract\":\"{}\",\"creator\":\"admin\",\"nonce\":7,\"recipient\":\"alice\",\
+                \"price\":\"100\",\"token_id\":\"Voucher\",\"public_metadata\":\
+                {{\"token_uri\":\"https://uri\",\"extension\":null}},\"private_metadata\":null,\
+                \"royalty_info\":null,\"expiration\":{{\"at_height\":20000}}}}",
+                MOCK_CONTRACT_ADDR
+            )
+        );
+
+        // test paying the wrong price
+        let handle_result = handle(
+            &mut deps,
+            mock_env("bob", &coins(99, "uscrt")),
+            redeem(&voucher),
+        );
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Insufficient funds provided"));
+
+        // sanity check.  Anyone can redeem the voucher, but the recipient gets the token
+        let handle_result = handle(
+            &mut deps,
+            mock_env("bob", &coins(100, "uscrt")),
+            redeem(&voucher),
+        );
+        let handle_answer: HandleAnswer =
+            from_binary(&handle_result.unwrap().data.unwrap()).unwrap();
+        match handle_answer {
+            HandleAnswer::RedeemMintVoucher { token_id } => {
+                assert_eq!(token_id, "Voucher".to_string());
+            }
+            _ => panic!("unexpected"),
+        }
+        let query_msg = QueryMsg::OwnerOf {
+            token_id: "Voucher".to_string(),
+            viewer: None,
+            include_expired: None,
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::OwnerOf { owner, .. } => {
+                assert_eq!(owner, HumanAddr("alice".to_string()));
+            }
+            _ => panic!("unexpected"),
+        }
+        let handle_msg = HandleMsg::SetViewingKey {
+            key: "akey".to_string(),
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let query_msg = QueryMsg::PendingProceeds {
+            address: HumanAddr("admin".to_string()),
+            viewing_key: "akey".to_string(),
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::PendingProceeds { balances } => {
+                assert_eq!(balances, coins(98, "uscrt"));
+            }
+            _ => panic!("unexpected"),
+        }
+        // test the redemption is the token's primary sale
+        let sale_num = ReadonlyPrefixedStorage::new(PREFIX_SALE_NUM, &deps.storage);
+        let num: i32 = load(&sale_num, &0u32.to_le_bytes()).unwrap();
+        assert_eq!(num, 1);
+
+        // test replaying the voucher
+        let mut replay = voucher.clone();
+        replay.token_id = None;
+        let handle_result = handle(
+            &mut deps,
+            mock_env("bob", &coins(100, "uscrt")),
+            redeem(&replay),
+        );
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Voucher nonce 7 has already been redeemed"));
+
+        // test removing the signer
+        let handle_msg = HandleMsg::SetVoucherSigner {
+            pubkey: None,
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        replay.nonce = 8;
+        let handle_result = handle(
+            &mut deps,
+            mock_env("bob", &coins(100, "uscrt")),
+            redeem(&replay),
+        );
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("admin has not registered a voucher signer"));
+    }
+
+    // test metadata validation
+    #[test]
+    fn test_metadata_validation() {
+        let (init_result, mut deps) = init_helper_default();
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let mint =
+            |public_metadata: Metadata, private_metadata: Option<Metadata>| HandleMsg::MintNft {
+                token_id: Some("NFT1".to_string()),
+                owner: None,
+                public_metadata: Some(public_metadata),
+                private_metadata,
+                royalty_info: None,
+                serial_number: None,
+                transferable: None,
+                memo: None,
+                padding: None,
+            };
+        let with_ext = |ext: Extension| Metadata {
+            token_uri: None,
+            extension: Some(ext),
+        };
+
+        // test url schemes
+        let bad_uri = Metadata {
+            token_uri: Some("www.example.com".to_string()),
+            extension: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), mint(bad_uri, None));
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains(
+            "public_metadata.token_uri must start with http://, https://, ipfs://, ar://"
+        ));
+        let bad_image = with_ext(Extension {
+            image: Some("ftp://image".to_string()),
+            ..Extension::default()
+        });
+        let good = with_ext(Extension {
+            image: Some("ipfs://image".to_string()),
+            ..Extension::default()
+        });
+        let handle_result = handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            mint(good.clone(), Some(bad_image)),
+        );
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("private_metadata.extension.image must start with"));
+
+        // test the background color
+        let bad_color = with_ext(Extension {
+            background_color: Some("#fff000".to_string()),
+            ..Extension::default()
+        });
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), mint(bad_color, None));
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains(
+            "public_metadata.extension.background_color must be six hexadecimal characters"
+        ));
+
+        // test attribute limits
+        let attr = Trait {
+            display_type: None,
+            trait_type: Some("Color".to_string()),
+            value: "Red".to_string(),
+            max_value: None,
+        };
+        let too_many = with_ext(Extension {
+            attributes: Some(vec![attr.clone(); 65]),
+            ..Extension::default()
+        });
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), mint(too_many, None));
+        let error = extract_error_msg(handle_result);
+        assert!(
+            error.contains("public_metadata.extension.attributes can not have more than 64 traits")
+        );
+        let mut long = attr.clone();
+        long.value = "a".repeat(257);
+        let too_long = with_ext(Extension {
+            attributes: Some(vec![attr.clone(), long]),
+            ..Extension::default()
+        });
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), mint(too_long, None));
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains(
+            "public_metadata.extension.attributes[1].value can not be longer than 256 characters"
+        ));
+
+        // test media file types
+        let bad_media = with_ext(Extension {
+            media: Some(vec![MediaFile {
+                file_type: Some("picture".to_string()),
+                extension: Some("png".to_string()),
+                authentication: None,
+                url: "ar://file".to_string(),
+            }]),
+            ..Extension::default()
+        });
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), mint(bad_media, None));
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains(
+            "public_metadata.extension.media[0].file_type must be one of image, video, audio, text, font, application"
+        ));
+
+        // sanity check
+        let valid = with_ext(Extension {
+            image: Some("https://image".to_string()),
+            background_color: Some("00fFaa".to_string()),
+            attributes: Some(vec![attr.clone(); 64]),
+            media: Some(vec![MediaFile {
+                file_type: Some("image".to_string()),
+                extension: Some("png".to_string()),
+                authentication: None,
+                url: "ar://file".to_string(),
+            }]),
+            ..Extension::default()
+        });
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), mint(valid, None));
+        assert!(handle_result.is_ok());
+
+        // test updates are validated too
+        let handle_msg = HandleMsg::SetMetadata {
+            token_id: "NFT1".to_string(),
+            public_metadata: Some(with_ext(Extension {
+                youtube_url: Some("youtube.com/watch".to_string()),
+                ..Extension::default()
+            })),
+            private_metadata: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("public_metadata.extension.youtube_url must start with"));
+    }
+
+    // test freezing metadata
+    #[test]
+    fn test_freeze_metadata() {
+        let (init_result, mut deps) =
+            init_helper_with_config(true, true, false, false, true, true, false);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        for id in ["NFT1", "NFT2"].iter() {
+            let handle_msg = HandleMsg::MintNft {
+                token_id: Some(id.to_string()),
+                owner: Some(HumanAddr("alice".to_string())),
+                public_metadata: None,
+                private_metadata: None,
+                royalty_info: None,
+                serial_number: None,
+                transferable: None,
+                memo: None,
+                padding: None,
+            };
+            let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        }
+        let set_metadata = |token_id: &str| HandleMsg::SetMetadata {
+            token_id: token_id.to_string(),
+            public_metadata: Some(Metadata {
+                token_uri: Some("https://new".to_string()),
+                extension: None,
+            }),
+            private_metadata: None,
+            padding: None,
+        };
+
+        // test someone who can not update the metadata can not freeze it
+        let handle_msg = HandleMsg::FreezeMetadata {
+            token_id: "NFT1".to_string(),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Not authorized to freeze the metadata of token NFT1"));
+
+        // test the owner freezing the metadata
+        let handle_msg = HandleMsg::FreezeMetadata {
+            token_id: "NFT1".to_string(),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), set_metadata("NFT1"));
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("The metadata of token NFT1 is frozen"));
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), set_metadata("NFT2"));
+        assert!(handle_result.is_ok());
+
+        let query_msg = QueryMsg::NftInfo {
+            token_id: "NFT1".to_string(),
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::NftInfo { frozen, .. } => {
+                assert!(frozen);
+            }
+            _ => panic!("unexpected"),
+        }
+        let query_msg = QueryMsg::NftDossier {
+            token_id: "NFT2".to_string(),
+            viewer: None,
+            include_expired: None,
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::NftDossier { frozen, .. } => {
+                assert!(!frozen);
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // test freezing the whole collection
+        let handle_msg = HandleMsg::FreezeAllMetadata { padding: None };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(
+            error.contains("This is an admin command and can only be run from the admin address")
+        );
+        let handle_msg = HandleMsg::FreezeAllMetadata { padding: None };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), set_metadata("NFT2"));
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("The metadata of token NFT2 is frozen"));
+        let query_msg = QueryMsg::NftDossier {
+            token_id: "NFT2".to_string(),
+            viewer: None,
+            include_expired: None,
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::NftDossier { frozen, .. } => {
+                assert!(frozen);
+            }
+            _ => panic!("unexpected"),
+        }
+    }
+
+    // test recording the revisions of a token's metadata
+    #[test]
+    fn test_metadata_history() {
+        let (init_result, mut deps) =
+            init_helper_with_config(true, true, false, false, true, true, false);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let first_pub = Metadata {
+            token_uri: Some("https://first".to_string()),
+            extension: None,
+        };
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some("NFT1".to_string()),
+            owner: Some(HumanAddr("alice".to_string())),
+            public_metadata: Some(first_pub.clone()),
+            private_metadata: None,
+            royalty_info: None,
+            serial_number: None,
+            transferable: None,
+            memo: None,
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let new_pub = Metadata {
+            token_uri: Some("https://second".to_string()),
+            extension: None,
+        };
+        let new_priv = Metadata {
+            token_uri: Some("https://private".to_string()),
+            extension: None,
+        };
+        let handle_msg = HandleMsg::SetMetadata {
+            token_id: "NFT1".to_string(),
+            public_metadata: Some(new_pub.clone()),
+            private_metadata: Some(new_priv.clone()),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+        let handle_msg = HandleMsg::SetViewingKey {
+            key: "akey".to_string(),
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let hash = |meta: &Metadata| Binary::from(sha_256(to_binary(meta).unwrap().as_slice()));
+
+        // test a querier who can not view private metadata only sees the public revision
+        let query_msg = QueryMsg::MetadataHistory {
+            token_id: "NFT1".to_string(),
+            viewer: None,
+            page: None,
+            page_size: None,
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::MetadataHistory { total, revisions } => {
+                assert_eq!(total, 1);
+                assert!(!revisions[0].private);
+                assert_eq!(revisions[0].changer, HumanAddr("alice".to_string()));
+                assert_eq!(revisions[0].old_hash, Some(hash(&first_pub)));
+                assert_eq!(revisions[0].new_hash, hash(&new_pub));
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // test the owner sees both revisions, newest first
+        let query_msg = QueryMsg::MetadataHistory {
+            token_id: "NFT1".to_string(),
+            viewer: Some(ViewerInfo {
+                address: HumanAddr("alice".to_string()),
+                viewing_key: "akey".to_string(),
+            }),
+            page: None,
+            page_size: None,
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::MetadataHistory { total, revisions } => {
+                assert_eq!(total, 2);
+                assert!(revisions[0].private);
+                assert_eq!(revisions[0].old_hash, None);
+                assert_eq!(revisions[0].new_hash, hash(&new_priv));
+                assert_eq!(revisions[0].block_height, 12345);
+                assert!(!revisions[1].private);
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // test pages skip the private revisions a querier can not view
+        let third_pub = Metadata {
+            token_uri: Some("https://third".to_string()),
+            extension: None,
+        };
+        let handle_msg = HandleMsg::SetMetadata {
+            token_id: "NFT1".to_string(),
+            public_metadata: Some(third_pub.clone()),
+            private_metadata: None,
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let handle_msg = HandleMsg::SetMetadata {
+            token_id: "NFT1".to_string(),
+            public_metadata: None,
+            private_metadata: Some(first_pub.clone()),
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let query_msg = QueryMsg::MetadataHistory {
+            token_id: "NFT1".to_string(),
+            viewer: None,
+            page: Some(1),
+            page_size: Some(1),
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::MetadataHistory { total, revisions } => {
+                assert_eq!(total, 2);
+                assert_eq!(revisions.len(), 1);
+                assert_eq!(revisions[0].new_hash, hash(&new_pub));
+            }
+            _ => panic!("unexpected"),
+        }
+        let query_msg = QueryMsg::MetadataHistory {
+            token_id: "NFT1".to_string(),
+            viewer: Some(ViewerInfo {
+                address: HumanAddr("alice".to_string()),
+                viewing_key: "akey".to_string(),
+            }),
+            page: Some(1),
+            page_size: Some(2),
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::MetadataHistory { total, revisions } => {
+                assert_eq!(total, 4);
+                assert_eq!(revisions.len(), 2);
+                assert_eq!(revisions[0].new_hash, hash(&new_priv));
+                assert_eq!(revisions[1].new_hash, hash(&new_pub));
+            }
+            _ => panic!("unexpected"),
+        }
+    }
+
+    // test unlockable content is only revealed to the token's owner
+    #[test]
+    fn test_unlockable() {
+        let (init_result, mut deps) =
+            init_helper_with_config(true, true, false, false, true, true, false);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some("NFT1".to_string()),
+            owner: Some(HumanAddr("alice".to_string())),
+            public_metadata: None,
+            private_metadata: None,
+            royalty_info: None,
+            serial_number: None,
+            transferable: Some(true),
+            memo: None,
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        for (addr, key) in [("alice", "akey"), ("bob", "bkey")].iter() {
+            let handle_msg = HandleMsg::SetViewingKey {
+                key: key.to_string(),
+                padding: None,
+            };
+            let _handle_result = handle(&mut deps, mock_env(*addr, &[]), handle_msg);
+        }
+        let set_unlockable = |content: &str| HandleMsg::SetUnlockable {
+            token_id: "NFT1".to_string(),
+            unlockable: Some(content.to_string()),
+            padding: None,
+        };
+        let unlockable_of = |deps: &Extern<MockStorage, MockApi, MockQuerier>,
+                             addr: &str,
+                             key: &str|
+         -> Option<String> {
+            let query_msg = QueryMsg::PrivateMetadata {
+                token_id: "NFT1".to_string(),
+                viewer: Some(ViewerInfo {
+                    address: HumanAddr(addr.to_string()),
+                    viewing_key: key.to_string(),
+                }),
+            };
+            let query_result = query(deps, query_msg);
+            match from_binary(&query_result.unwrap()).unwrap() {
+                QueryAnswer::PrivateMetadata { unlockable, .. } => unlockable,
+                _ => panic!("unexpected"),
+            }
+        };
+
+        // test only the owner can set the unlockable content
+        let handle_result = handle(&mut deps, mock_env("bob", &[]), set_unlockable("secret"));
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Not authorized to set the unlockable content of token NFT1"));
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), set_unlockable("secret"));
+        assert!(handle_result.is_ok());
+        assert_eq!(
+            unlockable_of(&deps, "alice", "akey"),
+            Some("secret".to_string())
+        );
+
+        // test the content can not be changed while listed
+        let handle_msg = HandleMsg::SetSaleStatus {
+            token_id: "NFT1".to_string(),
+            sale_status: SaleStatus::ForSale,
+            price: Some(100),
+        };
+        let _handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), set_unlockable("swap"));
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains(
+            "The unlockable content of token NFT1 can not be changed while it is listed for sale"
+        ));
+        let query_msg = QueryMsg::SaleInfo {
+            token_id: "NFT1".to_string(),
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::SaleInfo { has_unlockable, .. } => {
+                assert!(has_unlockable);
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // test the buyer can view the content after buying the token
+        let handle_msg = HandleMsg::BuyToken {
+            token_id: "NFT1".to_string(),
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &coins(100, "uscrt")), handle_msg);
+        assert!(handle_result.is_ok());
+        assert_eq!(
+            unlockable_of(&deps, "bob", "bkey"),
+            Some("secret".to_string())
+        );
+        let query_msg = QueryMsg::PrivateMetadata {
+            token_id: "NFT1".to_string(),
+            viewer: Some(ViewerInfo {
+                address: HumanAddr("alice".to_string()),
+                viewing_key: "akey".to_string(),
+            }),
+        };
+        assert!(query(&deps, query_msg).is_err());
+    }
+
+    // test attribute updaters changing the attributes they were allowed
+    #[test]
+    fn test_update_attributes() {
+        let (init_result, mut deps) =
+            init_helper_with_config(true, true, false, false, true, true, false);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let attribute = |trait_type: &str, value: &str| Trait {
+            display_type: None,
+            trait_type: Some(trait_type.to_string()),
+            value: value.to_string(),
+            max_value: None,
+        };
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some("NFT1".to_string()),
+            owner: Some(HumanAddr("alice".to_string())),
+            public_metadata: Some(Metadata {
+                token_uri: None,
+                extension: Some(Extension {
+                    name: Some("Hero".to_string()),
+                    attributes: Some(vec![attribute("level", "1"), attribute("class", "warrior")]),
+                    ..Extension::default()
+                }),
+            }),
+            private_metadata: None,
+            royalty_info: None,
+            serial_number: None,
+            transferable: None,
+            memo: None,
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let update = |trait_type: &str, value: &str, private: bool| {
+            let attributes = Some(vec![attribute(trait_type, value)]);
+            HandleMsg::UpdateAttributes {
+                token_id: "NFT1".to_string(),
+                public_attributes: if private { None } else { attributes.clone() },
+                private_attributes: if private { attributes } else { None },
+                padding: None,
+            }
+        };
+
+        // test only the admin can register updaters
+        let set_updater = || HandleMsg::SetAttributeUpdater {
+            updater: HumanAddr("game".to_string()),
+            trait_types: vec!["level".to_string(), "xp".to_string()],
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), set_updater());
+        let error = extract_error_msg(handle_result);
+        assert!(
+            error.contains("This is an admin command and can only be run from the admin address")
+        );
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), set_updater());
+        assert!(handle_result.is_ok());
+        let query_result = query(&deps, QueryMsg::AttributeUpdaters {});
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::AttributeUpdaters { updaters } => {
+                assert_eq!(updaters.len(), 1);
+                assert_eq!(updaters[0].updater, HumanAddr("game".to_string()));
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // test minters and other addresses are not attribute updaters
+        let handle_result = handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            update("level", "2", false),
+        );
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Only attribute updaters may update the attributes of a token"));
+        // test an updater can only update its trait types
+        let handle_result = handle(
+            &mut deps,
+            mock_env("game", &[]),
+            update("class", "mage", false),
+        );
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Not authorized to update the class attribute"));
+
+        // test replacing a public attribute and adding a private one
+        let handle_result = handle(
+            &mut deps,
+            mock_env("game", &[]),
+            update("level", "2", false),
+        );
+        assert!(handle_result.is_ok());
+        let handle_result = handle(&mut deps, mock_env("game", &[]), update("xp", "50", true));
+        assert!(handle_result.is_ok());
+        let public: Option<Metadata> = {
+            let meta_store = ReadonlyPrefixedStorage::new(PREFIX_PUB_META, &deps.storage);
+            may_load(&meta_store, &0u32.to_le_bytes()).unwrap()
+        };
+        let extension = public.unwrap().extension.unwrap();
+        assert_eq!(extension.name, Some("Hero".to_string()));
+        assert_eq!(
+            extension.attributes,
+            Some(vec![attribute("level", "2"), attribute("class", "warrior")])
+        );
+        let private: Option<Metadata> = {
+            let meta_store = ReadonlyPrefixedStorage::new(PREFIX_PRIV_META, &deps.storage);
+            may_load(&meta_store, &0u32.to_le_bytes()).unwrap()
+        };
+        assert_eq!(
+            private.unwrap().extension.unwrap().attributes,
+            Some(vec![attribute("xp", "50")])
+        );
+
+        // test frozen metadata can not be updated
+        let handle_msg = HandleMsg::FreezeMetadata {
+            token_id: "NFT1".to_string(),
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let handle_result = handle(
+            &mut deps,
+            mock_env("game", &[]),
+            update("level", "3", false),
+        );
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("The metadata of token NFT1 is frozen"));
+    }
+
+    // test the original minter revoking and reissuing non-transferable tokens
+    #[test]
+    fn test_revoke_reissue_non_transferable() {
+        let (init_result, mut deps) =
+            init_helper_with_config(true, true, false, false, true, false, false);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        for (id, transferable) in [("Badge", false), ("NFT", true)].iter() {
+            let handle_msg = HandleMsg::MintNft {
+                token_id: Some(id.to_string()),
+                owner: Some(HumanAddr("alice".to_string())),
+                public_metadata: None,
+                private_metadata: None,
+                royalty_info: None,
+                serial_number: None,
+                transferable: Some(*transferable),
+                memo: None,
+                padding: None,
+            };
+            let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        }
+        let handle_msg = HandleMsg::AddMinters {
+            minters: vec![HumanAddr("bob".to_string())],
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let reissue = |token_id: &str, new_owner: &str| HandleMsg::ReissueNonTransferable {
+            token_id: token_id.to_string(),
+            new_owner: HumanAddr(new_owner.to_string()),
+            memo: None,
+            padding: None,
+        };
+        let revoke = |token_id: &str| HandleMsg::RevokeNonTransferable {
+            token_id: token_id.to_string(),
+            memo: Some("expired".to_string()),
+            padding: None,
+        };
+
+        // test only the original minter can reissue or revoke
+        let handle_result = handle(&mut deps, mock_env("bob", &[]), reissue("Badge", "charlie"));
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Only the original minter of token Badge can reissue it"));
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), revoke("Badge"));
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Only the original minter of token Badge can revoke it"));
+        // test transferable tokens are left to their owners
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), revoke("NFT"));
+        let error = extract_error_msg(handle_result);
+        assert!(error
+            .contains("Only non-transferable tokens can be revoked or reissued by their minter"));
+
+        // test reissuing to a new wallet
+        let handle_result = handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            reissue("Badge", "charlie"),
+        );
+        assert!(handle_result.is_ok());
+        let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
+        let token: Token = json_load(&info_store, &0u32.to_le_bytes()).unwrap();
+        let charlie_raw = deps
+            .api
+            .canonical_address(&HumanAddr("charlie".to_string()))
+            .unwrap();
+        assert_eq!(token.owner, charlie_raw);
+        let (txs, _) = get_txs(&deps.api, &deps.storage, &charlie_raw, 0, 1).unwrap();
+        assert_eq!(
+            txs[0].action,
+            TxAction::Reissue {
+                from: HumanAddr("alice".to_string()),
+                issuer: HumanAddr("admin".to_string()),
+                recipient: HumanAddr("charlie".to_string()),
+            }
+        );
+
+        // test revoking burns the token even though burn is not enabled
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), revoke("Badge"));
+        assert!(handle_result.is_ok());
+        let map2idx = ReadonlyPrefixedStorage::new(PREFIX_MAP_TO_INDEX, &deps.storage);
+        assert!(may_load::<u32, _>(&map2idx, "Badge".as_bytes())
+            .unwrap()
+            .is_none());
+        let (txs, _) = get_txs(&deps.api, &deps.storage, &charlie_raw, 0, 1).unwrap();
+        assert_eq!(
+            txs[0].action,
+            TxAction::Revoke {
+                owner: HumanAddr("charlie".to_string()),
+                issuer: HumanAddr("admin".to_string()),
+            }
+        );
+        assert_eq!(txs[0].memo, Some("expired".to_string()));
+    }
+
+    // test attaching and detaching children of composable tokens
+    #[test]
+    fn test_composable() {
+        let (init_result, mut deps) =
+            init_helper_with_config(true, false, false, false, true, false, true);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        for id in ["Parent", "Sword", "Shield"].iter() {
+            let handle_msg = HandleMsg::MintNft {
+                token_id: Some(id.to_string()),
+                owner: Some(HumanAddr("alice".to_string())),
+                public_metadata: None,
+                private_metadata: None,
+                royalty_info: None,
+                serial_number: None,
+                transferable: None,
+                memo: None,
+                padding: None,
+            };
+            let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        }
+        let attach = |child: &str| HandleMsg::AttachChild {
+            parent_token_id: "Parent".to_string(),
+            child_token_id: child.to_string(),
+            padding: None,
+        };
+        let detach = |contract: Option<&str>, child: &str| HandleMsg::DetachChild {
+            parent_token_id: "Parent".to_string(),
+            child: ChildToken {
+                contract: contract.map(|c| HumanAddr(c.to_string())),
+                token_id: child.to_string(),
+            },
+            padding: None,
+        };
+
+        // test only the owner of the parent can attach children
+        let handle_result = handle(&mut deps, mock_env("bob", &[]), attach("Sword"));
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Only the owner of token Parent can change its children"));
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), attach("Parent"));
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("A token can not be attached to itself"));
+
+        // test attaching escrows the child in the contract
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), attach("Sword"));
+        assert!(handle_result.is_ok());
+        let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
+        let token: Token = json_load(&info_store, &1u32.to_le_bytes()).unwrap();
+        let contract_raw = deps
+            .api
+            .canonical_address(&HumanAddr(MOCK_CONTRACT_ADDR.to_string()))
+            .unwrap();
+        assert_eq!(token.owner, contract_raw);
+        // test the children are not revealed without permission to view the parent's owner
+        let query_msg = QueryMsg::Children {
+            token_id: "Parent".to_string(),
+            viewer: None,
+        };
+        assert!(query(&deps, query_msg).is_err());
+
+        // test a parent holding children can not be burned
+        let handle_msg = HandleMsg::BurnNft {
+            token_id: "Parent".to_string(),
+            memo: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Token Parent must have its children detached before it is burned"));
+
+        // test children of a registered contract are attached when sent to this contract
+        let handle_msg = HandleMsg::RegisterChildContract {
+            contract: HumanAddr("game".to_string()),
+            code_hash: "game hash".to_string(),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("This is an admin command"));
+        let handle_msg = HandleMsg::RegisterChildContract {
+            contract: HumanAddr("game".to_string()),
+            code_hash: "game hash".to_string(),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let resp = handle_result.unwrap();
+        match &resp.messages[0] {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr,
+                callback_code_hash,
+                ..
+            }) => {
+                assert_eq!(contract_addr, &HumanAddr("game".to_string()));
+                assert_eq!(callback_code_hash, "game hash");
+            }
+            _ => panic!("unexpected message"),
+        }
+        let receive = || HandleMsg::BatchReceiveNft {
+            sender: HumanAddr("alice".to_string()),
+            from: HumanAddr("alice".to_string()),
+            token_ids: vec!["Helmet".to_string()],
+            msg: Some(
+                to_binary(&AttachChildMsg {
+                    parent_token_id: "Parent".to_string(),
+                })
+                .unwrap(),
+            ),
+        };
+        let handle_result = handle(&mut deps, mock_env("market", &[]), receive());
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("market is not a registered child contract"));
+        let handle_result = handle(&mut deps, mock_env("game", &[]), receive());
+        assert!(handle_result.is_ok());
+
+        // test the new owner of the parent can detach its children
+        let handle_msg = HandleMsg::TransferNft {
+            recipient: HumanAddr("bob".to_string()),
+            token_id: "Parent".to_string(),
+            memo: None,
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let query_msg = QueryMsg::Children {
+            token_id: "Parent".to_string(),
+            viewer: None,
+        };
+        assert!(query(&deps, query_msg).is_err());
+        // test children can not be changed while the parent is listed for sale
+        let handle_msg = HandleMsg::SetSaleStatus {
+            token_id: "Parent".to_string(),
+            sale_status: SaleStatus::ForSale,
+            price: Some(100),
+        };
+        let _handle_result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
+        let handle_result = handle(&mut deps, mock_env("bob", &[]), detach(None, "Sword"));
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains(
+            "The children of token Parent can not be changed while it is listed for sale"
+        ));
+        let handle_msg = HandleMsg::SetSaleStatus {
+            token_id: "Parent".to_string(),
+            sale_status: SaleStatus::NotForSale,
+            price: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
+        let handle_result = handle(&mut deps, mock_env("bob", &[]), detach(None, "Shield"));
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Token Parent does not hold token Shield"));
+        let handle_result = handle(&mut deps, mock_env("bob", &[]), detach(None, "Sword"));
+        assert!(handle_result.is_ok());
+        let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
+        let token: Token = json_load(&info_store, &1u32.to_le_bytes()).unwrap();
+        let bob_raw = deps
+            .api
+            .canonical_address(&HumanAddr("bob".to_string()))
+            .unwrap();
+        assert_eq!(token.owner, bob_raw);
+        let handle_result = handle(
+            &mut deps,
+            mock_env("bob", &[]),
+            detach(Some("game"), "Helmet"),
+        );
+        let resp = handle_result.unwrap();
+        match &resp.messages[0] {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr, msg, ..
+            }) => {
+                assert_eq!(contract_addr, &HumanAddr("game".to_string()));
+                let transfer: Snip721HandleMsg = from_binary(msg).unwrap();
+                assert_eq!(
+                    transfer,
+                    Snip721HandleMsg::TransferNft {
+                        recipient: HumanAddr("bob".to_string()),
+                        token_id: "Helmet".to_string(),
+                        memo: None,
+                        padding: None,
+                    }
+                );
+            }
+            _ => panic!("unexpected message"),
+        }
+
+        // test a parent without children can be burned
+        let handle_msg = HandleMsg::BurnNft {
+            token_id: "Parent".to_string(),
+            memo: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+    }
+
+    // test listing and buying bundles of tokens
+    #[test]
+    fn test_bundles() {
+        let (init_result, mut deps) =
+            init_helper_with_config(true, true, false, false, false, false, false);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        for (id, royalty) in [("Ace", true), ("King", false), ("Queen", false)].iter() {
+            let handle_msg = HandleMsg::MintNft {
+                token_id: Some(id.to_string()),
+                owner: Some(HumanAddr("alice".to_string())),
+                public_metadata: None,
+                private_metadata: None,
+                royalty_info: if *royalty {
+                    Some(RoyaltyInfo {
+                        decimal_places_in_rates: 2,
+                        royalties: vec![Royalty {
+                            recipient: HumanAddr("charlie".to_string()),
+                            rate: 10,
+                        }],
+                    })
+                } else {
+                    None
+                },
+                serial_number: None,
+                transferable: Some(true),
+                memo: None,
+                padding: None,
+            };
+            let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        }
+        for (name, key) in [("bob", "bkey"), ("charlie", "ckey")].iter() {
+            let handle_msg = HandleMsg::SetViewingKey {
+                key: key.to_string(),
+                padding: None,
+            };
+            let _handle_result = handle(&mut deps, mock_env(*name, &[]), handle_msg);
+        }
+        let list = |ids: &[&str], price: u32| HandleMsg::ListBundle {
+            token_ids: ids.iter().map(|id| id.to_string()).collect(),
+            price,
+            token_prices: None,
+        };
+
+        // test only the owner can bundle a token, and a bundle needs several tokens
+        let handle_result = handle(&mut deps, mock_env("bob", &[]), list(&["Ace", "King"], 100));
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("You are not authorized to perform this action on token Ace"));
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), list(&["Ace"], 100));
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("A bundle must include at least two tokens"));
+        let handle_result = handle(
+            &mut deps,
+            mock_env("alice", &[]),
+            list(&["Ace", "Ace"], 100),
+        );
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Token Ace is included in the bundle more than once"));
+
+        // test bundled tokens are locked from transfer and individual sale
+        let handle_result = handle(
+            &mut deps,
+            mock_env("alice", &[]),
+            list(&["Ace", "King"], 100),
+        );
+        assert!(handle_result.is_ok());
+        let handle_msg = HandleMsg::TransferNft {
+            recipient: HumanAddr("david".to_string()),
+            token_id: "King".to_string(),
+            memo: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Token King is locked in bundle 0"));
+        let handle_msg = HandleMsg::SetSaleStatus {
+            token_id: "Ace".to_string(),
+            sale_status: SaleStatus::ForSale,
+            price: Some(100),
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Token Ace is locked in bundle 0"));
+        let handle_result = handle(
+            &mut deps,
+            mock_env("alice", &[]),
+            list(&["King", "Queen"], 100),
+        );
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Token King is locked in bundle 0"));
+
+        // test bundles are included in the listing query
+        let query_result = query(&deps, QueryMsg::TokensForSale {});
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::TokensForSale { bundles, .. } => {
+                assert_eq!(
+                    bundles,
+                    vec![Bundle {
+                        bundle_id: 0,
+                        token_ids: vec!["Ace".to_string(), "King".to_string()],
+                        price: 100,
+                        token_prices: None,
+                    }]
+                );
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // test buying a bundle transfers all of its tokens
+        let handle_msg = HandleMsg::BuyBundle { bundle_id: 0 };
+        let handle_result = handle(&mut deps, mock_env("bob", &coins(99, "uscrt")), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Insufficient funds provided"));
+        let handle_msg = HandleMsg::BuyBundle { bundle_id: 0 };
+        let handle_result = handle(&mut deps, mock_env("bob", &coins(100, "uscrt")), handle_msg);
+        assert!(handle_result.is_ok());
+        let info_store = ReadonlyPrefixedStorage::new(PREFIX_INFOS, &deps.storage);
+        let bob_raw = deps
+            .api
+            .canonical_address(&HumanAddr("bob".to_string()))
+            .unwrap();
+        for idx in 0u32..2 {
+            let token: Token = json_load(&info_store, &idx.to_le_bytes()).unwrap();
+            assert_eq!(token.owner, bob_raw);
+        }
+        let handle_msg = HandleMsg::BuyBundle { bundle_id: 0 };
+        let handle_result = handle(
+            &mut deps,
+            mock_env("david", &coins(100, "uscrt")),
+            handle_msg,
+        );
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Bundle 0 is not for sale"));
+
+        // test the token prices must cover every token and add up to the bundle price
+        let handle_msg = HandleMsg::ListBundle {
+            token_ids: vec!["Ace".to_string(), "King".to_string()],
+            price: 1000,
+            token_prices: Some(vec![1000]),
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("A price must be given for each token in the bundle"));
+        let handle_msg = HandleMsg::ListBundle {
+            token_ids: vec!["Ace".to_string(), "King".to_string()],
+            price: 1000,
+            token_prices: Some(vec![800, 100]),
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("The token prices must add up to the price of the bundle"));
+
+        // test only the seller can cancel a bundle
+        let handle_msg = HandleMsg::ListBundle {
+            token_ids: vec!["Ace".to_string(), "King".to_string()],
+            price: 1000,
+            token_prices: Some(vec![800, 200]),
+        };
+        let handle_result = handle(&mut deps, mock_env("bob", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+        let handle_msg = HandleMsg::CancelBundle { bundle_id: 1 };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Only the seller can cancel bundle 1"));
+
+        // test the royalties of a secondary sale are paid on each token's listed price
+        let handle_msg = HandleMsg::BuyBundle { bundle_id: 1 };
+        let handle_result = handle(
+            &mut deps,
+            mock_env("alice", &coins(1000, "uscrt")),
+            handle_msg,
+        );
+        assert!(handle_result.is_ok());
+        for (name, key, expected) in [("charlie", "ckey", 80), ("bob", "bkey", 901)].iter() {
+            let query_msg = QueryMsg::PendingProceeds {
+                address: HumanAddr(name.to_string()),
+                viewing_key: key.to_string(),
+            };
+            let query_result = query(&deps, query_msg);
+            let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+            match query_answer {
+                QueryAnswer::PendingProceeds { balances } => {
+                    assert_eq!(balances, coins(*expected, "uscrt"));
+                }
+                _ => panic!("unexpected"),
+            }
+        }
+
+        // test cancelling unlocks the tokens
+        let handle_result = handle(
+            &mut deps,
+            mock_env("alice", &[]),
+            list(&["Ace", "King"], 500),
+        );
+        assert!(handle_result.is_ok());
+        let handle_msg = HandleMsg::CancelBundle { bundle_id: 2 };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+        let handle_msg = HandleMsg::TransferNft {
+            recipient: HumanAddr("david".to_string()),
+            token_id: "King".to_string(),
+            memo: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+        let query_result = query(&deps, QueryMsg::TokensForSale {});
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::TokensForSale { bundles, .. } => assert!(bundles.is_empty()),
+            _ => panic!("unexpected"),
+        }
+    }
 }
@@ -1,10 +1,13 @@
 #[cfg(test)]
 mod tests {
     use crate::contract::{handle, init, query};
-    use crate::mint_run::MintRunInfo;
-    use crate::msg::{HandleMsg, InitMsg, QueryAnswer, QueryMsg};
+    use crate::mint_run::{EditionOverride, MintRun, MintRunInfo};
+    use crate::msg::{HandleMsg, InitMsg, QueryAnswer, QueryMsg, ViewerInfo};
+    use crate::state::{may_load, PREFIX_PUB_META};
+    use crate::token::{Extension, Metadata, Trait};
     use cosmwasm_std::testing::*;
     use cosmwasm_std::{from_binary, Extern, HumanAddr, InitResponse, StdError, StdResult};
+    use cosmwasm_storage::ReadonlyPrefixedStorage;
     use std::any::Any;
 
     // Helper functions
@@ -58,7 +61,9 @@ mod tests {
             private_metadata: None,
             royalty_info: None,
             memo: None,
+            editions: None,
             padding: None,
+            listing: None,
         };
         let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
         let error = extract_error_msg(handle_result);
@@ -73,7 +78,9 @@ mod tests {
             private_metadata: None,
             royalty_info: None,
             memo: None,
+            editions: None,
             padding: None,
+            listing: None,
         };
         let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
         let error = extract_error_msg(handle_result);
@@ -88,7 +95,9 @@ mod tests {
             private_metadata: None,
             royalty_info: None,
             memo: None,
+            editions: None,
             padding: None,
+            listing: None,
         };
         let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
         assert!(handle_result.is_ok());
@@ -167,7 +176,9 @@ mod tests {
             private_metadata: None,
             royalty_info: None,
             memo: None,
+            editions: None,
             padding: None,
+            listing: None,
         };
         let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
         assert!(handle_result.is_ok());
@@ -203,7 +214,9 @@ mod tests {
             private_metadata: None,
             royalty_info: None,
             memo: None,
+            editions: None,
             padding: None,
+            listing: None,
         };
         let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
         assert!(handle_result.is_ok());
@@ -251,4 +264,336 @@ mod tests {
             _ => panic!("unexpected"),
         }
     }
+
+    #[test]
+    fn test_mint_run_registry() {
+        let (init_result, mut deps) = init_helper();
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let clones = |quantity: u32| HandleMsg::MintNftClones {
+            mint_run_id: Some("A".to_string()),
+            quantity,
+            owner: None,
+            public_metadata: None,
+            private_metadata: None,
+            royalty_info: None,
+            memo: None,
+            listing: None,
+            editions: None,
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), clones(2));
+        let handle_msg = HandleMsg::AddMinters {
+            minters: vec![HumanAddr("alice".to_string())],
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let _handle_result = handle(&mut deps, mock_env("alice", &[]), clones(3));
+        let handle_msg = HandleMsg::SetViewingKey {
+            key: "akey".to_string(),
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let viewer = Some(ViewerInfo {
+            address: HumanAddr("admin".to_string()),
+            viewing_key: "akey".to_string(),
+        });
+
+        // test the registry is private when the token supply is private
+        let query_msg = QueryMsg::MintRuns {
+            creator: None,
+            viewer: None,
+            start_after: None,
+            limit: None,
+        };
+        let query_result = query(&deps, query_msg);
+        let error = extract_error_msg(query_result);
+        assert!(error.contains("The token supply of this contract is private"));
+
+        let query_msg = QueryMsg::MintRuns {
+            creator: None,
+            viewer: viewer.clone(),
+            start_after: None,
+            limit: None,
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::MintRuns { mint_runs } => {
+                assert_eq!(mint_runs.len(), 2);
+                assert_eq!(
+                    mint_runs[0],
+                    MintRun {
+                        index: 0,
+                        run_id: "A".to_string(),
+                        run_number: 1,
+                        creator: HumanAddr("admin".to_string()),
+                        quantity: 2,
+                        minted: 2,
+                        first_token: Some("0".to_string()),
+                        last_token: Some("1".to_string()),
+                        time_of_minting: mock_env("admin", &[]).block.time,
+                    }
+                );
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // test filtering by creator and paginating
+        let query_msg = QueryMsg::MintRuns {
+            creator: Some(HumanAddr("alice".to_string())),
+            viewer: viewer.clone(),
+            start_after: None,
+            limit: None,
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::MintRuns { mint_runs } => {
+                assert_eq!(mint_runs.len(), 1);
+                assert_eq!(mint_runs[0].run_number, 2);
+                assert_eq!(mint_runs[0].first_token, Some("2".to_string()));
+                assert_eq!(mint_runs[0].last_token, Some("4".to_string()));
+            }
+            _ => panic!("unexpected"),
+        }
+        let query_msg = QueryMsg::MintRuns {
+            creator: None,
+            viewer: viewer.clone(),
+            start_after: Some(0),
+            limit: Some(5),
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::MintRuns { mint_runs } => {
+                assert_eq!(mint_runs.len(), 1);
+                assert_eq!(mint_runs[0].index, 1);
+            }
+            _ => panic!("unexpected"),
+        }
+
+        let query_msg = QueryMsg::TokensInMintRun {
+            run_id: "A".to_string(),
+            run_number: 2,
+            viewer: viewer.clone(),
+            start_after: None,
+            limit: None,
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::TokensInMintRun {
+                token_ids,
+                last_position,
+            } => {
+                assert_eq!(
+                    token_ids,
+                    vec!["2".to_string(), "3".to_string(), "4".to_string()]
+                );
+                assert_eq!(last_position, Some(2));
+            }
+            _ => panic!("unexpected"),
+        }
+        // test pagination
+        let query_msg = QueryMsg::TokensInMintRun {
+            run_id: "A".to_string(),
+            run_number: 2,
+            viewer: viewer.clone(),
+            start_after: None,
+            limit: Some(2),
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::TokensInMintRun {
+                token_ids,
+                last_position,
+            } => {
+                assert_eq!(token_ids, vec!["2".to_string(), "3".to_string()]);
+                assert_eq!(last_position, Some(1));
+            }
+            _ => panic!("unexpected"),
+        }
+        let query_msg = QueryMsg::TokensInMintRun {
+            run_id: "A".to_string(),
+            run_number: 2,
+            viewer: viewer.clone(),
+            start_after: Some(1),
+            limit: Some(2),
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::TokensInMintRun {
+                token_ids,
+                last_position,
+            } => {
+                assert_eq!(token_ids, vec!["4".to_string()]);
+                assert_eq!(last_position, Some(2));
+            }
+            _ => panic!("unexpected"),
+        }
+        let query_msg = QueryMsg::TokensInMintRun {
+            run_id: "A".to_string(),
+            run_number: 3,
+            viewer,
+            start_after: None,
+            limit: None,
+        };
+        let query_result = query(&deps, query_msg);
+        let error = extract_error_msg(query_result);
+        assert!(error.contains("Mint run 3 of mint run ID A is not registered"));
+    }
+
+    #[test]
+    fn test_editions() {
+        let (init_result, mut deps) = init_helper();
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let shared = Metadata {
+            token_uri: None,
+            extension: Some(Extension {
+                name: Some("Print".to_string()),
+                attributes: Some(vec![Trait {
+                    display_type: None,
+                    trait_type: Some("Artist".to_string()),
+                    value: "Ann".to_string(),
+                    max_value: None,
+                }]),
+                ..Extension::default()
+            }),
+        };
+        let gold = Trait {
+            display_type: None,
+            trait_type: Some("Frame".to_string()),
+            value: "Gold".to_string(),
+            max_value: None,
+        };
+        let editions_msg = |editions: Vec<EditionOverride>| HandleMsg::MintNftClones {
+            mint_run_id: Some("Prints".to_string()),
+            quantity: 2,
+            owner: None,
+            public_metadata: Some(shared.clone()),
+            private_metadata: None,
+            royalty_info: None,
+            memo: None,
+            listing: None,
+            editions: Some(editions),
+            padding: None,
+        };
+
+        // test the number of overrides must match the quantity
+        let handle_result = handle(
+            &mut deps,
+            mock_env("admin", &[]),
+            editions_msg(vec![EditionOverride::default()]),
+        );
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("Provide either one edition override per clone or none"));
+
+        let overrides = vec![
+            EditionOverride {
+                name: Some("Print (Artist Proof)".to_string()),
+                attributes: None,
+            },
+            EditionOverride {
+                name: None,
+                attributes: Some(vec![gold.clone()]),
+            },
+        ];
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), editions_msg(overrides));
+        assert!(handle_result.is_ok());
+
+        // the shared metadata is stored once, and only the overrides per token
+        let pub_store = ReadonlyPrefixedStorage::new(PREFIX_PUB_META, &deps.storage);
+        let stored: Option<Metadata> = may_load(&pub_store, &0u32.to_le_bytes()).unwrap();
+        assert!(stored.is_none());
+
+        let edition_trait = |edition: &str| Trait {
+            display_type: Some("number".to_string()),
+            trait_type: Some("edition".to_string()),
+            value: edition.to_string(),
+            max_value: Some("2".to_string()),
+        };
+        let query_msg = QueryMsg::NftInfo {
+            token_id: "0".to_string(),
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::NftInfo { extension, .. } => {
+                let ext = extension.unwrap();
+                assert_eq!(ext.name, Some("Print (Artist Proof)".to_string()));
+                assert_eq!(
+                    ext.attributes,
+                    Some(vec![
+                        shared.extension.clone().unwrap().attributes.unwrap()[0].clone(),
+                        edition_trait("1"),
+                    ])
+                );
+            }
+            _ => panic!("unexpected"),
+        }
+        let query_msg = QueryMsg::NftDossier {
+            token_id: "1".to_string(),
+            viewer: None,
+            include_expired: None,
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::NftDossier {
+                public_metadata, ..
+            } => {
+                let ext = public_metadata.unwrap().extension.unwrap();
+                assert_eq!(ext.name, Some("Print".to_string()));
+                assert_eq!(
+                    ext.attributes,
+                    Some(vec![
+                        shared.extension.clone().unwrap().attributes.unwrap()[0].clone(),
+                        edition_trait("2"),
+                        gold.clone(),
+                    ])
+                );
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // test setting an edition's own metadata replaces the shared metadata
+        let own = Metadata {
+            token_uri: Some("https://uri".to_string()),
+            extension: None,
+        };
+        let handle_msg = HandleMsg::SetMetadata {
+            token_id: "1".to_string(),
+            public_metadata: Some(own.clone()),
+            private_metadata: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+        let query_msg = QueryMsg::NftInfo {
+            token_id: "1".to_string(),
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::NftInfo {
+                token_uri,
+                extension,
+                frozen: _,
+            } => {
+                assert_eq!(token_uri, own.token_uri);
+                assert!(extension.is_none());
+            }
+            _ => panic!("unexpected"),
+        }
+    }
 }
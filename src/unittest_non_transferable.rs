@@ -358,6 +358,7 @@ mod tests {
                 transferable: Some(false),
                 serial_number: None,
                 memo: None,
+                listing: None,
             },
             Mint {
                 token_id: Some("TryDefaultRoys".to_string()),
@@ -368,6 +369,7 @@ mod tests {
                 transferable: Some(false),
                 serial_number: None,
                 memo: None,
+                listing: None,
             },
         ];
 
@@ -453,6 +455,7 @@ mod tests {
                 transferable: Some(false),
                 serial_number: None,
                 memo: None,
+                listing: None,
             },
             Mint {
                 token_id: Some("NFT2".to_string()),
@@ -463,6 +466,7 @@ mod tests {
                 transferable: Some(false),
                 serial_number: None,
                 memo: None,
+                listing: None,
             },
         ];
 
@@ -571,6 +575,7 @@ mod tests {
                 transferable: Some(false),
                 serial_number: None,
                 memo: None,
+                listing: None,
             },
             Mint {
                 token_id: Some("NFT2".to_string()),
@@ -581,6 +586,7 @@ mod tests {
                 transferable: Some(false),
                 serial_number: None,
                 memo: None,
+                listing: None,
             },
             Mint {
                 token_id: Some("NFT3".to_string()),
@@ -591,6 +597,7 @@ mod tests {
                 transferable: Some(false),
                 serial_number: None,
                 memo: None,
+                listing: None,
             },
         ];
 
@@ -680,7 +687,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("Name1".to_string()),
                 description: Some("PubDesc1".to_string()),
-                image: Some("PubUri1".to_string()),
+                image: Some("https://PubUri1".to_string()),
                 ..Extension::default()
             }),
         };
@@ -689,7 +696,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("PrivName1".to_string()),
                 description: Some("PrivDesc1".to_string()),
-                image: Some("PrivUri1".to_string()),
+                image: Some("https://PrivUri1".to_string()),
                 ..Extension::default()
             }),
         };
@@ -733,6 +740,7 @@ mod tests {
                 private_metadata_is_public_expiration,
                 token_approvals,
                 inventory_approvals,
+                frozen: _,
             } => {
                 assert_eq!(owner, Some(alice.clone()));
                 assert_eq!(public_metadata, Some(public_meta.clone()));
@@ -814,6 +822,7 @@ mod tests {
                 transferable: Some(false),
                 serial_number: None,
                 memo: None,
+                listing: None,
             },
             Mint {
                 token_id: Some("NFT2".to_string()),
@@ -824,6 +833,7 @@ mod tests {
                 transferable: None,
                 serial_number: None,
                 memo: None,
+                listing: None,
             },
         ];
 
@@ -7,7 +7,7 @@ mod tests {
         AccessLevel, BatchNftDossierElement, Cw721Approval, HandleMsg, InitConfig, InitMsg, Mint,
         QueryAnswer, QueryMsg, Snip721Approval, Tx, TxAction, ViewerInfo,
     };
-    use crate::token::{Extension, Metadata};
+    use crate::token::{Extension, Metadata, Trait};
     use cosmwasm_std::testing::*;
     use cosmwasm_std::{
         from_binary, Binary, BlockInfo, Env, Extern, HumanAddr, InitResponse, MessageInfo,
@@ -148,6 +148,8 @@ mod tests {
                 minter_may_update_metadata,
                 owner_may_update_metadata,
                 burn_is_enabled,
+                max_royalty_bps,
+                sale_stats_are_public,
             } => {
                 assert_eq!(token_supply_is_public, false);
                 assert_eq!(owner_is_public, true);
@@ -156,6 +158,8 @@ mod tests {
                 assert_eq!(minter_may_update_metadata, true);
                 assert_eq!(owner_may_update_metadata, false);
                 assert_eq!(burn_is_enabled, true);
+                assert_eq!(max_royalty_bps, 10000);
+                assert_eq!(sale_stats_are_public, true);
             }
             _ => panic!("unexpected"),
         }
@@ -196,7 +200,7 @@ mod tests {
         );
         let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
         match query_answer {
-            QueryAnswer::Minters { minters } => {
+            QueryAnswer::Minters { minters, .. } => {
                 assert_eq!(minters.len(), 3);
                 assert!(minters.contains(&alice));
                 assert!(minters.contains(&bob));
@@ -225,7 +229,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("My1".to_string()),
                     description: Some("Public 1".to_string()),
-                    image: Some("URI 1".to_string()),
+                    image: Some("https://URI 1".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -245,7 +249,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("My2".to_string()),
                     description: Some("Public 2".to_string()),
-                    image: Some("URI 2".to_string()),
+                    image: Some("https://URI 2".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -333,7 +337,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("My1".to_string()),
                     description: Some("Public 1".to_string()),
-                    image: Some("URI 1".to_string()),
+                    image: Some("https://URI 1".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -353,7 +357,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("My2".to_string()),
                     description: Some("Public 2".to_string()),
-                    image: Some("URI 2".to_string()),
+                    image: Some("https://URI 2".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -406,7 +410,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("My1".to_string()),
                     description: Some("Public 1".to_string()),
-                    image: Some("URI 1".to_string()),
+                    image: Some("https://URI 1".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -426,7 +430,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("My2".to_string()),
                     description: Some("Public 2".to_string()),
-                    image: Some("URI 2".to_string()),
+                    image: Some("https://URI 2".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -446,7 +450,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("My3".to_string()),
                     description: Some("Public 3".to_string()),
-                    image: Some("URI 3".to_string()),
+                    image: Some("https://URI 3".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -543,7 +547,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("My1".to_string()),
                     description: Some("Public 1".to_string()),
-                    image: Some("URI 1".to_string()),
+                    image: Some("https://URI 1".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -563,7 +567,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("My2".to_string()),
                     description: Some("Public 2".to_string()),
-                    image: Some("URI 2".to_string()),
+                    image: Some("https://URI 2".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -583,7 +587,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("My3".to_string()),
                     description: Some("Public 3".to_string()),
-                    image: Some("URI 3".to_string()),
+                    image: Some("https://URI 3".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -603,7 +607,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("My5".to_string()),
                     description: Some("Public 5".to_string()),
-                    image: Some("URI 5".to_string()),
+                    image: Some("https://URI 5".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -623,7 +627,7 @@ mod tests {
                 extension: Some(Extension {
                     name: Some("My4".to_string()),
                     description: Some("Public 4".to_string()),
-                    image: Some("URI 4".to_string()),
+                    image: Some("https://URI 4".to_string()),
                     ..Extension::default()
                 }),
             }),
@@ -747,7 +751,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("Name1".to_string()),
                 description: Some("PubDesc1".to_string()),
-                image: Some("PubUri1".to_string()),
+                image: Some("https://PubUri1".to_string()),
                 ..Extension::default()
             }),
         };
@@ -756,7 +760,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("PrivName1".to_string()),
                 description: Some("PrivDesc1".to_string()),
-                image: Some("PrivUri1".to_string()),
+                image: Some("https://PrivUri1".to_string()),
                 ..Extension::default()
             }),
         };
@@ -841,6 +845,7 @@ mod tests {
                 private_metadata_is_public_expiration,
                 token_approvals,
                 inventory_approvals,
+                frozen: _,
             } => {
                 assert_eq!(owner, Some(alice.clone()));
                 assert_eq!(public_metadata, Some(public_meta.clone()));
@@ -945,6 +950,7 @@ mod tests {
                 private_metadata_is_public_expiration,
                 token_approvals,
                 inventory_approvals,
+                frozen: _,
             } => {
                 assert_eq!(owner, Some(alice.clone()));
                 assert_eq!(public_metadata, Some(public_meta.clone()));
@@ -1046,6 +1052,7 @@ mod tests {
                 private_metadata_is_public_expiration,
                 token_approvals,
                 inventory_approvals,
+                frozen: _,
             } => {
                 assert_eq!(owner, Some(alice.clone()));
                 assert_eq!(public_metadata, Some(public_meta.clone()));
@@ -1150,6 +1157,7 @@ mod tests {
                 private_metadata_is_public_expiration,
                 token_approvals,
                 inventory_approvals,
+                frozen: _,
             } => {
                 assert_eq!(owner, Some(alice.clone()));
                 assert_eq!(public_metadata, Some(public_meta.clone()));
@@ -1196,6 +1204,7 @@ mod tests {
                 private_metadata_is_public_expiration,
                 token_approvals,
                 inventory_approvals,
+                frozen: _,
             } => {
                 assert_eq!(owner, Some(alice.clone()));
                 assert_eq!(public_metadata, Some(public_meta.clone()));
@@ -1329,6 +1338,7 @@ mod tests {
                 private_metadata_is_public_expiration,
                 token_approvals,
                 inventory_approvals,
+                frozen: _,
             } => {
                 assert_eq!(owner, Some(alice.clone()));
                 assert_eq!(public_metadata, Some(public_meta.clone()));
@@ -1384,6 +1394,7 @@ mod tests {
                 private_metadata_is_public_expiration,
                 token_approvals,
                 inventory_approvals,
+                frozen: _,
             } => {
                 assert!(owner.is_none());
                 assert_eq!(public_metadata, Some(public_meta.clone()));
@@ -2428,6 +2439,7 @@ mod tests {
             QueryAnswer::NftInfo {
                 token_uri,
                 extension,
+                frozen: _,
             } => {
                 assert!(token_uri.is_none());
                 assert!(extension.is_none());
@@ -2436,7 +2448,7 @@ mod tests {
         }
         let alice = HumanAddr("alice".to_string());
         let public_meta = Metadata {
-            token_uri: Some("uri".to_string()),
+            token_uri: Some("https://uri".to_string()),
             extension: None,
         };
         let handle_msg = HandleMsg::MintNft {
@@ -2462,6 +2474,7 @@ mod tests {
             QueryAnswer::NftInfo {
                 token_uri,
                 extension,
+                frozen: _,
             } => {
                 assert_eq!(token_uri, public_meta.token_uri);
                 assert_eq!(extension, public_meta.extension);
@@ -2489,11 +2502,11 @@ mod tests {
         let _handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
 
         let meta_for_fail = Metadata {
-            token_uri: Some("uri".to_string()),
+            token_uri: Some("https://uri".to_string()),
             extension: Some(Extension {
                 name: Some("Name1".to_string()),
                 description: Some("PubDesc1".to_string()),
-                image: Some("PubUri1".to_string()),
+                image: Some("https://PubUri1".to_string()),
                 ..Extension::default()
             }),
         };
@@ -2503,7 +2516,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("Name1".to_string()),
                 description: Some("PubDesc1".to_string()),
-                image: Some("PubUri1".to_string()),
+                image: Some("https://PubUri1".to_string()),
                 ..Extension::default()
             }),
         };
@@ -2631,7 +2644,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("Name1".to_string()),
                 description: Some("PrivDesc1".to_string()),
-                image: Some("PrivUri1".to_string()),
+                image: Some("https://PrivUri1".to_string()),
                 ..Extension::default()
             }),
         };
@@ -2668,6 +2681,7 @@ mod tests {
             QueryAnswer::PrivateMetadata {
                 token_uri,
                 extension,
+                unlockable: _,
             } => {
                 assert_eq!(token_uri, private_meta.token_uri);
                 assert_eq!(extension, private_meta.extension);
@@ -2695,6 +2709,7 @@ mod tests {
             QueryAnswer::PrivateMetadata {
                 token_uri,
                 extension,
+                unlockable: _,
             } => {
                 assert_eq!(token_uri, private_meta.token_uri);
                 assert_eq!(extension, private_meta.extension);
@@ -2727,7 +2742,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("Name1".to_string()),
                 description: Some("PrivDesc1".to_string()),
-                image: Some("PrivUri1".to_string()),
+                image: Some("https://PrivUri1".to_string()),
                 ..Extension::default()
             }),
         };
@@ -2777,6 +2792,7 @@ mod tests {
             QueryAnswer::PrivateMetadata {
                 token_uri,
                 extension,
+                unlockable: _,
             } => {
                 assert!(token_uri.is_none());
                 assert!(extension.is_none());
@@ -3790,6 +3806,7 @@ mod tests {
                 serial_number: None,
                 transferable: None,
                 memo: None,
+                listing: None,
             },
             Mint {
                 token_id: Some("NFT2".to_string()),
@@ -3800,6 +3817,7 @@ mod tests {
                 serial_number: None,
                 transferable: None,
                 memo: None,
+                listing: None,
             },
             Mint {
                 token_id: Some("NFT3".to_string()),
@@ -3810,6 +3828,7 @@ mod tests {
                 serial_number: None,
                 transferable: None,
                 memo: None,
+                listing: None,
             },
         ];
 
@@ -4287,7 +4306,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("Name1".to_string()),
                 description: Some("PubDesc1".to_string()),
-                image: Some("PubUri1".to_string()),
+                image: Some("https://PubUri1".to_string()),
                 ..Extension::default()
             }),
         };
@@ -4296,7 +4315,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("PrivName1".to_string()),
                 description: Some("PrivDesc1".to_string()),
-                image: Some("PrivUri1".to_string()),
+                image: Some("https://PrivUri1".to_string()),
                 ..Extension::default()
             }),
         };
@@ -4305,7 +4324,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("Name2".to_string()),
                 description: Some("PubDesc2".to_string()),
-                image: Some("PubUri2".to_string()),
+                image: Some("https://PubUri2".to_string()),
                 ..Extension::default()
             }),
         };
@@ -4314,7 +4333,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("PrivName2".to_string()),
                 description: Some("PrivDesc2".to_string()),
-                image: Some("PrivUri2".to_string()),
+                image: Some("https://PrivUri2".to_string()),
                 ..Extension::default()
             }),
         };
@@ -4324,7 +4343,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("Name3".to_string()),
                 description: Some("PubDesc3".to_string()),
-                image: Some("PubUri3".to_string()),
+                image: Some("https://PubUri3".to_string()),
                 ..Extension::default()
             }),
         };
@@ -4333,7 +4352,7 @@ mod tests {
             extension: Some(Extension {
                 name: Some("PrivName3".to_string()),
                 description: Some("PrivDesc3".to_string()),
-                image: Some("PrivUri3".to_string()),
+                image: Some("https://PrivUri3".to_string()),
                 ..Extension::default()
             }),
         };
@@ -4348,6 +4367,7 @@ mod tests {
                 serial_number: None,
                 transferable: None,
                 memo: None,
+                listing: None,
             },
             Mint {
                 token_id: Some("NFT2".to_string()),
@@ -4358,6 +4378,7 @@ mod tests {
                 serial_number: None,
                 transferable: None,
                 memo: None,
+                listing: None,
             },
             Mint {
                 token_id: Some("NFT3".to_string()),
@@ -4368,6 +4389,7 @@ mod tests {
                 serial_number: None,
                 transferable: None,
                 memo: None,
+                listing: None,
             },
         ];
 
@@ -4420,6 +4442,7 @@ mod tests {
                 private_metadata_is_public_expiration: None,
                 token_approvals: Some(Vec::new()),
                 inventory_approvals: Some(Vec::new()),
+                frozen: false,
             },
             BatchNftDossierElement {
                 token_id: "NFT2".to_string(),
@@ -4437,6 +4460,7 @@ mod tests {
                 private_metadata_is_public_expiration: None,
                 token_approvals: Some(Vec::new()),
                 inventory_approvals: Some(Vec::new()),
+                frozen: false,
             },
             // last one belongs to bob, so you can only see public info
             BatchNftDossierElement {
@@ -4457,6 +4481,7 @@ mod tests {
                 private_metadata_is_public_expiration: None,
                 token_approvals: None,
                 inventory_approvals: None,
+                frozen: false,
             },
         ];
         let query_result = query(&deps, query_msg);
@@ -4468,4 +4493,264 @@ mod tests {
             _ => panic!("unexpected"),
         }
     }
+
+    // test the trait-frequency index and rarity queries
+    #[test]
+    fn test_token_rarity() {
+        let (init_result, mut deps) =
+            init_helper_with_config(true, true, false, false, true, false, true);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let metadata = |traits: &[(&str, &str)]| {
+            let mut attributes: Vec<Trait> = traits
+                .iter()
+                .map(|(trait_type, value)| Trait {
+                    display_type: None,
+                    trait_type: Some(trait_type.to_string()),
+                    value: value.to_string(),
+                    max_value: None,
+                })
+                .collect();
+            // numeric traits do not count toward rarity
+            attributes.push(Trait {
+                display_type: Some("number".to_string()),
+                trait_type: Some("level".to_string()),
+                value: "1".to_string(),
+                max_value: None,
+            });
+            Some(Metadata {
+                token_uri: None,
+                extension: Some(Extension {
+                    attributes: Some(attributes),
+                    ..Extension::default()
+                }),
+            })
+        };
+        let tokens = [
+            ("NFT1", metadata(&[("background", "red"), ("hat", "cap")])),
+            ("NFT2", metadata(&[("background", "red"), ("hat", "crown")])),
+            ("NFT3", metadata(&[("background", "blue")])),
+        ];
+        for (id, public_metadata) in tokens.iter() {
+            let handle_msg = HandleMsg::MintNft {
+                token_id: Some(id.to_string()),
+                owner: Some(HumanAddr("alice".to_string())),
+                public_metadata: public_metadata.clone(),
+                private_metadata: None,
+                royalty_info: None,
+                serial_number: None,
+                transferable: None,
+                memo: None,
+                padding: None,
+            };
+            let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        }
+        let trait_counts = |deps: &Extern<MockStorage, MockApi, MockQuerier>| {
+            let query_result = query(deps, QueryMsg::TraitCounts { viewer: None });
+            match from_binary(&query_result.unwrap()).unwrap() {
+                QueryAnswer::TraitCounts {
+                    total_tokens,
+                    traits,
+                } => (
+                    total_tokens,
+                    traits
+                        .into_iter()
+                        .map(|t| (t.trait_type, t.value, t.count))
+                        .collect::<Vec<_>>(),
+                ),
+                _ => panic!("unexpected"),
+            }
+        };
+        let count = |trait_type: &str, value: &str, count: u32| {
+            (trait_type.to_string(), value.to_string(), count)
+        };
+        assert_eq!(
+            trait_counts(&deps),
+            (
+                3,
+                vec![
+                    count("background", "red", 2),
+                    count("background", "blue", 1),
+                    count("hat", "cap", 1),
+                    count("hat", "crown", 1),
+                ]
+            )
+        );
+
+        // test the rarity score sums total / count for each trait
+        let query_msg = QueryMsg::TokenRarity {
+            token_id: "NFT2".to_string(),
+            viewer: None,
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::TokenRarity {
+                rarity_score,
+                traits,
+                ..
+            } => {
+                assert_eq!(rarity_score, 4500);
+                assert_eq!(traits.len(), 2);
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // test updating metadata and burning keep the index current
+        let handle_msg = HandleMsg::SetMetadata {
+            token_id: "NFT3".to_string(),
+            public_metadata: metadata(&[("background", "red")]),
+            private_metadata: None,
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let handle_msg = HandleMsg::BurnNft {
+            token_id: "NFT1".to_string(),
+            memo: None,
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert_eq!(
+            trait_counts(&deps),
+            (
+                2,
+                vec![count("background", "red", 2), count("hat", "crown", 1)]
+            )
+        );
+    }
+
+    // test searching tokens by a public trait value
+    #[test]
+    fn test_tokens_by_trait() {
+        let (init_result, mut deps) =
+            init_helper_with_config(true, true, false, false, true, false, true);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+        let metadata = |hat: &str| {
+            Some(Metadata {
+                token_uri: None,
+                extension: Some(Extension {
+                    attributes: Some(vec![Trait {
+                        display_type: None,
+                        trait_type: Some("hat".to_string()),
+                        value: hat.to_string(),
+                        max_value: None,
+                    }]),
+                    ..Extension::default()
+                }),
+            })
+        };
+        for (id, hat) in [
+            ("NFT1", "cap"),
+            ("NFT2", "crown"),
+            ("NFT3", "cap"),
+            ("NFT4", "cap"),
+        ]
+        .iter()
+        {
+            let handle_msg = HandleMsg::MintNft {
+                token_id: Some(id.to_string()),
+                owner: Some(HumanAddr("alice".to_string())),
+                public_metadata: metadata(hat),
+                private_metadata: None,
+                royalty_info: None,
+                serial_number: None,
+                transferable: None,
+                memo: None,
+                padding: None,
+            };
+            let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        }
+        let search = |deps: &Extern<MockStorage, MockApi, MockQuerier>,
+                      value: &str,
+                      start_after: Option<&str>,
+                      limit: Option<u32>| {
+            let query_msg = QueryMsg::TokensByTrait {
+                trait_type: "hat".to_string(),
+                value: value.to_string(),
+                viewer: None,
+                start_after: start_after.map(|s| s.to_string()),
+                limit,
+            };
+            let query_result = query(deps, query_msg);
+            match from_binary(&query_result.unwrap()).unwrap() {
+                QueryAnswer::TokenList { tokens } => tokens,
+                _ => panic!("unexpected"),
+            }
+        };
+        assert_eq!(
+            search(&deps, "cap", None, None),
+            vec!["NFT1", "NFT3", "NFT4"]
+        );
+        // test pagination
+        assert_eq!(search(&deps, "cap", None, Some(2)), vec!["NFT1", "NFT3"]);
+        assert_eq!(search(&deps, "cap", Some("NFT3"), None), vec!["NFT4"]);
+        assert!(search(&deps, "beanie", None, None).is_empty());
+
+        // test updating metadata and burning keep the index current
+        let handle_msg = HandleMsg::SetMetadata {
+            token_id: "NFT2".to_string(),
+            public_metadata: metadata("cap"),
+            private_metadata: None,
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let handle_msg = HandleMsg::BurnNft {
+            token_id: "NFT3".to_string(),
+            memo: None,
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        assert_eq!(
+            search(&deps, "cap", None, None),
+            vec!["NFT1", "NFT2", "NFT4"]
+        );
+        assert!(search(&deps, "crown", None, None).is_empty());
+
+        // test paginating across several token buckets
+        let mints = (0..300)
+            .map(|i| Mint {
+                token_id: Some(format!("T{}", i)),
+                owner: Some(HumanAddr("alice".to_string())),
+                public_metadata: metadata(if i % 3 == 0 { "cap" } else { "beanie" }),
+                private_metadata: None,
+                royalty_info: None,
+                serial_number: None,
+                transferable: None,
+                memo: None,
+                listing: None,
+            })
+            .collect::<Vec<Mint>>();
+        let handle_msg = HandleMsg::BatchMintNft {
+            mints,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+        let mut expected: Vec<String> = ["NFT1", "NFT2", "NFT4"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        expected.extend((0..300).filter(|i| i % 3 == 0).map(|i| format!("T{}", i)));
+        let mut found: Vec<String> = Vec::new();
+        loop {
+            let page = search(&deps, "cap", found.last().map(|s| s.as_str()), Some(40));
+            if page.is_empty() {
+                break;
+            }
+            assert!(page.len() <= 40);
+            found.extend(page);
+        }
+        assert_eq!(found, expected);
+        assert_eq!(
+            search(&deps, "beanie", Some("T250"), Some(3)),
+            vec!["T251", "T253", "T254"]
+        );
+    }
 }
@@ -2,15 +2,17 @@
 mod tests {
     use crate::contract::{handle, init, query};
     use crate::msg::{
-        AccessLevel, ContractStatus, HandleMsg, InitConfig, InitMsg, PostInitCallback, QueryAnswer,
-        QueryMsg, ViewerInfo,
+        AccessLevel, ContractStatus, HandleMsg, InitConfig, InitMsg, Mint, PostInitCallback,
+        QueryAnswer, QueryMsg, SaleStatus, TxAction, ViewerInfo,
+    };
+    use crate::royalties::{
+        DisplayRoyalty, DisplayRoyaltyInfo, RecipientRoyalty, Royalty, RoyaltyInfo,
     };
-    use crate::royalties::{DisplayRoyalty, DisplayRoyaltyInfo, Royalty, RoyaltyInfo};
     use crate::state::{load, Config, CONFIG_KEY};
     use cosmwasm_std::testing::*;
     use cosmwasm_std::{
-        from_binary, to_binary, Api, Binary, Coin, CosmosMsg, Extern, HumanAddr, InitResponse,
-        StdError, StdResult, Uint128, WasmMsg,
+        coins, from_binary, to_binary, Api, Binary, Coin, CosmosMsg, Extern, HumanAddr,
+        InitResponse, StdError, StdResult, Uint128, WasmMsg,
     };
     use std::any::Any;
 
@@ -861,4 +863,356 @@ mod tests {
         let error = extract_error_msg(query_result);
         assert!(error.contains("Token ID: NFT not found"));
     }
+
+    #[test]
+    fn test_royalty_cap_and_updates_after_sale() {
+        let (init_result, mut deps) = init_helper_royalties(None);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+
+        // test only the admin may set the max royalty
+        let handle_msg = HandleMsg::SetMaxRoyalty {
+            max_royalty_bps: 2000,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("This is an admin command"));
+        let handle_msg = HandleMsg::SetMaxRoyalty {
+            max_royalty_bps: 2000,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+        let config: Config = load(&deps.storage, CONFIG_KEY).unwrap();
+        assert_eq!(config.max_royalty_bps, 2000);
+
+        // test the max royalty is enforced when minting
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some("TooHigh".to_string()),
+            owner: None,
+            public_metadata: None,
+            private_metadata: None,
+            royalty_info: Some(RoyaltyInfo {
+                decimal_places_in_rates: 3,
+                royalties: vec![Royalty {
+                    recipient: HumanAddr("charlie".to_string()),
+                    rate: 201,
+                }],
+            }),
+            serial_number: None,
+            transferable: None,
+            memo: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains("The sum of royalty rates must not exceed 2000 basis points"));
+
+        let handle_msg = HandleMsg::MintNft {
+            token_id: Some("NFT".to_string()),
+            owner: None,
+            public_metadata: None,
+            private_metadata: None,
+            royalty_info: Some(RoyaltyInfo {
+                decimal_places_in_rates: 2,
+                royalties: vec![Royalty {
+                    recipient: HumanAddr("charlie".to_string()),
+                    rate: 10,
+                }],
+            }),
+            serial_number: None,
+            transferable: None,
+            memo: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+
+        let handle_msg = HandleMsg::SetSaleStatus {
+            token_id: "NFT".to_string(),
+            sale_status: SaleStatus::ForSale,
+            price: Some(100),
+        };
+        let _handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let handle_msg = HandleMsg::BuyToken {
+            token_id: "NFT".to_string(),
+        };
+        let handle_result = handle(
+            &mut deps,
+            mock_env("alice", &coins(100, "uscrt")),
+            handle_msg,
+        );
+        assert!(handle_result.is_ok());
+
+        // test the new owner can not change royalties after the sale
+        let handle_msg = HandleMsg::SetRoyaltyInfo {
+            token_id: Some("NFT".to_string()),
+            royalty_info: None,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains(
+            "After a token's first sale, its royalties may only be changed by the token creator or the admin"
+        ));
+
+        // test the creator can not raise royalties after the sale
+        let handle_msg = HandleMsg::SetRoyaltyInfo {
+            token_id: Some("NFT".to_string()),
+            royalty_info: Some(RoyaltyInfo {
+                decimal_places_in_rates: 3,
+                royalties: vec![Royalty {
+                    recipient: HumanAddr("charlie".to_string()),
+                    rate: 101,
+                }],
+            }),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        let error = extract_error_msg(handle_result);
+        assert!(error.contains(
+            "After a token's first sale, its royalties may only be lowered or redirected"
+        ));
+
+        // test the creator can redirect royalties after the sale
+        let handle_msg = HandleMsg::SetRoyaltyInfo {
+            token_id: Some("NFT".to_string()),
+            royalty_info: Some(RoyaltyInfo {
+                decimal_places_in_rates: 3,
+                royalties: vec![
+                    Royalty {
+                        recipient: HumanAddr("charlie".to_string()),
+                        rate: 50,
+                    },
+                    Royalty {
+                        recipient: HumanAddr("bob".to_string()),
+                        rate: 50,
+                    },
+                ],
+            }),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+
+        // test the change is recorded in the owner's history
+        let handle_msg = HandleMsg::SetViewingKey {
+            key: "akey".to_string(),
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("alice", &[]), handle_msg);
+        let query_msg = QueryMsg::TransactionHistory {
+            address: HumanAddr("alice".to_string()),
+            viewing_key: "akey".to_string(),
+            page: None,
+            page_size: None,
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::TransactionHistory { txs, .. } => {
+                assert_eq!(txs[0].token_id, "NFT".to_string());
+                assert_eq!(
+                    txs[0].action,
+                    TxAction::SetRoyalties {
+                        changer: HumanAddr("admin".to_string()),
+                    }
+                );
+            }
+            _ => panic!("unexpected"),
+        }
+    }
+
+    #[test]
+    fn test_query_my_royalties() {
+        let (init_result, mut deps) = init_helper_royalties(None);
+        assert!(
+            init_result.is_ok(),
+            "Init failed: {}",
+            init_result.err().unwrap()
+        );
+
+        for id in ["NFT1", "NFT2"].iter() {
+            let handle_msg = HandleMsg::MintNft {
+                token_id: Some(id.to_string()),
+                owner: None,
+                public_metadata: None,
+                private_metadata: None,
+                royalty_info: Some(RoyaltyInfo {
+                    decimal_places_in_rates: 2,
+                    royalties: vec![Royalty {
+                        recipient: HumanAddr("charlie".to_string()),
+                        rate: 10,
+                    }],
+                }),
+                serial_number: None,
+                transferable: None,
+                memo: None,
+                padding: None,
+            };
+            let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+            assert!(handle_result.is_ok());
+        }
+        let handle_msg = HandleMsg::SetViewingKey {
+            key: "ckey".to_string(),
+            padding: None,
+        };
+        let _handle_result = handle(&mut deps, mock_env("charlie", &[]), handle_msg);
+
+        // sell NFT1 twice so the second sale pays royalties
+        for (seller, buyer, price) in [("admin", "alice", 100u32), ("alice", "bob", 1000)].iter() {
+            let handle_msg = HandleMsg::SetSaleStatus {
+                token_id: "NFT1".to_string(),
+                sale_status: SaleStatus::ForSale,
+                price: Some(*price),
+            };
+            let _handle_result = handle(&mut deps, mock_env(*seller, &[]), handle_msg);
+            let handle_msg = HandleMsg::BuyToken {
+                token_id: "NFT1".to_string(),
+            };
+            let handle_result = handle(
+                &mut deps,
+                mock_env(*buyer, &coins(*price as u128, "uscrt")),
+                handle_msg,
+            );
+            assert!(handle_result.is_ok());
+        }
+
+        // test wrong viewing key
+        let query_msg = QueryMsg::MyRoyalties {
+            address: HumanAddr("charlie".to_string()),
+            viewing_key: "akey".to_string(),
+            page: None,
+            page_size: None,
+        };
+        let query_result = query(&deps, query_msg);
+        let error = extract_error_msg(query_result);
+        assert!(error.contains("Wrong viewing key"));
+
+        let query_msg = QueryMsg::MyRoyalties {
+            address: HumanAddr("charlie".to_string()),
+            viewing_key: "ckey".to_string(),
+            page: None,
+            page_size: None,
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::MyRoyalties {
+                count,
+                royalties,
+                total_received,
+            } => {
+                assert_eq!(count, 2);
+                assert_eq!(
+                    royalties,
+                    vec![
+                        RecipientRoyalty {
+                            token_id: "NFT1".to_string(),
+                            decimal_places_in_rates: 2,
+                            rate: 10,
+                            received: coins(100, "uscrt"),
+                        },
+                        RecipientRoyalty {
+                            token_id: "NFT2".to_string(),
+                            decimal_places_in_rates: 2,
+                            rate: 10,
+                            received: vec![],
+                        },
+                    ]
+                );
+                assert_eq!(total_received, coins(100, "uscrt"));
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // test redirected royalties are removed from the list, but past earnings remain
+        let handle_msg = HandleMsg::SetRoyaltyInfo {
+            token_id: Some("NFT1".to_string()),
+            royalty_info: Some(RoyaltyInfo {
+                decimal_places_in_rates: 2,
+                royalties: vec![Royalty {
+                    recipient: HumanAddr("david".to_string()),
+                    rate: 10,
+                }],
+            }),
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+        let query_msg = QueryMsg::MyRoyalties {
+            address: HumanAddr("charlie".to_string()),
+            viewing_key: "ckey".to_string(),
+            page: None,
+            page_size: None,
+        };
+        let query_result = query(&deps, query_msg);
+        let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+        match query_answer {
+            QueryAnswer::MyRoyalties {
+                count,
+                royalties,
+                total_received,
+            } => {
+                assert_eq!(count, 1);
+                assert_eq!(royalties[0].token_id, "NFT2".to_string());
+                assert_eq!(total_received, coins(100, "uscrt"));
+            }
+            _ => panic!("unexpected"),
+        }
+
+        // test paginating across several token buckets
+        let mints = (0..300)
+            .map(|_| Mint {
+                token_id: None,
+                owner: None,
+                public_metadata: None,
+                private_metadata: None,
+                royalty_info: Some(RoyaltyInfo {
+                    decimal_places_in_rates: 2,
+                    royalties: vec![Royalty {
+                        recipient: HumanAddr("charlie".to_string()),
+                        rate: 10,
+                    }],
+                }),
+                serial_number: None,
+                transferable: None,
+                memo: None,
+                listing: None,
+            })
+            .collect::<Vec<Mint>>();
+        let handle_msg = HandleMsg::BatchMintNft {
+            mints,
+            padding: None,
+        };
+        let handle_result = handle(&mut deps, mock_env("admin", &[]), handle_msg);
+        assert!(handle_result.is_ok());
+        let mut expected = vec!["NFT2".to_string()];
+        expected.extend((2..302).map(|i| format!("{}", i)));
+        let mut found: Vec<String> = Vec::new();
+        for page in 0..7 {
+            let query_msg = QueryMsg::MyRoyalties {
+                address: HumanAddr("charlie".to_string()),
+                viewing_key: "ckey".to_string(),
+                page: Some(page),
+                page_size: Some(50),
+            };
+            let query_result = query(&deps, query_msg);
+            let query_answer: QueryAnswer = from_binary(&query_result.unwrap()).unwrap();
+            match query_answer {
+                QueryAnswer::MyRoyalties {
+                    count, royalties, ..
+                } => {
+                    assert_eq!(count, 301);
+                    found.extend(royalties.into_iter().map(|r| r.token_id));
+                }
+                _ => panic!("unexpected"),
+            }
+        }
+        assert_eq!(found, expected);
+    }
 }